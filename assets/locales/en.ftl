@@ -0,0 +1,271 @@
+# English, which the game falls back to for anything a translation leaves out.
+
+on = On
+off = Off
+unavailable = Unavailable
+back = Press Escape to go back
+loading = Loading
+
+score = Score: { $score }
+
+difficulty-easy = Easy
+difficulty-normal = Normal
+difficulty-hard = Hard
+
+menu-difficulty = Difficulty: { $difficulty }
+menu-preset = { $key } - { $difficulty }
+menu-daily = D - Daily challenge (best: { $best })
+menu-daily-played = D - Daily challenge (today: { $today }, best: { $best }, practice only)
+menu-modes = L - Lives mode, with three hearts   Z - Zen mode, to practice   X - Hardcore (best: { $best })
+menu-more-modes = C - Stages (stars: { $stars }/{ $max })   A - Watch the autopilot play   B - Versus, for two players
+menu-time-attack = I - Time attack to { $pipes } pipes   U - change target
+menu-time-attack-best = I - Time attack to { $pipes } pipes (best: { $best })   U - change target
+menu-mutators = G - Gravity flip: { $gravity_flip }   M - Mirror: { $mirror }   N - Night: { $night }
+menu-controls = Press { $key } or click to start, S for settings, H for high scores, O for the online leaderboard, T for stats, E for the level editor, V for neuroevolution, W for split-screen, K for credits, J for skins, Y for the shop, Tab for profiles
+
+tutorial-get-ready = Press { $key }, click or tap to flap.
+    Flap again and again to stay in the air.
+tutorial-flying = Fly through the gaps, every pipe you pass is a point.
+    Hit a pipe or the ground and it's over, press { $key } or tap to try again.
+
+get-ready = Get ready!
+get-ready-flap = Press { $key }, click or tap to flap
+paused = Paused
+paused-resume = Press { $key } to resume
+demo = Demo - press any key to play
+autopilot = Autopilot
+flap-button = FLAP
+
+settings-title = Settings
+settings-language = Language: { $language }
+settings-help = Up/Down to select, Left/Right to change, Enter to rebind, Escape to go back
+settings-master-volume = Master volume: { $percent }%
+settings-music-volume = Music volume: { $percent }%
+settings-difficulty = Difficulty: { $value }
+settings-weather = Weather: { $value }
+settings-window-mode = Window mode: { $value }
+settings-screen-scaling = Screen scaling: { $value }
+settings-screen-shake = Screen shake: { $percent }%
+settings-rumble = Rumble: { $percent }%
+settings-reduced-motion = Reduced motion: { $value }
+settings-reduce-flashing = Reduce flashing: { $value }
+settings-motion-trail = Motion trail: { $value }
+settings-crt-filter = CRT filter: { $value }
+settings-theme = Theme: { $value }
+settings-colors = Colors: { $value }
+settings-high-contrast = High contrast: { $value }
+settings-announce-score = Announce score: { $value }
+settings-tutorial = Show tutorial: { $value }
+settings-binding = { $action }: { $key }
+settings-rebinding = { $action }: press a key, Escape to cancel
+weather-clear = Clear
+weather-rain = Rain
+weather-snow = Snow
+window-fullscreen = Fullscreen
+window-windowed = Windowed
+scaling-letterbox = Letterbox
+scaling-fill = Fill
+theme-classic = Classic
+theme-dark = Dark
+palette-standard = Standard
+palette-deuteranopia = Deuteranopia
+palette-protanopia = Protanopia
+palette-tritanopia = Tritanopia
+action-flap = Flap
+action-restart = Restart
+action-pause = Pause
+
+game-over = Game over
+game-over-best = { $label }: { $score }
+game-over-new-best = { $label }: { $score } - new best!
+game-over-score = Score: { $score }
+game-over-pipes = Pipes passed: { $pipes }
+game-over-time = Time: { $time }
+game-over-coins = Coins: { $coins }
+game-over-medal = { $medal } medal
+best-endless = Best
+best-hardcore = Hardcore best
+best-goal = Goal
+medal-bronze = Bronze
+medal-silver = Silver
+medal-gold = Gold
+medal-platinum = Platinum
+button-restart = Restart
+button-lobby = Lobby
+button-menu = Menu
+
+achievement-unlocked = Achievement unlocked: { $name }
+    { $description }
+achievement-ten-pipes = Getting the hang of it
+achievement-ten-pipes-description = Pass 10 pipes in one run
+achievement-fifty-pipes = Frequent flyer
+achievement-fifty-pipes-description = Pass 50 pipes in one run
+achievement-quick-crash = That was quick
+achievement-quick-crash-description = Crash within 1 second
+achievement-ten-runs = Persistent
+achievement-ten-runs-description = Play 10 runs
+achievement-hundred-runs = Dedicated
+achievement-hundred-runs-description = Play 100 runs
+
+power-up-remaining = { $power_up } { $seconds }s
+power-up-shield = Shield
+power-up-slow-time = Slow time
+power-up-magnet = Magnet
+
+retry-help = R to retry, Esc for the menu
+combo = Combo x{ $multiplier }
+seed = Seed: { $seed }
+zen-misses = Misses: { $misses } - Q to finish
+splash-presents = presents
+speech-game-over = Game over, score { $score }
+
+stage-first-flight = First flight
+stage-stairs = Stairs
+stage-zigzag = Zigzag
+stage-canyon = Canyon
+stage-summit = Summit
+stage-progress = Stage { $stage }: { $name } - { $pipes }/{ $goal }
+stage-complete = Stage { $stage } complete!
+stage-coins = Coins: { $coins }/{ $goal }
+stage-next = Enter for the next stage, R to retry, Esc for the menu
+stage-last = Last stage cleared! R to retry, Esc for the menu
+
+time-attack-reached = { $pipes } pipes reached!
+time-attack-behind = Time: { $time } ({ $diff } behind the best)
+time-attack-best = Time: { $time } - new personal best!
+time-attack-split = Pipe { $pipe }
+
+replay-playing = Replay of a { $score } point run - Esc to stop
+replay-imported = Imported replay of a { $score } point run - Esc to stop
+replay-exported = Replay exported to { $location }
+replay-export-failed = Couldn't export the replay
+replay-unreadable = Not a replay that can be watched: { $error }
+
+editor-help = Click to place or drag a pair of pipes, right click to remove it
+    C - coin   B - spinning bar   +/- - gap size   Arrows or wheel - scroll
+    P - play   E - export   Esc - menu
+editor-exported = Exported to { $file }
+editor-empty = Place some pipes first
+
+split-controls-left = Space or W to flap
+split-controls-right = Up or gamepad to flap
+split-score = Player { $player } - pipes: { $pipes } - wins: { $wins }
+split-draw = Draw
+split-crashed = Crashed
+split-win = You win!
+split-over = { $result }
+    Flap for another round
+    Escape for the menu
+
+daily-official = Daily challenge { $day } - official attempt
+daily-practice = Daily challenge { $day } - practice
+evolution-status = Generation { $generation } - { $flying }/{ $population } flying - pipes: { $pipes } (best: { $best }) - Esc to leave
+gravity-flip-warning = { $arrow } Gravity flips in { $seconds } { $arrow }
+tas-status = TAS { $name } - tick { $tick } - flap { $flap }/{ $flaps } - . to step while paused
+
+new-high-score = New high score!
+initials-help = Type your initials or use the arrows, Enter to confirm
+high-scores-title = High scores
+high-scores-empty = No high scores yet
+
+leaderboard-title = Leaderboard
+leaderboard-loading = Loading...
+leaderboard-empty = No scores yet
+leaderboard-offline = { $reason }, showing your own high scores
+leaderboard-not-set-up = No online leaderboard set up
+leaderboard-unreachable = Couldn't reach the online leaderboard
+
+stats-title = Stats
+stats-runs = Runs
+stats-flaps = Flaps
+stats-pipes = Pipes passed
+stats-close-calls = Close calls
+stats-time = Time played
+stats-best = Best score
+stats-average = Average score
+
+credits-title = Credits
+credits-made-by = Made by
+credits-author = KHTangent
+credits-art = Sprites, sounds and music
+credits-art-license = Made for Flappy, under the MIT license like the code
+credits-font = DejaVu Sans in the dark theme, under the Bitstream Vera license
+credits-built-with = Built with
+credits-bevy = Bevy, under the MIT or Apache 2.0 license
+credits-crates = rand, serde and RON, under the MIT or Apache 2.0 license
+credits-license = License
+credits-license-file = MIT, see LICENSE.md
+credits-thanks = Thanks for playing!
+
+skins-title = Skins
+skins-help = Arrow keys to browse, Enter to wear, Escape to go back
+skins-worn = { $skin } (worn)
+skins-unlocked = Unlocked
+skins-locked = Locked: { $requirement }
+skins-always = Always unlocked
+skins-score = Score { $score } in one run
+skins-shop = Sold in the shop
+skin-classic = Classic
+skin-emerald = Emerald
+skin-crimson = Crimson
+skin-golden = Golden
+skin-violet = Violet
+skin-sunset = Sunset
+skin-teal = Teal
+skin-rose = Rose
+
+shop-title = Shop
+shop-coins = Coins: { $coins }
+shop-help = Enter to buy, or to equip what you own. Escape to go back
+shop-item = { $item }: { $status }
+shop-equipped = Equipped
+shop-owned = Owned
+shop-price = { $price } coins
+shop-skin = { $name } skin
+shop-trail = { $name } trail
+shop-pipes = { $name } pipes
+trail-bird = Bird
+trail-shadow = Shadow
+trail-gold = Gold
+trail-ice = Ice
+trail-ember = Ember
+pipes-classic = Classic
+pipes-copper = Copper
+pipes-ocean = Ocean
+pipes-amethyst = Amethyst
+
+profiles-title = Who's playing?
+profiles-playing = { $name } (playing)
+profiles-new = New profile
+profiles-naming = Name: { $name }_
+profiles-help = Enter to play, Delete to delete a profile, Escape to go back
+profiles-naming-help = Type a name, Enter to create it, Escape to cancel
+profiles-delete = Press Delete again to delete { $name } and everything kept for it
+
+versus-win = Player { $player } wins!
+versus-draw = Draw
+versus-score = Player { $player }: { $pipes }
+versus-hud = Player { $player } ({ $controls }): { $pipes }
+versus-hud-crashed = Player { $player } ({ $controls }): { $pipes } - crashed
+versus-controls-1 = Space/W
+versus-controls-2 = Up/gamepad
+
+race-lobby = Race lobby
+race-hosting = Hosting on port { $port }
+race-joining = Joining { $address }...
+race-joined = Joined { $address }
+race-last-win = Race { $race } won by { $player }
+race-racer = { $player } - { $wins } wins
+race-racer-you = { $player } - { $wins } wins (you)
+race-waiting-for-host = Waiting for the host to start the race - Escape to leave
+race-waiting-for-players = Waiting for players to join - Escape to leave
+race-start = Press Enter to start the race - Escape to leave
+race-full = The race is full
+race-host-left = The host left the race
+race-connection-lost = Lost the connection to the host
+race-unreachable = Couldn't reach the host
+race-standings = Race { $race } - { $flying }/{ $racers } flying
+race-flying = { $player }: { $score }
+race-out = { $player }: { $score } - out
+race-you-win = You win!
+race-win = { $player } wins race { $race }!
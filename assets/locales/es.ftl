@@ -0,0 +1,271 @@
+# Spanish.
+
+on = Sí
+off = No
+unavailable = No disponible
+back = Pulsa Escape para volver
+loading = Cargando
+
+score = Puntos: { $score }
+
+difficulty-easy = Fácil
+difficulty-normal = Normal
+difficulty-hard = Difícil
+
+menu-difficulty = Dificultad: { $difficulty }
+menu-preset = { $key } - { $difficulty }
+menu-daily = D - Reto diario (récord: { $best })
+menu-daily-played = D - Reto diario (hoy: { $today }, récord: { $best }, solo práctica)
+menu-modes = L - Modo vidas, con tres corazones   Z - Modo zen, para practicar   X - Extremo (récord: { $best })
+menu-more-modes = C - Niveles (estrellas: { $stars }/{ $max })   A - Ver jugar al piloto automático   B - Duelo, para dos jugadores
+menu-time-attack = I - Contrarreloj hasta { $pipes } tubos   U - cambiar objetivo
+menu-time-attack-best = I - Contrarreloj hasta { $pipes } tubos (récord: { $best })   U - cambiar objetivo
+menu-mutators = G - Gravedad invertida: { $gravity_flip }   M - Espejo: { $mirror }   N - Noche: { $night }
+menu-controls = Pulsa { $key } o haz clic para empezar, S para ajustes, H para récords, O para la clasificación en línea, T para estadísticas, E para el editor de niveles, V para neuroevolución, W para pantalla dividida, K para créditos, J para aspectos, Y para la tienda, Tab para perfiles
+
+tutorial-get-ready = Pulsa { $key }, haz clic o toca para aletear.
+    Aletea una y otra vez para mantenerte en el aire.
+tutorial-flying = Vuela entre los huecos, cada tubo que pasas es un punto.
+    Si chocas con un tubo o el suelo se acaba, pulsa { $key } o toca para volver a intentarlo.
+
+get-ready = ¡Prepárate!
+get-ready-flap = Pulsa { $key }, haz clic o toca para aletear
+paused = En pausa
+paused-resume = Pulsa { $key } para continuar
+demo = Demostración - pulsa cualquier tecla para jugar
+autopilot = Piloto automático
+flap-button = ALETEAR
+
+settings-title = Ajustes
+settings-language = Idioma: { $language }
+settings-help = Arriba/Abajo para elegir, Izquierda/Derecha para cambiar, Intro para reasignar, Escape para volver
+settings-master-volume = Volumen general: { $percent }%
+settings-music-volume = Volumen de la música: { $percent }%
+settings-difficulty = Dificultad: { $value }
+settings-weather = Clima: { $value }
+settings-window-mode = Modo de ventana: { $value }
+settings-screen-scaling = Escalado: { $value }
+settings-screen-shake = Temblor de pantalla: { $percent }%
+settings-rumble = Vibración: { $percent }%
+settings-reduced-motion = Movimiento reducido: { $value }
+settings-reduce-flashing = Reducir destellos: { $value }
+settings-motion-trail = Estela: { $value }
+settings-crt-filter = Filtro CRT: { $value }
+settings-theme = Tema: { $value }
+settings-colors = Colores: { $value }
+settings-high-contrast = Alto contraste: { $value }
+settings-announce-score = Anunciar puntos: { $value }
+settings-tutorial = Mostrar tutorial: { $value }
+settings-binding = { $action }: { $key }
+settings-rebinding = { $action }: pulsa una tecla, Escape para cancelar
+weather-clear = Despejado
+weather-rain = Lluvia
+weather-snow = Nieve
+window-fullscreen = Pantalla completa
+window-windowed = Ventana
+scaling-letterbox = Con bandas
+scaling-fill = Llenar
+theme-classic = Clásico
+theme-dark = Oscuro
+palette-standard = Estándar
+palette-deuteranopia = Deuteranopía
+palette-protanopia = Protanopía
+palette-tritanopia = Tritanopía
+action-flap = Aletear
+action-restart = Reintentar
+action-pause = Pausa
+
+game-over = Fin de la partida
+game-over-best = { $label }: { $score }
+game-over-new-best = { $label }: { $score } - ¡nuevo récord!
+game-over-score = Puntos: { $score }
+game-over-pipes = Tubos superados: { $pipes }
+game-over-time = Tiempo: { $time }
+game-over-coins = Monedas: { $coins }
+game-over-medal = Medalla de { $medal }
+best-endless = Récord
+best-hardcore = Récord extremo
+best-goal = Objetivo
+medal-bronze = bronce
+medal-silver = plata
+medal-gold = oro
+medal-platinum = platino
+button-restart = Reintentar
+button-lobby = Sala
+button-menu = Menú
+
+achievement-unlocked = Logro desbloqueado: { $name }
+    { $description }
+achievement-ten-pipes = Le vas pillando el truco
+achievement-ten-pipes-description = Supera 10 tubos en una partida
+achievement-fifty-pipes = Viajero frecuente
+achievement-fifty-pipes-description = Supera 50 tubos en una partida
+achievement-quick-crash = Qué rápido
+achievement-quick-crash-description = Choca en menos de 1 segundo
+achievement-ten-runs = Persistente
+achievement-ten-runs-description = Juega 10 partidas
+achievement-hundred-runs = Entregado
+achievement-hundred-runs-description = Juega 100 partidas
+
+power-up-remaining = { $power_up } { $seconds }s
+power-up-shield = Escudo
+power-up-slow-time = Cámara lenta
+power-up-magnet = Imán
+
+retry-help = R para reintentar, Esc para el menú
+combo = Combo x{ $multiplier }
+seed = Semilla: { $seed }
+zen-misses = Fallos: { $misses } - Q para terminar
+splash-presents = presenta
+speech-game-over = Fin de la partida, { $score } puntos
+
+stage-first-flight = Primer vuelo
+stage-stairs = Escaleras
+stage-zigzag = Zigzag
+stage-canyon = Cañón
+stage-summit = Cumbre
+stage-progress = Nivel { $stage }: { $name } - { $pipes }/{ $goal }
+stage-complete = ¡Nivel { $stage } superado!
+stage-coins = Monedas: { $coins }/{ $goal }
+stage-next = Intro para el siguiente nivel, R para reintentar, Esc para el menú
+stage-last = ¡Último nivel superado! R para reintentar, Esc para el menú
+
+time-attack-reached = ¡{ $pipes } tubos alcanzados!
+time-attack-behind = Tiempo: { $time } ({ $diff } por detrás del récord)
+time-attack-best = Tiempo: { $time } - ¡nuevo récord personal!
+time-attack-split = Tubo { $pipe }
+
+replay-playing = Repetición de una partida de { $score } puntos - Esc para parar
+replay-imported = Repetición importada de una partida de { $score } puntos - Esc para parar
+replay-exported = Repetición exportada a { $location }
+replay-export-failed = No se pudo exportar la repetición
+replay-unreadable = No es una repetición que se pueda ver: { $error }
+
+editor-help = Haz clic para colocar o arrastrar un par de tubos, clic derecho para quitarlo
+    C - moneda   B - barra giratoria   +/- - tamaño del hueco   Flechas o rueda - desplazar
+    P - jugar   E - exportar   Esc - menú
+editor-exported = Exportado a { $file }
+editor-empty = Coloca algunos tubos primero
+
+split-controls-left = Espacio o W para aletear
+split-controls-right = Arriba o mando para aletear
+split-score = Jugador { $player } - tubos: { $pipes } - victorias: { $wins }
+split-draw = Empate
+split-crashed = Chocaste
+split-win = ¡Has ganado!
+split-over = { $result }
+    Aletea para otra ronda
+    Escape para el menú
+
+daily-official = Reto diario { $day } - intento oficial
+daily-practice = Reto diario { $day } - práctica
+evolution-status = Generación { $generation } - { $flying }/{ $population } en vuelo - tubos: { $pipes } (récord: { $best }) - Esc para salir
+gravity-flip-warning = { $arrow } La gravedad se invierte en { $seconds } { $arrow }
+tas-status = TAS { $name } - tick { $tick } - aleteo { $flap }/{ $flaps } - . para avanzar en pausa
+
+new-high-score = ¡Nuevo récord!
+initials-help = Escribe tus iniciales o usa las flechas, Intro para confirmar
+high-scores-title = Récords
+high-scores-empty = Todavía no hay récords
+
+leaderboard-title = Clasificación
+leaderboard-loading = Cargando...
+leaderboard-empty = Todavía no hay puntuaciones
+leaderboard-offline = { $reason }, se muestran tus propios récords
+leaderboard-not-set-up = No hay clasificación en línea configurada
+leaderboard-unreachable = No se pudo conectar con la clasificación en línea
+
+stats-title = Estadísticas
+stats-runs = Partidas
+stats-flaps = Aleteos
+stats-pipes = Tubos superados
+stats-close-calls = Por los pelos
+stats-time = Tiempo jugado
+stats-best = Mejor puntuación
+stats-average = Puntuación media
+
+credits-title = Créditos
+credits-made-by = Hecho por
+credits-author = KHTangent
+credits-art = Gráficos, sonidos y música
+credits-art-license = Hechos para Flappy, con licencia MIT como el código
+credits-font = DejaVu Sans en el tema oscuro, con licencia Bitstream Vera
+credits-built-with = Hecho con
+credits-bevy = Bevy, con licencia MIT o Apache 2.0
+credits-crates = rand, serde y RON, con licencia MIT o Apache 2.0
+credits-license = Licencia
+credits-license-file = MIT, ver LICENSE.md
+credits-thanks = ¡Gracias por jugar!
+
+skins-title = Aspectos
+skins-help = Flechas para elegir, Intro para ponértelo, Escape para volver
+skins-worn = { $skin } (puesto)
+skins-unlocked = Desbloqueado
+skins-locked = Bloqueado: { $requirement }
+skins-always = Siempre desbloqueado
+skins-score = Consigue { $score } puntos en una partida
+skins-shop = A la venta en la tienda
+skin-classic = Clásico
+skin-emerald = Esmeralda
+skin-crimson = Carmesí
+skin-golden = Dorado
+skin-violet = Violeta
+skin-sunset = Atardecer
+skin-teal = Turquesa
+skin-rose = Rosa
+
+shop-title = Tienda
+shop-coins = Monedas: { $coins }
+shop-help = Intro para comprar, o para equipar lo que tienes. Escape para volver
+shop-item = { $item }: { $status }
+shop-equipped = Equipado
+shop-owned = Comprado
+shop-price = { $price } monedas
+shop-skin = Aspecto { $name }
+shop-trail = Estela { $name }
+shop-pipes = Tubos { $name }
+trail-bird = del pájaro
+trail-shadow = de sombra
+trail-gold = dorada
+trail-ice = de hielo
+trail-ember = de brasas
+pipes-classic = clásicos
+pipes-copper = de cobre
+pipes-ocean = oceánicos
+pipes-amethyst = de amatista
+
+profiles-title = ¿Quién juega?
+profiles-playing = { $name } (jugando)
+profiles-new = Nuevo perfil
+profiles-naming = Nombre: { $name }_
+profiles-help = Intro para jugar, Supr para borrar un perfil, Escape para volver
+profiles-naming-help = Escribe un nombre, Intro para crearlo, Escape para cancelar
+profiles-delete = Pulsa Supr otra vez para borrar { $name } y todo lo guardado para él
+
+versus-win = ¡Gana el jugador { $player }!
+versus-draw = Empate
+versus-score = Jugador { $player }: { $pipes }
+versus-hud = Jugador { $player } ({ $controls }): { $pipes }
+versus-hud-crashed = Jugador { $player } ({ $controls }): { $pipes } - se estrelló
+versus-controls-1 = Espacio/W
+versus-controls-2 = Arriba/mando
+
+race-lobby = Sala de carrera
+race-hosting = Alojando en el puerto { $port }
+race-joining = Uniéndose a { $address }...
+race-joined = Unido a { $address }
+race-last-win = Carrera { $race } ganada por { $player }
+race-racer = { $player } - { $wins } victorias
+race-racer-you = { $player } - { $wins } victorias (tú)
+race-waiting-for-host = Esperando a que el anfitrión empiece la carrera - Escape para salir
+race-waiting-for-players = Esperando a que se unan jugadores - Escape para salir
+race-start = Pulsa Intro para empezar la carrera - Escape para salir
+race-full = La carrera está llena
+race-host-left = El anfitrión ha dejado la carrera
+race-connection-lost = Se perdió la conexión con el anfitrión
+race-unreachable = No se pudo conectar con el anfitrión
+race-standings = Carrera { $race } - { $flying }/{ $racers } en vuelo
+race-flying = { $player }: { $score }
+race-out = { $player }: { $score } - fuera
+race-you-win = ¡Has ganado!
+race-win = ¡{ $player } gana la carrera { $race }!
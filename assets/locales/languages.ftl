@@ -0,0 +1,4 @@
+# Languages to pick from in the settings, by the code their file is named
+# after, each with the name it goes by in its own language.
+en = English
+es = Español
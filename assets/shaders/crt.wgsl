@@ -0,0 +1,42 @@
+// CRT look for the retro filter, see src/crt.rs: the picture bulges out a
+// little, every other row of pixels is darkened like the scanlines of an old
+// screen, and the corners fade to black.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct CrtSettings {
+	curvature: f32,
+	scanlines: f32,
+	vignette: f32,
+	brightness: f32,
+}
+
+@group(0) @binding(0) var screen_texture: texture_2d<f32>;
+@group(0) @binding(1) var texture_sampler: sampler;
+@group(0) @binding(2) var<uniform> settings: CrtSettings;
+
+// Pushes points outwards the further they are from the middle.
+fn curve(uv: vec2<f32>) -> vec2<f32> {
+	let centered = uv * 2.0 - 1.0;
+	let bent = centered * (1.0 + settings.curvature * dot(centered.yx, centered.yx));
+	return bent * 0.5 + 0.5;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+	let uv = curve(in.uv);
+	var color = textureSample(screen_texture, texture_sampler, uv).rgb;
+
+	let height = f32(textureDimensions(screen_texture).y);
+	let row = sin(uv.y * height * 3.14159265);
+	color *= 1.0 - settings.scanlines * (1.0 - row * row);
+
+	let centered = uv * 2.0 - 1.0;
+	let vignette = 1.0 - settings.vignette * dot(centered, centered) * 0.5;
+	color *= clamp(vignette, 0.0, 1.0) * settings.brightness;
+
+	// Black past the bent edges of the screen. Sampling has to happen either
+	// way, outside of any branch.
+	let inside = all(uv >= vec2(0.0)) && all(uv <= vec2(1.0));
+	return vec4(select(vec3(0.0), color, inside), 1.0);
+}
@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+#[bevy_main]
+fn main() {
+	flappy::app().run();
+}
@@ -0,0 +1,159 @@
+//! Achievements unlocked by playing, announced with a toast when they are.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{locale::Locale, save::SaveData, theme::UiTheme, GameplayEvent};
+
+const TOAST_DURATION: f32 = 3.0;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+	TenPipes,
+	FiftyPipes,
+	QuickCrash,
+	TenRuns,
+	HundredRuns,
+}
+
+impl Achievement {
+	const ALL: [Achievement; 5] = [
+		Achievement::TenPipes,
+		Achievement::FiftyPipes,
+		Achievement::QuickCrash,
+		Achievement::TenRuns,
+		Achievement::HundredRuns,
+	];
+
+	/// Message of the name, with `-description` added for the description.
+	fn key(self) -> &'static str {
+		match self {
+			Achievement::TenPipes => "achievement-ten-pipes",
+			Achievement::FiftyPipes => "achievement-fifty-pipes",
+			Achievement::QuickCrash => "achievement-quick-crash",
+			Achievement::TenRuns => "achievement-ten-runs",
+			Achievement::HundredRuns => "achievement-hundred-runs",
+		}
+	}
+
+	pub fn name(self, locale: &Locale) -> String {
+		locale.text(self.key())
+	}
+
+	pub fn description(self, locale: &Locale) -> String {
+		locale.text(&format!("{}-description", self.key()))
+	}
+
+	/// Whether the event earns the achievement, given the progress so far.
+	fn earned_by(self, event: &GameplayEvent, record: &AchievementRecord) -> bool {
+		match (self, *event) {
+			(Achievement::TenPipes, GameplayEvent::PipePassed(passed)) => passed >= 10,
+			(Achievement::FiftyPipes, GameplayEvent::PipePassed(passed)) => passed >= 50,
+			(Achievement::QuickCrash, GameplayEvent::RunEnded { seconds }) => seconds < 1.0,
+			(Achievement::TenRuns, GameplayEvent::RunEnded { .. }) => record.runs_played >= 10,
+			(Achievement::HundredRuns, GameplayEvent::RunEnded { .. }) => record.runs_played >= 100,
+			_ => false,
+		}
+	}
+}
+
+/// Persisted achievement progress.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct AchievementRecord {
+	pub unlocked: Vec<Achievement>,
+	/// Live runs played to the end.
+	pub runs_played: u32,
+}
+
+/// Holds the toasts, stacked at the top of the screen.
+#[derive(Component)]
+struct ToastArea;
+
+/// Counts down, in real time, to the toast going away.
+#[derive(Component)]
+struct Toast(Timer);
+
+fn spawn_toast_area(mut commands: Commands) {
+	commands.spawn((
+		ToastArea,
+		Node {
+			position_type: PositionType::Absolute,
+			width: Val::Percent(100.0),
+			top: Val::Px(16.0),
+			flex_direction: FlexDirection::Column,
+			align_items: AlignItems::Center,
+			row_gap: Val::Px(8.0),
+			..default()
+		},
+		// Above every other screen.
+		GlobalZIndex(1),
+	));
+}
+
+fn track_achievements(
+	mut commands: Commands,
+	mut events: EventReader<GameplayEvent>,
+	mut save: ResMut<SaveData>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+	toast_area: Single<Entity, With<ToastArea>>,
+) {
+	for event in events.read() {
+		let record = &mut save.achievements;
+		if matches!(event, GameplayEvent::RunEnded { .. }) {
+			record.runs_played += 1;
+		}
+		for achievement in Achievement::ALL {
+			if record.unlocked.contains(&achievement) || !achievement.earned_by(event, record) {
+				continue;
+			}
+			record.unlocked.push(achievement);
+			commands.entity(*toast_area).with_child((
+				Toast(Timer::from_seconds(TOAST_DURATION, TimerMode::Once)),
+				Text::new(locale.format(
+					"achievement-unlocked",
+					&[
+						("name", &achievement.name(&locale)),
+						("description", &achievement.description(&locale)),
+					],
+				)),
+				theme.text(24.0),
+				TextLayout::new_with_justify(JustifyText::Center),
+				Node {
+					padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+					..default()
+				},
+				BackgroundColor(theme.button),
+				BorderRadius::all(Val::Px(theme.button_radius)),
+			));
+		}
+	}
+}
+
+/// Real time keeps toasts moving while the game is paused or slowed down.
+fn expire_toasts(
+	mut commands: Commands,
+	toasts: Query<(Entity, &mut Toast)>,
+	time: Res<Time<Real>>,
+) {
+	for (entity, mut toast) in toasts {
+		if toast.0.tick(time.delta()).finished() {
+			commands.entity(entity).despawn();
+		}
+	}
+}
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(Startup, spawn_toast_area).add_systems(
+			Update,
+			(
+				track_achievements.run_if(crate::counts_for_progress),
+				expire_toasts,
+			),
+		);
+	}
+}
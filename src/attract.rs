@@ -0,0 +1,128 @@
+//! The attract mode, which plays back the last recorded run behind the title
+//! once the main menu has sat idle for a while, like arcade cabinets do, or
+//! lets the autopilot play one if there's no recording yet. Any key or button
+//! brings the menu back.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit, input::PlayerInput, locale::Locale, menu::MenuScreen,
+	replay::LastReplay, theme::UiTheme, timed_transition::TimedTransition, GameMode, GameStates,
+	InRun,
+};
+
+/// Seconds without input on the main menu before the demo starts.
+const ATTRACT_IDLE: f32 = 15.0;
+/// Seconds the crash stays on screen before the demo goes back to the menu.
+const DEMO_END_DELAY: f32 = 3.0;
+
+/// Set while a demo plays, as opposed to a replay the player asked for.
+#[derive(Resource)]
+struct AttractDemo;
+
+#[derive(Resource, Deref, DerefMut)]
+struct IdleTimer(Timer);
+
+impl Default for IdleTimer {
+	fn default() -> Self {
+		IdleTimer(Timer::from_seconds(ATTRACT_IDLE, TimerMode::Once))
+	}
+}
+
+#[derive(Component)]
+struct DemoOverlay;
+
+fn reset_idle_timer(mut timer: ResMut<IdleTimer>) {
+	timer.reset();
+}
+
+fn start_demo_when_idle(
+	mut commands: Commands,
+	input: PlayerInput,
+	mut timer: ResMut<IdleTimer>,
+	time: Res<Time>,
+	last_replay: Option<Res<LastReplay>>,
+	mut mode: ResMut<GameMode>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if input.any_pressed() {
+		timer.reset();
+	} else if timer.tick(time.delta()).just_finished() {
+		commands.insert_resource(AttractDemo);
+		if last_replay.is_some() {
+			next_state.set(GameStates::Replay);
+		} else {
+			*mode = GameMode::Autopilot;
+			next_state.set(GameStates::InGame);
+		}
+	}
+}
+
+fn spawn_demo_overlay(mut commands: Commands, locale: Res<Locale>, theme: Res<UiTheme>) {
+	commands
+		.spawn((
+			DemoOverlay,
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				align_items: AlignItems::Center,
+				padding: UiRect::top(Val::Px(64.0)),
+				row_gap: Val::Px(16.0),
+				..default()
+			},
+		))
+		.with_children(|parent| {
+			parent.spawn((Text::new("Flappy"), theme.text(96.0)));
+			parent.spawn((Text::new(locale.text("demo")), theme.text(36.0)));
+		});
+}
+
+fn end_demo_after_crash(mut commands: Commands) {
+	commands.spawn((
+		CleanupOnExit(GameStates::GameOver),
+		TimedTransition::new(DEMO_END_DELAY, GameStates::Menu),
+	));
+}
+
+/// Runs after everything in `Update`, so going back to the menu wins over
+/// whatever else the same press would do, like restarting from the game over
+/// panel.
+fn stop_demo_on_any_press(input: PlayerInput, mut next_state: ResMut<NextState<GameStates>>) {
+	if input.any_pressed() {
+		next_state.set(GameStates::Menu);
+	}
+}
+
+fn clear_demo(mut commands: Commands, overlay: Query<Entity, With<DemoOverlay>>) {
+	commands.remove_resource::<AttractDemo>();
+	for entity in &overlay {
+		commands.entity(entity).despawn();
+	}
+}
+
+pub struct AttractPlugin;
+
+impl Plugin for AttractPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<IdleTimer>()
+			.add_systems(OnEnter(MenuScreen::Main), reset_idle_timer)
+			.add_systems(
+				OnEnter(InRun),
+				spawn_demo_overlay.run_if(resource_exists::<AttractDemo>),
+			)
+			.add_systems(
+				OnEnter(GameStates::GameOver),
+				end_demo_after_crash.run_if(resource_exists::<AttractDemo>),
+			)
+			.add_systems(OnEnter(GameStates::Menu), clear_demo)
+			.add_systems(
+				Update,
+				start_demo_when_idle.run_if(in_state(MenuScreen::Main)),
+			)
+			.add_systems(
+				PostUpdate,
+				stop_demo_on_any_press.run_if(resource_exists::<AttractDemo>),
+			);
+	}
+}
@@ -0,0 +1,75 @@
+//! The autopilot, a simple bot that plays endless runs by itself in
+//! [`GameMode::Autopilot`]. It flaps through [`PendingFlap`] just like the
+//! player does, timing its flaps so the bird's arc stays centred on the next
+//! gap.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit, difficulty::DifficultySettings, get_ready::RunPhase, locale::Locale,
+	next_gap, theme::UiTheme, Acceleration, GameMode, GameplaySet, InRun, PendingFlap, Pipe,
+	Player, Velocity,
+};
+
+fn spawn_autopilot_text(mut commands: Commands, locale: Res<Locale>, theme: Res<UiTheme>) {
+	commands.spawn((
+		CleanupOnExit(InRun),
+		Text::new(locale.text("autopilot")),
+		theme.text(28.0),
+		Node {
+			position_type: PositionType::Absolute,
+			bottom: Val::Px(5.0),
+			right: Val::Px(5.0),
+			..default()
+		},
+	));
+}
+
+fn take_off(mut pending_flap: ResMut<PendingFlap>, time: Res<Time<Real>>) {
+	pending_flap.press(time.elapsed_secs());
+}
+
+/// Flaps once the bird falls below the next gap by half of what a flap lifts
+/// it, or below the middle of the screen while there's no gap yet.
+fn fly(
+	mut pending_flap: ResMut<PendingFlap>,
+	time: Res<Time<Real>>,
+	settings: Res<DifficultySettings>,
+	player: Single<(&Transform, &Velocity, &Acceleration), With<Player>>,
+	pipes: Query<(&Pipe, &Transform, &Sprite)>,
+) {
+	let (transform, velocity, acceleration) = player.into_inner();
+	let bird = transform.translation;
+	let target = next_gap(bird.x, pipes).map_or(0.0, |gap| gap.y);
+	// Heights are taken against gravity, which can turn around.
+	let up = -acceleration.y.signum();
+	let lift = settings.jump_strength.powi(2) / (2.0 * acceleration.y.abs());
+	if velocity.y * up < 0.0 && (bird.y - target) * up < -lift / 2.0 {
+		pending_flap.press(time.elapsed_secs());
+	}
+}
+
+pub struct AutopilotPlugin;
+
+impl Plugin for AutopilotPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			OnEnter(InRun),
+			spawn_autopilot_text.run_if(resource_equals(GameMode::Autopilot)),
+		)
+		.add_systems(
+			Update,
+			take_off
+				.after(crate::expire_pending_flap)
+				.run_if(resource_equals(GameMode::Autopilot).and(in_state(RunPhase::GetReady))),
+		)
+		// Decides on the simulated positions, rather than the ones shown between
+		// ticks, so the same run always plays out the same.
+		.add_systems(
+			FixedUpdate,
+			fly.in_set(GameplaySet::Input)
+				.before(crate::apply_flap)
+				.run_if(resource_equals(GameMode::Autopilot)),
+		);
+	}
+}
@@ -0,0 +1,154 @@
+//! `--bench <ticks>`, which crowds a headless endless run with
+//! [`BIRDS`] birds and [`PIPE_COLUMNS`] columns of pipes, simulates it for that
+//! many ticks and prints how long the ticks took, to catch the movement and
+//! collision systems getting slower.
+//!
+//! The birds can't crash, and flap to stay up, so the crowd lasts the whole
+//! benchmark.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use web_time::Instant;
+
+use crate::{
+	cli::LaunchOptions, difficulty::DifficultySettings, get_ready::RunPhase, ground::ground_top,
+	mirror::ScrollDirection, pipe_patterns::Gap, pipe_pool::PipeSpawner, play_area::PlayArea,
+	power_ups::Invulnerable, sprites::Textures, GameplaySet, Player, Velocity,
+};
+
+pub const BIRDS: usize = 1000;
+pub const PIPE_COLUMNS: usize = 1000;
+/// Distance between two columns, close enough for thousands of pipes to stay
+/// in the run for a while.
+const COLUMN_SPACING: f32 = 40.0;
+const GAP_HEIGHT: f32 = 160.0;
+
+/// When the current update started.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct UpdateStart(Option<Instant>);
+
+/// How long each tick of the crowded run took so far.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct TickTimes(Vec<Duration>);
+
+fn crowd_run(
+	mut commands: Commands,
+	mut pipes: PipeSpawner,
+	players: Query<Entity, With<Player>>,
+	textures: Res<Textures>,
+	settings: Res<DifficultySettings>,
+	direction: Res<ScrollDirection>,
+	play_area: Res<PlayArea>,
+) {
+	for player in &players {
+		commands.entity(player).insert(Invulnerable);
+	}
+	let floor = ground_top(&play_area);
+	let height = play_area.max.y - floor;
+	for index in 0..BIRDS {
+		let y = floor + height * (index as f32 + 0.5) / BIRDS as f32;
+		let bird = crate::make_player(&textures, &settings, *direction);
+		commands.spawn(bird).insert((
+			Transform::from_translation(direction.player_start().with_y(y)),
+			Invulnerable,
+		));
+	}
+	let entry_x = direction.entry_x(&play_area);
+	for index in 0..PIPE_COLUMNS {
+		let x = entry_x - direction.sign() * COLUMN_SPACING * index as f32;
+		// Gaps go up and down the play area so the birds run into plenty of
+		// pipes.
+		let fraction = (index % 7) as f32 / 7.0;
+		let gap = Gap {
+			bottom: floor + (height - GAP_HEIGHT) * fraction,
+			height: GAP_HEIGHT,
+			safe: true,
+			coin: false,
+			spinning_bar: false,
+		};
+		pipes.spawn_column(x, play_area.height(), &[gap]);
+	}
+}
+
+fn keep_birds_up(
+	players: Query<(&Transform, &mut Velocity), With<Player>>,
+	settings: Res<DifficultySettings>,
+	play_area: Res<PlayArea>,
+) {
+	let low = ground_top(&play_area) + (play_area.max.y - ground_top(&play_area)) / 4.0;
+	for (transform, mut velocity) in players {
+		if transform.translation.y < low && velocity.y < 0.0 {
+			velocity.y = settings.jump_strength;
+		}
+	}
+}
+
+fn start_timing(mut start: ResMut<UpdateStart>) {
+	**start = Some(Instant::now());
+}
+
+fn finish_timing(
+	start: Res<UpdateStart>,
+	mut times: ResMut<TickTimes>,
+	options: Res<LaunchOptions>,
+	mut exit: EventWriter<AppExit>,
+) {
+	let Some(start) = **start else {
+		return;
+	};
+	times.push(start.elapsed());
+	if times.len() as u32 >= options.bench.unwrap_or_default() {
+		report(&times);
+		exit.write(AppExit::Success);
+	}
+}
+
+fn report(times: &[Duration]) {
+	let mut sorted = times.to_vec();
+	sorted.sort();
+	let total: Duration = sorted.iter().sum();
+	let percentile = |fraction: f64| {
+		let index = ((sorted.len() - 1) as f64 * fraction).round() as usize;
+		sorted[index]
+	};
+	let millis = |duration: Duration| duration.as_secs_f64() * 1000.0;
+	println!(
+		"{} ticks with {} birds and {} pipe columns",
+		sorted.len(),
+		BIRDS + 1,
+		PIPE_COLUMNS
+	);
+	println!(
+		"mean {:.3} ms, median {:.3} ms, p95 {:.3} ms, p99 {:.3} ms, max {:.3} ms",
+		millis(total / sorted.len() as u32),
+		millis(percentile(0.5)),
+		millis(percentile(0.95)),
+		millis(percentile(0.99)),
+		millis(percentile(1.0))
+	);
+	println!(
+		"{:.0} ticks per second",
+		sorted.len() as f64 / total.as_secs_f64()
+	);
+}
+
+/// Added along with the [`HeadlessPlugin`](crate::headless::HeadlessPlugin),
+/// which makes every update a single tick.
+pub struct BenchPlugin;
+
+impl Plugin for BenchPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<UpdateStart>()
+			.init_resource::<TickTimes>()
+			.add_systems(OnEnter(RunPhase::Flying), crowd_run)
+			.add_systems(
+				FixedUpdate,
+				keep_birds_up
+					.in_set(GameplaySet::Input)
+					.after(crate::apply_flap),
+			)
+			.add_systems(First, start_timing.run_if(in_state(RunPhase::Flying)))
+			.add_systems(Last, finish_timing.run_if(in_state(RunPhase::Flying)));
+	}
+}
@@ -0,0 +1,204 @@
+//! The world changing every few points, from the city to a forest, a desert
+//! and out into space, each with its own background, pipes and music. A quick
+//! fade to black covers the swap.
+
+use bevy::prelude::*;
+
+use crate::{
+	loading::Preload, play_area::VisibleArea, rotating_bars::SpinningBar, Background, GameScore,
+	GameStates, InRun, Pipe,
+};
+
+/// Points scored before the world moves on to the next biome.
+const POINTS_PER_BIOME: i64 = 25;
+const TRANSITION_DURATION: f32 = 0.8;
+/// In front of everything in the world, including the darkness of the night
+/// mutator.
+const TRANSITION_Z: f32 = 30.0;
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Biome {
+	#[default]
+	City,
+	Forest,
+	Desert,
+	Space,
+}
+
+impl Biome {
+	/// In the order they come in, starting with the first one of a run.
+	const ALL: [Biome; 4] = [Biome::City, Biome::Forest, Biome::Desert, Biome::Space];
+
+	fn for_score(score: i64) -> Self {
+		let index = (score / POINTS_PER_BIOME).rem_euclid(Self::ALL.len() as i64);
+		Self::ALL[index as usize]
+	}
+
+	fn name(self) -> &'static str {
+		match self {
+			Biome::City => "city",
+			Biome::Forest => "forest",
+			Biome::Desert => "desert",
+			Biome::Space => "space",
+		}
+	}
+}
+
+/// Everything that changes with the biome.
+pub struct BiomeSet {
+	pub background: Handle<Image>,
+	pub pipe: Handle<Image>,
+	pub music: Handle<AudioSource>,
+}
+
+#[derive(Resource)]
+pub struct BiomeAssets([BiomeSet; 4]);
+
+impl FromWorld for BiomeAssets {
+	fn from_world(world: &mut World) -> Self {
+		let asset_server = world.resource::<AssetServer>();
+		let assets = BiomeAssets(Biome::ALL.map(|biome| match biome {
+			// The city is the look the game always had.
+			Biome::City => BiomeSet {
+				background: asset_server.load("sprites/background.png"),
+				pipe: asset_server.load("sprites/pipe.png"),
+				music: asset_server.load("music/in_game.wav"),
+			},
+			_ => BiomeSet {
+				background:
+					asset_server.load(format!("sprites/biomes/{}_background.png", biome.name())),
+				pipe: asset_server.load(format!("sprites/biomes/{}_pipe.png", biome.name())),
+				music: asset_server.load(format!("music/biomes/{}.wav", biome.name())),
+			},
+		}));
+		let handles = assets.0.iter().flat_map(|set| {
+			[
+				set.background.clone().untyped(),
+				set.pipe.clone().untyped(),
+				set.music.clone().untyped(),
+			]
+		});
+		Preload::add(world, handles.collect::<Vec<_>>());
+		assets
+	}
+}
+
+impl BiomeAssets {
+	pub fn get(&self, biome: Biome) -> &BiomeSet {
+		&self.0[biome as usize]
+	}
+
+	/// The pipe textures of every biome.
+	pub fn pipe_images(&self) -> impl Iterator<Item = &Handle<Image>> {
+		self.0.iter().map(|set| &set.pipe)
+	}
+}
+
+/// Everything drawn with the pipe texture of the biome.
+type PipeLooking = Or<(With<Pipe>, With<SpinningBar>)>;
+type NewPipeLooking = Or<(Added<Pipe>, Added<SpinningBar>)>;
+
+/// Fades the world out and back in, switching over to `to` while it can't be
+/// seen.
+#[derive(Component)]
+struct BiomeTransition {
+	to: Biome,
+	timer: Timer,
+}
+
+/// Only starts a transition once the previous one is over, so the world
+/// catches up one biome at a time.
+fn start_transition(
+	mut commands: Commands,
+	score: Res<GameScore>,
+	biome: Res<Biome>,
+	visible_area: Res<VisibleArea>,
+	transitions: Query<(), With<BiomeTransition>>,
+) {
+	let to = Biome::for_score(**score);
+	if to == *biome || !transitions.is_empty() {
+		return;
+	}
+	commands.spawn((
+		BiomeTransition {
+			to,
+			timer: Timer::from_seconds(TRANSITION_DURATION, TimerMode::Once),
+		},
+		Sprite::from_color(Color::BLACK.with_alpha(0.0), visible_area.size()),
+		Transform::from_translation(visible_area.center().extend(TRANSITION_Z)),
+	));
+}
+
+fn run_transitions(
+	mut commands: Commands,
+	mut biome: ResMut<Biome>,
+	transitions: Query<(Entity, &mut BiomeTransition, &mut Sprite)>,
+	time: Res<Time>,
+) {
+	for (entity, mut transition, mut sprite) in transitions {
+		let fraction = transition.timer.tick(time.delta()).fraction();
+		if fraction >= 0.5 {
+			biome.set_if_neq(transition.to);
+		}
+		if transition.timer.finished() {
+			commands.entity(entity).despawn();
+		} else {
+			sprite.color.set_alpha(1.0 - (2.0 * fraction - 1.0).abs());
+		}
+	}
+}
+
+fn swap_scenery(
+	biome: Res<Biome>,
+	assets: Res<BiomeAssets>,
+	mut background: Single<&mut Sprite, With<Background>>,
+	obstacles: Query<&mut Sprite, (PipeLooking, Without<Background>)>,
+) {
+	let set = assets.get(*biome);
+	background.image = set.background.clone();
+	for mut sprite in obstacles {
+		sprite.image = set.pipe.clone();
+	}
+}
+
+fn dress_new_obstacles(
+	biome: Res<Biome>,
+	assets: Res<BiomeAssets>,
+	obstacles: Query<&mut Sprite, NewPipeLooking>,
+) {
+	for mut sprite in obstacles {
+		sprite.image = assets.get(*biome).pipe.clone();
+	}
+}
+
+fn reset_biome(
+	mut commands: Commands,
+	mut biome: ResMut<Biome>,
+	transitions: Query<Entity, With<BiomeTransition>>,
+) {
+	biome.set_if_neq(Biome::City);
+	for entity in transitions {
+		commands.entity(entity).despawn();
+	}
+}
+
+pub struct BiomePlugin;
+
+impl Plugin for BiomePlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<Biome>()
+			.init_resource::<BiomeAssets>()
+			.add_systems(OnExit(GameStates::GameOver), reset_biome)
+			.add_systems(OnEnter(GameStates::Menu), reset_biome)
+			.add_systems(
+				Update,
+				(
+					start_transition.run_if(in_state(InRun)),
+					run_transitions,
+					swap_scenery.run_if(resource_changed::<Biome>),
+					dress_new_obstacles,
+				)
+					.chain(),
+			);
+	}
+}
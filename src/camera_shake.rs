@@ -0,0 +1,59 @@
+//! Shakes the camera for a moment when the bird crashes.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+	settings::{motion_allowed, Settings},
+	MainCamera, PlayerDiedEvent,
+};
+
+/// Largest offset of the camera at full strength, in world units.
+const SHAKE_DISTANCE: f32 = 12.0;
+const SHAKE_DURATION: f32 = 0.35;
+
+/// Counts down the current shake, which fades out as it runs.
+#[derive(Resource)]
+struct CameraShake(Timer);
+
+fn start_shake(mut commands: Commands) {
+	commands.insert_resource(CameraShake(Timer::from_seconds(
+		SHAKE_DURATION,
+		TimerMode::Once,
+	)));
+}
+
+/// Runs on real time, so the shake isn't affected by slowing down the game.
+fn shake_camera(
+	mut commands: Commands,
+	mut shake: ResMut<CameraShake>,
+	settings: Res<Settings>,
+	mut camera: Single<&mut Transform, With<MainCamera>>,
+	time: Res<Time<Real>>,
+) {
+	shake.0.tick(time.delta());
+	if shake.0.finished() {
+		camera.translation = Vec3::ZERO;
+		commands.remove_resource::<CameraShake>();
+		return;
+	}
+	let mut rng = rand::rng();
+	let direction = Vec2::new(rng.random_range(-1.0..1.0), rng.random_range(-1.0..1.0));
+	let distance = SHAKE_DISTANCE * settings.screen_shake * shake.0.fraction_remaining();
+	camera.translation = (direction * distance).extend(0.0);
+}
+
+pub struct CameraShakePlugin;
+
+impl Plugin for CameraShakePlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			Update,
+			(
+				start_shake.run_if(on_event::<PlayerDiedEvent>.and(motion_allowed)),
+				shake_camera.run_if(resource_exists::<CameraShake>),
+			)
+				.chain(),
+		);
+	}
+}
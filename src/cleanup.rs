@@ -0,0 +1,81 @@
+//! Entities tied to a state, cleaned up as soon as the game leaves it.
+//!
+//! Most of what a run spawns goes away with [`InRun`]. The bird and the world
+//! around it, marked [`RunScene`], stay on screen behind the game over panel
+//! and go away when the game moves on from there instead.
+
+use bevy::{
+	prelude::*,
+	state::state::{ExitSchedules, StateTransitionSteps},
+};
+
+use crate::{menu::MenuScreen, pipe_pool::PipeSpawner, GameStates, InRun, Pipe};
+
+/// Despawned when the game leaves the given state. Pipes go back to the
+/// [`PipePool`](crate::pipe_pool::PipePool) instead.
+#[derive(Component, Debug, Clone)]
+pub struct CleanupOnExit<S: States>(pub S);
+
+/// Part of the world a run plays out in, like the bird, the pipes and the
+/// coins.
+#[derive(Component, Default)]
+#[require(CleanupOnExit<InRun> = CleanupOnExit(InRun))]
+pub struct RunScene;
+
+/// Runs right after the `OnExit` schedules of `S`, so they still see
+/// everything and can keep some of it around.
+fn clean_up_on_exit<S: States>(
+	mut commands: Commands,
+	mut pipes: PipeSpawner,
+	mut transitions: EventReader<StateTransitionEvent<S>>,
+	query: Query<(Entity, &CleanupOnExit<S>, Has<Pipe>)>,
+) {
+	for transition in transitions.read() {
+		let Some(exited) = &transition.exited else {
+			continue;
+		};
+		// Moving to the same state again doesn't leave it.
+		if transition.entered.as_ref() == Some(exited) {
+			continue;
+		}
+		for (entity, cleanup, is_pipe) in &query {
+			if cleanup.0 != *exited {
+				continue;
+			}
+			if is_pipe {
+				pipes.recycle(entity);
+			} else {
+				commands.entity(entity).despawn();
+			}
+		}
+	}
+}
+
+fn keep_scene_for_game_over(mut commands: Commands, scene: Query<Entity, With<RunScene>>) {
+	for entity in &scene {
+		commands
+			.entity(entity)
+			.remove::<CleanupOnExit<InRun>>()
+			.insert(CleanupOnExit(GameStates::GameOver));
+	}
+}
+
+pub struct CleanupPlugin;
+
+impl Plugin for CleanupPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			StateTransition,
+			(
+				clean_up_on_exit::<GameStates>.after(ExitSchedules::<GameStates>::default()),
+				clean_up_on_exit::<InRun>.after(ExitSchedules::<InRun>::default()),
+				clean_up_on_exit::<MenuScreen>.after(ExitSchedules::<MenuScreen>::default()),
+			)
+				.in_set(StateTransitionSteps::ExitSchedules),
+		)
+		.add_systems(
+			OnExit(InRun),
+			keep_scene_for_game_over.run_if(in_state(GameStates::GameOver)),
+		);
+	}
+}
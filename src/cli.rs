@@ -0,0 +1,200 @@
+//! Command line options to launch the game in a specific setup, for testers
+//! and speedrunners:
+//!
+//! - `--seed <n>`: every run is played with this seed, see [`seed`](crate::seed)
+//! - `--windowed <width>x<height>`: starts in a window of this size, even if
+//!   the settings ask for full screen
+//! - `--mode <mode>`: skips the menu for a run of `endless`, `daily`, `lives`,
+//!   `zen`, `hardcore`, `autopilot`, `stage<n>`, counting stages from 1, or
+//!   `time-attack<n>` to one of the targets of
+//!   [`time_attack`](crate::time_attack), like `time-attack25`
+//! - `--mute`: no sound effects or music
+//! - `--headless`: simulates runs without a window or sound, see
+//!   [`headless`](crate::headless)
+//! - `--runs <n>`: how many runs `--headless` plays before quitting, 1 by
+//!   default
+//! - `--bench <ticks>`: times that many ticks of a headless run crowded with
+//!   birds and pipes, see [`bench`](crate::bench)
+//! - `--leaderboard <url>`: the online leaderboard to submit scores to and
+//!   show, see [`leaderboard`](crate::leaderboard)
+//! - `--cloud-save <url>`: where to keep a copy of the save file, see
+//!   [`cloud_sync`](crate::cloud_sync)
+//! - `--host <port>`: hosts a multiplayer race, see [`race`](crate::race)
+//! - `--join <address:port>`: joins the race hosted there
+//! - `--tas <file>`: plays every run from a script of flaps, see
+//!   [`tas`](crate::tas)
+//!
+//! Options with a value can also be written as `--seed=<n>`.
+
+use bevy::prelude::*;
+
+use crate::{
+	leaderboard::Endpoint, race::RaceLink, stages, tas::TasScript, time_attack, GameMode,
+	GameStates,
+};
+
+#[derive(Resource, Debug, Default, Clone)]
+pub struct LaunchOptions {
+	pub seed: Option<u64>,
+	pub window_size: Option<UVec2>,
+	pub mode: Option<GameMode>,
+	pub mute: bool,
+	pub headless: bool,
+	pub runs: Option<u32>,
+	pub bench: Option<u32>,
+	pub leaderboard: Option<Endpoint>,
+	pub cloud_save: Option<Endpoint>,
+	pub race: Option<RaceLink>,
+	pub tas: Option<TasScript>,
+}
+
+impl LaunchOptions {
+	/// Invalid and unknown options are reported and left out.
+	pub fn from_args(mut args: impl Iterator<Item = String>) -> Self {
+		let mut options = LaunchOptions::default();
+		while let Some(arg) = args.next() {
+			let (name, inline_value) = match arg.split_once('=') {
+				Some((name, value)) => (name.to_string(), Some(value.to_string())),
+				None => (arg, None),
+			};
+			if name == "--mute" {
+				options.mute = true;
+				continue;
+			}
+			if name == "--headless" {
+				options.headless = true;
+				continue;
+			}
+			if !matches!(
+				name.as_str(),
+				"--seed"
+					| "--windowed" | "--mode"
+					| "--runs" | "--bench"
+					| "--leaderboard"
+					| "--cloud-save"
+					| "--host" | "--join"
+					| "--tas"
+			) {
+				eprintln!("Ignoring unknown option: {name}");
+				continue;
+			}
+			let value = inline_value.or_else(|| args.next());
+			let valid = match (name.as_str(), value.as_deref()) {
+				("--seed", Some(value)) => {
+					value.parse().map(|seed| options.seed = Some(seed)).is_ok()
+				}
+				("--windowed", Some(value)) => parse_size(value)
+					.map(|size| options.window_size = Some(size))
+					.is_some(),
+				("--mode", Some(value)) => parse_mode(value)
+					.map(|mode| options.mode = Some(mode))
+					.is_some(),
+				("--runs", Some(value)) => value
+					.parse()
+					.ok()
+					.filter(|runs| *runs > 0)
+					.map(|runs| options.runs = Some(runs))
+					.is_some(),
+				("--bench", Some(value)) => value
+					.parse()
+					.ok()
+					.filter(|ticks| *ticks > 0)
+					.map(|ticks| options.bench = Some(ticks))
+					.is_some(),
+				("--leaderboard", Some(value)) => Endpoint::parse(value)
+					.map(|endpoint| options.leaderboard = Some(endpoint))
+					.is_some(),
+				("--cloud-save", Some(value)) => Endpoint::parse(value)
+					.map(|endpoint| options.cloud_save = Some(endpoint))
+					.is_some(),
+				("--host", Some(value)) => value
+					.parse()
+					.map(|port| options.race = Some(RaceLink::Host(port)))
+					.is_ok(),
+				("--join", Some(value)) => RaceLink::parse_join(value)
+					.map(|link| options.race = Some(link))
+					.is_some(),
+				("--tas", Some(value)) => match TasScript::load(value) {
+					Ok(script) => {
+						options.tas = Some(script);
+						true
+					}
+					Err(err) => {
+						eprintln!("Couldn't load the TAS script {err}");
+						false
+					}
+				},
+				_ => false,
+			};
+			if !valid {
+				eprintln!("Ignoring invalid {name} value: {value:?}");
+			}
+		}
+		// The script plays out the same only with its own seed and mode, but
+		// either can still be picked on the command line.
+		if let Some(script) = &options.tas {
+			options.seed = options.seed.or(script.seed);
+			options.mode = options.mode.or(script.mode);
+		}
+		if options.bench.is_some() {
+			options.headless = true;
+			options.mode = Some(GameMode::Endless);
+		}
+		// There's no menu to pick a run from, and zen runs never end by
+		// themselves.
+		if options.headless && matches!(options.mode, None | Some(GameMode::Zen)) {
+			options.mode = Some(GameMode::Endless);
+		}
+		options
+	}
+}
+
+fn parse_size(value: &str) -> Option<UVec2> {
+	let (width, height) = value.split_once('x')?;
+	let size = UVec2::new(width.parse().ok()?, height.parse().ok()?);
+	(size.min_element() > 0).then_some(size)
+}
+
+pub fn parse_mode(value: &str) -> Option<GameMode> {
+	match value {
+		"endless" => Some(GameMode::Endless),
+		"daily" => Some(GameMode::Daily),
+		"lives" => Some(GameMode::Lives),
+		"zen" => Some(GameMode::Zen),
+		"hardcore" => Some(GameMode::Hardcore),
+		"autopilot" => Some(GameMode::Autopilot),
+		_ if value.starts_with("time-attack") => {
+			let pipes: u32 = value.strip_prefix("time-attack")?.parse().ok()?;
+			let target = time_attack::TARGETS
+				.iter()
+				.position(|&target| target == pipes)?;
+			Some(GameMode::TimeAttack(target as u8))
+		}
+		_ => {
+			let number: u8 = value.strip_prefix("stage")?.parse().ok()?;
+			let mode = GameMode::Stage(number.checked_sub(1)?);
+			stages::stage(mode).map(|_| mode)
+		}
+	}
+}
+
+/// Once everything has loaded. Only the first run is skipped to, quitting it
+/// goes back to the menu as usual.
+fn start_launch_mode(
+	options: Res<LaunchOptions>,
+	mut mode: ResMut<GameMode>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if let Some(launch_mode) = options.mode {
+		*mode = launch_mode;
+		next_state.set(GameStates::InGame);
+	}
+}
+
+pub struct CliPlugin;
+
+impl Plugin for CliPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(OnExit(GameStates::Loading), start_launch_mode);
+	}
+}
@@ -0,0 +1,215 @@
+//! Keeping a copy of the save file online, so progress carries over between
+//! computers. The save of each profile is uploaded to the endpoint passed with
+//! `--cloud-save <url>`, or to Steam Cloud when playing on Steam without one.
+//!
+//! When the game starts, and when switching profiles, the copy online is
+//! downloaded and compared with the save on disk, and whichever shows more
+//! progress wins: more runs played, then more achievements, a better best
+//! score, more stage stars and more coins. Nothing is uploaded before that, so
+//! a fresh install can't overwrite the progress kept online. From then on the
+//! save is uploaded whenever it changed, outside of runs. If the copy online
+//! can't be reached, the save stays on disk until the next start.
+//!
+//! The endpoint keeps the save of each profile at `<url>/<profile>`. It answers
+//! a GET with `{"save": "<save file>"}`, or an empty body when it has none,
+//! and takes the same with a PUT. Only plain `http://` URLs are supported, and
+//! none in the browser.
+
+use std::{io, sync::Arc};
+
+use bevy::{
+	prelude::*,
+	tasks::{futures::check_ready, IoTaskPool, Task},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	cli::LaunchOptions, headless::is_headless, leaderboard::Endpoint, profiles::Profiles,
+	save::SaveData, GameStates,
+};
+
+/// Somewhere to keep a copy of the save file of each profile.
+pub trait CloudStorage: Send + Sync {
+	/// The save file kept for `profile`, if there is one.
+	fn download(&self, profile: &str) -> io::Result<Option<String>>;
+	fn upload(&self, profile: &str, contents: &str) -> io::Result<()>;
+}
+
+/// Where the save is synced to. Left out when it isn't synced at all.
+#[derive(Resource, Clone)]
+pub struct CloudSave(pub Arc<dyn CloudStorage>);
+
+#[derive(Serialize, Deserialize)]
+struct CloudFile {
+	save: String,
+}
+
+/// Spaces are the only characters in profile names that need escaping.
+fn path_segment(profile: &str) -> String {
+	profile.replace(' ', "%20")
+}
+
+impl CloudStorage for Endpoint {
+	fn download(&self, profile: &str) -> io::Result<Option<String>> {
+		let body = self.join(&path_segment(profile)).request("GET", "")?;
+		if body.trim().is_empty() {
+			return Ok(None);
+		}
+		let file: CloudFile = serde_json::from_str(&body)?;
+		Ok(Some(file.save))
+	}
+
+	fn upload(&self, profile: &str, contents: &str) -> io::Result<()> {
+		let body = serde_json::to_string(&CloudFile {
+			save: contents.to_string(),
+		})?;
+		self.join(&path_segment(profile))
+			.request("PUT", &body)
+			.map(drop)
+	}
+}
+
+/// Compared one after the other, see the module docs.
+fn progress(save: &SaveData) -> (u32, usize, i64, u32, u64) {
+	(
+		save.achievements.runs_played,
+		save.achievements.unlocked.len(),
+		save.high_scores.best().max(save.hardcore_best),
+		save.stage_stars.iter().map(|stars| u32::from(*stars)).sum(),
+		save.coins,
+	)
+}
+
+#[derive(Resource, Default)]
+struct CloudSync {
+	downloading: Option<Task<io::Result<Option<String>>>>,
+	uploading: Option<Task<io::Result<()>>>,
+	/// The profile whose save is being synced, until another one is picked.
+	profile: Option<String>,
+	/// Whether the save of that profile has been compared with the copy online,
+	/// which has to happen before anything is uploaded.
+	compared: bool,
+	/// Whether the save changed since it was last uploaded.
+	changed: bool,
+}
+
+impl CloudSync {
+	fn syncing(&self, profiles: &Profiles) -> bool {
+		self.profile.as_deref() == Some(profiles.current.as_str())
+	}
+}
+
+fn note_changes(mut sync: ResMut<CloudSync>) {
+	sync.changed = true;
+}
+
+/// Whether another profile is being played than the one synced, which is
+/// also the case at the start.
+fn profile_switched(profiles: Res<Profiles>, sync: Res<CloudSync>) -> bool {
+	!sync.syncing(&profiles)
+}
+
+/// Runs at the start and on switching profiles. An upload already on its way
+/// carries on, since it's for the profile it started with.
+fn start_download(cloud: Res<CloudSave>, profiles: Res<Profiles>, mut sync: ResMut<CloudSync>) {
+	let storage = cloud.0.clone();
+	let profile = profiles.current.clone();
+	sync.profile = Some(profile.clone());
+	sync.downloading = Some(IoTaskPool::get().spawn(async move { storage.download(&profile) }));
+	sync.compared = false;
+	sync.changed = false;
+}
+
+/// Takes the copy online if it shows more progress, and otherwise uploads the
+/// save over it.
+fn finish_download(mut sync: ResMut<CloudSync>, mut save: ResMut<SaveData>) {
+	let Some(task) = &mut sync.downloading else {
+		return;
+	};
+	let Some(result) = check_ready(task) else {
+		return;
+	};
+	sync.downloading = None;
+	let remote = match result {
+		Ok(remote) => remote,
+		Err(err) => {
+			warn!("Failed to download the save from the cloud, not syncing it: {err}");
+			return;
+		}
+	};
+	sync.compared = true;
+	sync.changed = true;
+	let Some(contents) = remote else {
+		return;
+	};
+	match ron::from_str::<SaveData>(&contents) {
+		Ok(remote) if progress(&remote) > progress(&save) => {
+			info!("Continuing from the save in the cloud, which is further along");
+			*save = remote;
+		}
+		Ok(_) => {}
+		Err(err) => warn!("Replacing the unreadable save in the cloud: {err}"),
+	}
+}
+
+fn start_upload(
+	cloud: Res<CloudSave>,
+	profiles: Res<Profiles>,
+	save: Res<SaveData>,
+	mut sync: ResMut<CloudSync>,
+) {
+	if !sync.syncing(&profiles) || !sync.compared || !sync.changed || sync.uploading.is_some() {
+		return;
+	}
+	let contents = match ron::ser::to_string_pretty(&*save, default()) {
+		Ok(contents) => contents,
+		Err(err) => {
+			error!("Failed to serialize the save for the cloud: {err}");
+			return;
+		}
+	};
+	sync.changed = false;
+	let storage = cloud.0.clone();
+	let profile = profiles.current.clone();
+	sync.uploading =
+		Some(IoTaskPool::get().spawn(async move { storage.upload(&profile, &contents) }));
+}
+
+/// A failed upload is tried again the next time the save changes.
+fn finish_upload(mut sync: ResMut<CloudSync>) {
+	let Some(task) = &mut sync.uploading else {
+		return;
+	};
+	if let Some(result) = check_ready(task) {
+		sync.uploading = None;
+		if let Err(err) = result {
+			warn!("Failed to upload the save to the cloud: {err}");
+		}
+	}
+}
+
+pub struct CloudSyncPlugin;
+
+impl Plugin for CloudSyncPlugin {
+	fn build(&self, app: &mut App) {
+		if let Some(endpoint) = app
+			.world()
+			.get_resource::<LaunchOptions>()
+			.and_then(|options| options.cloud_save.clone())
+		{
+			app.insert_resource(CloudSave(Arc::new(endpoint)));
+		}
+		app.init_resource::<CloudSync>().add_systems(
+			Update,
+			(
+				note_changes.run_if(resource_changed::<SaveData>),
+				start_download.run_if(profile_switched),
+				finish_download,
+				finish_upload,
+				start_upload.run_if(not(in_state(GameStates::InGame))),
+			)
+				.chain()
+				.run_if(resource_exists::<CloudSave>.and(not(is_headless))),
+		);
+	}
+}
@@ -0,0 +1,121 @@
+//! Coins floating in the pipe gaps, collected into a wallet kept between
+//! sessions.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::RunScene,
+	collision::{Collider, ColliderShape, CollisionLayer, OnCollision},
+	loading::Preload,
+	moving_gaps::Oscillator,
+	offscreen::DespawnOffscreen,
+	save::SaveData,
+	sprites::Textures,
+	GameStates, GameplayEvent, GameplaySet, InRun, Pipe, Scrolling,
+};
+
+const COIN_SIZE: Vec2 = Vec2::splat(24.0);
+
+#[derive(Component)]
+#[require(
+	Collider = Collider::new(ColliderShape::Circle(COIN_SIZE.x / 2.0), CollisionLayer::Pickup),
+	DespawnOffscreen = DespawnOffscreen::new(COIN_SIZE.x),
+	RunScene
+)]
+pub struct Coin;
+
+#[derive(Resource)]
+struct CoinSound(Handle<AudioSource>);
+
+impl FromWorld for CoinSound {
+	fn from_world(world: &mut World) -> Self {
+		let handle = world.resource::<AssetServer>().load("sounds/coin.wav");
+		Preload::add(world, [handle.clone().untyped()]);
+		CoinSound(handle)
+	}
+}
+
+/// Places a coin in the middle of the gaps of new pipes that call for one.
+/// Worked out from the pipes rather than drawn from the game's randomness, so
+/// runs play out the same as they did before coins existed.
+fn spawn_coins(
+	mut commands: Commands,
+	textures: Res<Textures>,
+	pipes: Query<(&Transform, &Sprite, &Pipe, Option<&Oscillator>), Added<Pipe>>,
+) {
+	for (transform, sprite, pipe, oscillator) in pipes {
+		// Only the pipe right above a gap knows about it.
+		if !pipe.coin {
+			continue;
+		}
+		let height = sprite.custom_size.unwrap_or_default().y;
+		let gap_top = transform.translation.y - height / 2.0;
+		let mut coin = commands.spawn((
+			Coin,
+			textures.coin_sprite(COIN_SIZE),
+			Transform::from_xyz(transform.translation.x, gap_top - pipe.gap / 2.0, 1.0),
+			Scrolling,
+		));
+		// Moves along with a moving gap.
+		if let Some(oscillator) = oscillator {
+			coin.insert(*oscillator);
+		}
+	}
+}
+
+fn collect_coin(
+	trigger: Trigger<OnCollision>,
+	mut commands: Commands,
+	coins: Query<(), With<Coin>>,
+	mut events: EventWriter<GameplayEvent>,
+) {
+	if coins.contains(trigger.target()) {
+		commands.entity(trigger.target()).despawn();
+		events.write(GameplayEvent::CoinCollected);
+	}
+}
+
+fn play_coin_sound(
+	mut commands: Commands,
+	mut events: EventReader<GameplayEvent>,
+	sound: Res<CoinSound>,
+) {
+	for event in events.read() {
+		if matches!(event, GameplayEvent::CoinCollected) {
+			commands.spawn((AudioPlayer::new(sound.0.clone()), PlaybackSettings::DESPAWN));
+		}
+	}
+}
+
+/// Coins picked up again while watching a replay don't count.
+fn add_to_wallet(mut events: EventReader<GameplayEvent>, mut save: ResMut<SaveData>) {
+	for event in events.read() {
+		if matches!(event, GameplayEvent::CoinCollected) {
+			save.coins += 1;
+		}
+	}
+}
+
+pub struct CoinsPlugin;
+
+impl Plugin for CoinsPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<CoinSound>()
+			.add_observer(collect_coin)
+			.add_systems(
+				FixedUpdate,
+				spawn_coins
+					.after(crate::handle_pipe_spawn)
+					.after(crate::moving_gaps::make_gaps_move)
+					.in_set(GameplaySet::Spawn),
+			)
+			.add_systems(
+				Update,
+				(
+					play_coin_sound.run_if(in_state(InRun)),
+					add_to_wallet
+						.run_if(in_state(GameStates::InGame).and(crate::counts_for_progress)),
+				),
+			);
+	}
+}
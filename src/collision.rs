@@ -0,0 +1,148 @@
+//! Collisions between the bird and everything it can run into. Anything with
+//! a [`Collider`] is checked against the bird every tick, and each overlap
+//! triggers [`OnCollision`] on both of them. Whatever should happen next is up
+//! to the observers, which tell things apart by their [`CollisionLayer`].
+//!
+//! A cheap bounding box test comes first, so the exact shapes are only
+//! compared for the few things right around the bird.
+
+use bevy::{
+	math::bounding::{Aabb2d, Bounded2d, BoundingCircle, BoundingVolume, IntersectsVolume},
+	prelude::*,
+};
+
+use crate::{GameplaySet, Player};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionLayer {
+	/// Crashes the bird, see [`Obstacle`](crate::Obstacle).
+	Obstacle,
+	/// Picked up by flying through it, like coins and power-ups.
+	Pickup,
+	Ground,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ColliderShape {
+	/// The size of the sprite, turned however its transform turns it.
+	Sprite,
+	/// A box of this size around the center, for things drawn larger or
+	/// smaller than they should be to pick up.
+	Box(Vec2),
+	/// A circle of this radius around the center.
+	Circle(f32),
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Collider {
+	pub shape: ColliderShape,
+	pub layer: CollisionLayer,
+}
+
+impl Collider {
+	pub const fn new(shape: ColliderShape, layer: CollisionLayer) -> Self {
+		Collider { shape, layer }
+	}
+
+	/// Box around the whole shape, turned or not.
+	fn bounds(&self, transform: &Transform, sprite: Option<&Sprite>) -> Aabb2d {
+		let center = transform.translation.truncate();
+		match self.shape {
+			ColliderShape::Sprite => {
+				let size = sprite
+					.and_then(|sprite| sprite.custom_size)
+					.unwrap_or_default();
+				let angle = transform.rotation.to_euler(EulerRot::ZYX).0;
+				Rectangle::from_size(size).aabb_2d(Isometry2d::new(center, Rot2::radians(angle)))
+			}
+			ColliderShape::Box(size) => Aabb2d::new(center, size / 2.0),
+			ColliderShape::Circle(radius) => Aabb2d::new(center, Vec2::splat(radius)),
+		}
+	}
+
+	fn overlaps(&self, aabb: &Aabb2d, transform: &Transform, sprite: Option<&Sprite>) -> bool {
+		let center = transform.translation.truncate();
+		match (self.shape, sprite) {
+			(ColliderShape::Sprite, Some(sprite)) => overlaps_rotated(aabb, transform, sprite),
+			(ColliderShape::Sprite, None) => false,
+			(ColliderShape::Box(size), _) => aabb.intersects(&Aabb2d::new(center, size / 2.0)),
+			(ColliderShape::Circle(radius), _) => {
+				BoundingCircle::new(center, radius).intersects(aabb)
+			}
+		}
+	}
+}
+
+/// Triggered on the bird and on what it ran into, with `other` the other one
+/// of the two and `layer` the layer of the one that isn't the bird. Sent every
+/// tick they overlap.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct OnCollision {
+	pub other: Entity,
+	pub layer: CollisionLayer,
+}
+
+pub fn sprite_collider(transform: &Transform, sprite: &Sprite) -> Aabb2d {
+	let size = sprite.custom_size.unwrap_or_default();
+	Aabb2d::new(transform.translation.truncate(), size / 2.0)
+}
+
+/// Whether the sprite, turned however its transform turns it, overlaps `aabb`.
+/// Checks for a gap between the two along the axes of both boxes.
+fn overlaps_rotated(aabb: &Aabb2d, transform: &Transform, sprite: &Sprite) -> bool {
+	let half_size = sprite.custom_size.unwrap_or_default() / 2.0;
+	let u = (transform.rotation * Vec3::X).truncate();
+	let v = (transform.rotation * Vec3::Y).truncate();
+	let offset = transform.translation.truncate() - aabb.center();
+	let aabb_half_size = aabb.half_size();
+	[Vec2::X, Vec2::Y, u, v].into_iter().all(|axis| {
+		let sprite_extent = half_size.x * u.dot(axis).abs() + half_size.y * v.dot(axis).abs();
+		let aabb_extent = aabb_half_size.x * axis.x.abs() + aabb_half_size.y * axis.y.abs();
+		offset.dot(axis).abs() <= sprite_extent + aabb_extent
+	})
+}
+
+/// Observers run one collision at a time, each seeing what the ones before
+/// it did, like a hit making the bird invulnerable to the next.
+fn detect_collisions(
+	mut commands: Commands,
+	players: Query<(Entity, &Transform, &Sprite), With<Player>>,
+	colliders: Query<(Entity, &Collider, &Transform, Option<&Sprite>), Without<Player>>,
+) {
+	for (player_entity, player_transform, player_sprite) in &players {
+		let player_collider = sprite_collider(player_transform, player_sprite);
+		for (entity, collider, transform, sprite) in &colliders {
+			if !player_collider.intersects(&collider.bounds(transform, sprite))
+				|| !collider.overlaps(&player_collider, transform, sprite)
+			{
+				continue;
+			}
+			let layer = collider.layer;
+			commands.trigger_targets(
+				OnCollision {
+					other: player_entity,
+					layer,
+				},
+				entity,
+			);
+			commands.trigger_targets(
+				OnCollision {
+					other: entity,
+					layer,
+				},
+				player_entity,
+			);
+		}
+	}
+}
+
+pub struct CollisionPlugin;
+
+impl Plugin for CollisionPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			FixedUpdate,
+			detect_collisions.in_set(GameplaySet::Collision),
+		);
+	}
+}
@@ -0,0 +1,146 @@
+//! Combos: passing pipes one after another through the middle third of their
+//! gaps raises a score multiplier, and a sloppy pass drops it back to x1. The
+//! meter in the top right shows the multiplier and the way to the next one.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit, locale::Locale, mirror::ScrollDirection, theme::UiTheme, GameplaySet,
+	InRun, Pipe, Player, ScoredEvent, PLAYER_SIZE,
+};
+
+/// Clean passes in a row needed for each step of the multiplier.
+const PASSES_PER_STEP: u32 = 3;
+const MAX_MULTIPLIER: u32 = 4;
+const METER_WIDTH: f32 = 120.0;
+const METER_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+const FILL_COLOR: Color = Color::srgb(1.0, 0.8, 0.2);
+
+/// Clean passes in a row so far this run.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct Combo(u32);
+
+impl Combo {
+	fn multiplier(&self) -> u32 {
+		(1 + **self / PASSES_PER_STEP).min(MAX_MULTIPLIER)
+	}
+
+	/// How far along the way to the next multiplier the combo is, full once
+	/// it's at the highest.
+	fn progress(&self) -> f32 {
+		if self.multiplier() == MAX_MULTIPLIER {
+			1.0
+		} else {
+			(**self % PASSES_PER_STEP) as f32 / PASSES_PER_STEP as f32
+		}
+	}
+}
+
+#[derive(Component)]
+struct ComboText;
+
+#[derive(Component)]
+struct ComboFill;
+
+/// Whether the bird stayed within the middle third of the gap below the pipe
+/// the whole way through, judged by how close it came to the edges.
+fn centered(pipe: &Pipe) -> bool {
+	pipe.closest >= pipe.gap / 3.0 - PLAYER_SIZE.y / 2.0
+}
+
+fn start_combo(mut commands: Commands, mut combo: ResMut<Combo>, theme: Res<UiTheme>) {
+	**combo = 0;
+	commands
+		.spawn((
+			CleanupOnExit(InRun),
+			Node {
+				position_type: PositionType::Absolute,
+				top: Val::Px(36.0),
+				right: Val::Px(5.0),
+				flex_direction: FlexDirection::Column,
+				align_items: AlignItems::End,
+				row_gap: Val::Px(4.0),
+				..default()
+			},
+		))
+		.with_children(|parent| {
+			parent.spawn((ComboText, Text::default(), theme.text(28.0)));
+			parent
+				.spawn((
+					Node {
+						width: Val::Px(METER_WIDTH),
+						height: Val::Px(8.0),
+						..default()
+					},
+					BackgroundColor(METER_COLOR),
+				))
+				.with_child((
+					ComboFill,
+					Node {
+						height: Val::Percent(100.0),
+						..default()
+					},
+					BackgroundColor(FILL_COLOR),
+				));
+		});
+}
+
+/// Judges each pipe as it's passed, right before it gives its point, and adds
+/// the points the multiplier makes it worth on top.
+fn score_combo(
+	mut combo: ResMut<Combo>,
+	players: Query<&Transform, With<Player>>,
+	pipes: Query<(&Transform, &Pipe)>,
+	direction: Res<ScrollDirection>,
+	mut scored: EventWriter<ScoredEvent>,
+) {
+	let Some(player_x) = players.iter().next().map(|player| player.translation.x) else {
+		return;
+	};
+	for (transform, pipe) in pipes {
+		if !pipe.give_score || !crate::has_passed(*direction, transform.translation.x, player_x) {
+			continue;
+		}
+		if centered(pipe) {
+			**combo += 1;
+		} else {
+			**combo = 0;
+		}
+		let bonus = combo.multiplier() - 1;
+		if bonus > 0 {
+			scored.write(ScoredEvent {
+				points: i64::from(bonus),
+			});
+		}
+	}
+}
+
+fn update_meter(
+	combo: Res<Combo>,
+	locale: Res<Locale>,
+	mut text: Single<&mut Text, With<ComboText>>,
+	mut fill: Single<&mut Node, With<ComboFill>>,
+) {
+	text.0 = locale.format("combo", &[("multiplier", &combo.multiplier())]);
+	fill.width = Val::Percent(combo.progress() * 100.0);
+}
+
+pub struct ComboPlugin;
+
+impl Plugin for ComboPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<Combo>()
+			.add_systems(OnEnter(InRun), start_combo)
+			.add_systems(
+				FixedUpdate,
+				score_combo
+					.in_set(GameplaySet::Scoring)
+					.after(crate::near_miss::track_close_calls)
+					.before(crate::give_score_when_over_player),
+			)
+			.add_systems(
+				Update,
+				update_meter.run_if(in_state(InRun).and(resource_changed::<Combo>)),
+			);
+	}
+}
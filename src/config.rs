@@ -0,0 +1,212 @@
+//! Gameplay tuning loaded from `assets/config/game.config.ron`, so the feel of
+//! the game can be changed without recompiling. Debug builds keep an eye on
+//! the file and pick up changes while the game runs, even in the middle of a
+//! run.
+
+use bevy::{
+	asset::{io::Reader, AssetLoader, LoadContext},
+	prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	difficulty::{DifficultyPreset, DifficultySettings},
+	loading::Preload,
+};
+
+const CONFIG_PATH: &str = "config/game.config.ron";
+/// How often debug builds look for changes to the file.
+#[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+const WATCH_INTERVAL: f32 = 1.0;
+
+/// Physics of the bird and the starting pipe layout for one difficulty preset.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct PresetConfig {
+	pub gravity: f32,
+	pub jump_strength: f32,
+	pub pipe_gap: f32,
+	/// Seconds between two columns of pipes.
+	pub pipe_spawn_interval: f32,
+}
+
+/// How the pipes speed up and close in as the score climbs, the same for every
+/// preset.
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct PipeConfig {
+	pub speed: f32,
+	pub max_speed: f32,
+	/// Score at which the game starts getting harder.
+	pub ramp_start_score: i64,
+	pub speed_per_point: f32,
+	pub gap_per_point: f32,
+	pub spawn_interval_per_point: f32,
+	pub min_gap: f32,
+	pub min_spawn_interval: f32,
+	/// How much faster the pipes move in hardcore mode.
+	pub hardcore_speed_scale: f32,
+	/// How much tighter the gaps are in hardcore mode.
+	pub hardcore_gap_scale: f32,
+}
+
+/// The defaults match the file shipped with the game, and are used until it
+/// is loaded or if it can't be.
+#[derive(Asset, Resource, TypePath, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GameConfig {
+	pub easy: PresetConfig,
+	pub normal: PresetConfig,
+	pub hard: PresetConfig,
+	pub pipes: PipeConfig,
+}
+
+impl Default for GameConfig {
+	fn default() -> Self {
+		GameConfig {
+			easy: PresetConfig {
+				gravity: 1700.0,
+				jump_strength: 720.0,
+				pipe_gap: 275.0,
+				pipe_spawn_interval: 2.3,
+			},
+			normal: PresetConfig {
+				gravity: 2000.0,
+				jump_strength: 800.0,
+				pipe_gap: 225.0,
+				pipe_spawn_interval: 2.0,
+			},
+			hard: PresetConfig {
+				gravity: 2300.0,
+				jump_strength: 860.0,
+				pipe_gap: 190.0,
+				pipe_spawn_interval: 1.7,
+			},
+			pipes: PipeConfig {
+				speed: 450.0,
+				max_speed: 700.0,
+				ramp_start_score: 10,
+				speed_per_point: 6.0,
+				gap_per_point: 2.0,
+				spawn_interval_per_point: 0.025,
+				min_gap: 160.0,
+				min_spawn_interval: 1.25,
+				hardcore_speed_scale: 1.25,
+				hardcore_gap_scale: 0.8,
+			},
+		}
+	}
+}
+
+impl GameConfig {
+	pub fn preset(&self, preset: DifficultyPreset) -> &PresetConfig {
+		match preset {
+			DifficultyPreset::Easy => &self.easy,
+			DifficultyPreset::Normal => &self.normal,
+			DifficultyPreset::Hard => &self.hard,
+		}
+	}
+}
+
+#[derive(Default)]
+struct GameConfigLoader;
+
+impl AssetLoader for GameConfigLoader {
+	type Asset = GameConfig;
+	type Settings = ();
+	type Error = Box<dyn std::error::Error + Send + Sync>;
+
+	async fn load(
+		&self,
+		reader: &mut dyn Reader,
+		_settings: &(),
+		_load_context: &mut LoadContext<'_>,
+	) -> Result<GameConfig, Self::Error> {
+		let mut bytes = Vec::new();
+		reader.read_to_end(&mut bytes).await?;
+		Ok(ron::de::from_bytes(&bytes)?)
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["config.ron"]
+	}
+}
+
+#[derive(Resource)]
+struct ConfigHandle(Handle<GameConfig>);
+
+impl FromWorld for ConfigHandle {
+	fn from_world(world: &mut World) -> Self {
+		let handle = world.resource::<AssetServer>().load(CONFIG_PATH);
+		Preload::add(world, [handle.clone().untyped()]);
+		ConfigHandle(handle)
+	}
+}
+
+/// Copies the file into the [`GameConfig`] resource whenever it's loaded or
+/// reloaded.
+fn apply_loaded_config(
+	mut events: EventReader<AssetEvent<GameConfig>>,
+	handle: Res<ConfigHandle>,
+	assets: Res<Assets<GameConfig>>,
+	mut config: ResMut<GameConfig>,
+) {
+	for event in events.read() {
+		let changed = event.is_loaded_with_dependencies(&handle.0) || event.is_modified(&handle.0);
+		if let Some(loaded) = assets.get(&handle.0).filter(|_| changed) {
+			if config.set_if_neq(loaded.clone()) {
+				info!("Applied game config from {CONFIG_PATH}");
+			}
+		}
+	}
+}
+
+/// Keeps the current preset, whichever set it.
+fn retune_difficulty(config: Res<GameConfig>, mut settings: ResMut<DifficultySettings>) {
+	let preset = settings.preset;
+	settings.set_if_neq(DifficultySettings::new(preset, &config));
+}
+
+/// Polls the modification time of the file rather than using Bevy's file
+/// watcher, which needs dependencies the game otherwise doesn't.
+#[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+fn watch_config_file(
+	asset_server: Res<AssetServer>,
+	time: Res<Time>,
+	mut timer: Local<Option<Timer>>,
+	mut last_modified: Local<Option<std::time::SystemTime>>,
+) {
+	let timer =
+		timer.get_or_insert_with(|| Timer::from_seconds(WATCH_INTERVAL, TimerMode::Repeating));
+	if !timer.tick(time.delta()).just_finished() {
+		return;
+	}
+	let path = bevy::asset::io::file::FileAssetReader::get_base_path()
+		.join("assets")
+		.join(CONFIG_PATH);
+	let Ok(modified) = std::fs::metadata(path).and_then(|metadata| metadata.modified()) else {
+		return;
+	};
+	if last_modified.is_some_and(|last| last != modified) {
+		asset_server.reload(CONFIG_PATH);
+	}
+	*last_modified = Some(modified);
+}
+
+pub struct ConfigPlugin;
+
+impl Plugin for ConfigPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_asset::<GameConfig>()
+			.init_asset_loader::<GameConfigLoader>()
+			.init_resource::<GameConfig>()
+			.init_resource::<ConfigHandle>()
+			.add_systems(
+				Update,
+				(
+					apply_loaded_config,
+					retune_difficulty.run_if(resource_changed::<GameConfig>),
+				)
+					.chain(),
+			);
+		#[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+		app.add_systems(Update, watch_config_file);
+	}
+}
@@ -0,0 +1,120 @@
+//! The moment right after a crash, where the bird tumbles down in slow motion
+//! before the game over panel comes up. Cleared stages skip straight to the
+//! end.
+
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::{
+	difficulty::DifficultySettings, ground::ground_top, high_scores::PendingHighScore,
+	play_area::PlayArea, settings::Settings, stages::stage_cleared, time_attack::target_reached,
+	GameStates, Player, Velocity, PLAYER_SIZE,
+};
+
+/// How fast the game runs while the bird falls.
+const SLOW_MOTION_SPEED: f32 = 0.25;
+/// How long the fall lasts, in real time.
+const FALL_DURATION: f32 = 0.6;
+/// Turns per second the falling bird spins at, in game time.
+const FALL_SPIN: f32 = -2.0;
+
+#[derive(SubStates, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[source(GameStates = GameStates::GameOver)]
+pub(crate) enum Crash {
+	#[default]
+	Falling,
+	/// The run made the high score table, waiting for the player's initials.
+	EnteringInitials,
+	/// The run is over for good, ready to restart.
+	Over,
+}
+
+#[derive(Resource, Deref, DerefMut)]
+struct FallTimer(Timer);
+
+/// How fast the game ran before the fall slowed it down, which isn't always
+/// full speed with the debug controls.
+#[derive(Resource, Deref)]
+struct SpeedBeforeFall(f32);
+
+fn start_fall(mut commands: Commands, mut time: ResMut<Time<Virtual>>) {
+	let speed = time.relative_speed();
+	time.set_relative_speed(speed * SLOW_MOTION_SPEED);
+	commands.insert_resource(SpeedBeforeFall(speed));
+	commands.insert_resource(FallTimer(Timer::from_seconds(
+		FALL_DURATION,
+		TimerMode::Once,
+	)));
+}
+
+fn end_fall(
+	mut commands: Commands,
+	mut time: ResMut<Time<Virtual>>,
+	speed: Option<Res<SpeedBeforeFall>>,
+) {
+	if let Some(speed) = speed {
+		time.set_relative_speed(**speed);
+		commands.remove_resource::<SpeedBeforeFall>();
+	}
+}
+
+fn skip_fall(mut next_crash: ResMut<NextState<Crash>>) {
+	next_crash.set(Crash::Over);
+}
+
+fn fall(
+	players: Query<(&mut Transform, &mut Velocity), With<Player>>,
+	difficulty: Res<DifficultySettings>,
+	play_area: Res<PlayArea>,
+	settings: Res<Settings>,
+	time: Res<Time>,
+) {
+	let floor = ground_top(&play_area) + PLAYER_SIZE.y / 2.0;
+	for (mut transform, mut velocity) in players {
+		if transform.translation.y <= floor {
+			continue;
+		}
+		velocity.y -= difficulty.gravity * time.delta_secs();
+		transform.translation.y =
+			(transform.translation.y + velocity.y * time.delta_secs()).max(floor);
+		if !settings.reduced_motion {
+			transform.rotate_z(FALL_SPIN * TAU * time.delta_secs());
+		}
+	}
+}
+
+fn finish_fall(
+	mut timer: ResMut<FallTimer>,
+	real_time: Res<Time<Real>>,
+	pending_high_score: Res<PendingHighScore>,
+	mut next_crash: ResMut<NextState<Crash>>,
+) {
+	if timer.tick(real_time.delta()).just_finished() {
+		next_crash.set(if **pending_high_score {
+			Crash::EnteringInitials
+		} else {
+			Crash::Over
+		});
+	}
+}
+
+pub struct CrashPlugin;
+
+impl Plugin for CrashPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			OnEnter(Crash::Falling),
+			(
+				start_fall.run_if(not(stage_cleared.or(target_reached))),
+				skip_fall.run_if(stage_cleared.or(target_reached)),
+			),
+		)
+		.add_systems(OnExit(Crash::Falling), end_fall)
+		.add_systems(
+			Update,
+			(fall, finish_fall)
+				.run_if(in_state(Crash::Falling).and(not(stage_cleared.or(target_reached)))),
+		);
+	}
+}
@@ -0,0 +1,135 @@
+//! Who made the game and with what, opened with K from the main menu. The list
+//! scrolls up slowly and starts over once it's gone, unless reduced motion is
+//! on.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit, input::PlayerInput, locale::Locale, menu::MenuScreen,
+	settings::motion_allowed, theme::UiTheme,
+};
+
+/// Logical pixels per second.
+const SCROLL_SPEED: f32 = 40.0;
+
+/// Messages of the headings, each with the messages of the lines listed under
+/// it.
+const CREDITS: &[(&str, &[&str])] = &[
+	("credits-made-by", &["credits-author"]),
+	("credits-art", &["credits-art-license", "credits-font"]),
+	("credits-built-with", &["credits-bevy", "credits-crates"]),
+	("credits-license", &["credits-license-file"]),
+	("credits-thanks", &[]),
+];
+
+#[derive(Component)]
+struct CreditsRoll;
+
+fn spawn_credits(mut commands: Commands, locale: Res<Locale>, theme: Res<UiTheme>) {
+	commands
+		.spawn((
+			CleanupOnExit(MenuScreen::Credits),
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				justify_content: JustifyContent::Center,
+				overflow: Overflow::clip(),
+				..default()
+			},
+			BackgroundColor(theme.screen),
+		))
+		.with_children(|parent| {
+			parent
+				.spawn((
+					CreditsRoll,
+					Node {
+						position_type: PositionType::Absolute,
+						top: Val::Px(0.0),
+						flex_direction: FlexDirection::Column,
+						align_items: AlignItems::Center,
+						row_gap: Val::Px(8.0),
+						padding: UiRect::vertical(Val::Px(32.0)),
+						..default()
+					},
+				))
+				.with_children(|roll| {
+					roll.spawn((Text::new(locale.text("credits-title")), theme.text(72.0)));
+					for (heading, lines) in CREDITS {
+						roll.spawn((
+							Text::new(locale.text(heading)),
+							theme.text(36.0),
+							Node {
+								margin: UiRect::top(Val::Px(24.0)),
+								..default()
+							},
+						));
+						for line in *lines {
+							roll.spawn((Text::new(locale.text(line)), theme.text(28.0)));
+						}
+					}
+					roll.spawn((
+						Text::new(locale.text("back")),
+						theme.text(24.0),
+						Node {
+							margin: UiRect::top(Val::Px(24.0)),
+							..default()
+						},
+					));
+				});
+		});
+}
+
+/// Starts from right below the screen once the whole list has scrolled past
+/// the top.
+fn scroll_credits(
+	roll: Single<(&mut Node, &ComputedNode, &ChildOf), With<CreditsRoll>>,
+	screens: Query<&ComputedNode>,
+	time: Res<Time>,
+) {
+	let (mut node, computed, child_of) = roll.into_inner();
+	let Ok(screen) = screens.get(child_of.parent()) else {
+		return;
+	};
+	let height = computed.size().y * computed.inverse_scale_factor();
+	let screen_height = screen.size().y * screen.inverse_scale_factor();
+	let Val::Px(top) = node.top else {
+		return;
+	};
+	let top = top - SCROLL_SPEED * time.delta_secs();
+	node.top = Val::Px(if top < -height { screen_height } else { top });
+}
+
+fn open_credits_on_k(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyK) {
+		next_screen.set(MenuScreen::Credits);
+	}
+}
+
+fn close_credits(
+	input: PlayerInput,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::Escape) || input.confirm_pressed() {
+		next_screen.set(MenuScreen::Main);
+	}
+}
+
+pub struct CreditsPlugin;
+
+impl Plugin for CreditsPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(OnEnter(MenuScreen::Credits), spawn_credits)
+			.add_systems(
+				Update,
+				(
+					open_credits_on_k.run_if(in_state(MenuScreen::Main)),
+					(close_credits, scroll_credits.run_if(motion_allowed))
+						.run_if(in_state(MenuScreen::Credits)),
+				),
+			);
+	}
+}
@@ -0,0 +1,223 @@
+//! The retro filter, turned on under CRT filter in the settings: the game
+//! world is drawn as if on an old CRT screen, with scanlines, a slight bulge
+//! and darker corners, see `assets/shaders/crt.wgsl`. The interface is drawn
+//! over it as usual.
+//!
+//! It runs as a pass of its own in the 2D render graph, right after
+//! tonemapping, for every camera with [`CrtFilter`].
+
+use bevy::{
+	core_pipeline::{
+		core_2d::graph::{Core2d, Node2d},
+		fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+	},
+	ecs::query::QueryItem,
+	prelude::*,
+	render::{
+		extract_component::{
+			ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+			UniformComponentPlugin,
+		},
+		render_graph::{
+			NodeRunError, RenderGraphApp, RenderGraphContext, RenderLabel, ViewNode, ViewNodeRunner,
+		},
+		render_resource::{
+			binding_types::{sampler, texture_2d, uniform_buffer},
+			*,
+		},
+		renderer::{RenderContext, RenderDevice},
+		view::ViewTarget,
+		RenderApp,
+	},
+};
+
+use crate::settings::Settings;
+
+const SHADER_PATH: &str = "shaders/crt.wgsl";
+
+/// Filters what the camera it's on sees, passed to the shader as is. Four
+/// floats keep it to the 16 bytes WebGL wants.
+#[derive(Component, Clone, Copy, ExtractComponent, ShaderType)]
+struct CrtFilter {
+	/// How far the picture bulges out.
+	curvature: f32,
+	/// How much darker the gaps between scanlines are, from 0 to 1.
+	scanlines: f32,
+	/// How much darker the corners are, from 0 to 1.
+	vignette: f32,
+	/// Brightens the picture back up after the scanlines.
+	brightness: f32,
+}
+
+impl Default for CrtFilter {
+	fn default() -> Self {
+		CrtFilter {
+			curvature: 0.04,
+			scanlines: 0.3,
+			vignette: 0.35,
+			brightness: 1.15,
+		}
+	}
+}
+
+/// Puts the filter on every 2D camera or takes it off, as the setting says,
+/// including cameras that come and go like those of split-screen.
+fn apply_setting(
+	mut commands: Commands,
+	settings: Res<Settings>,
+	cameras: Query<(Entity, Has<CrtFilter>), With<Camera2d>>,
+) {
+	for (camera, filtered) in &cameras {
+		if settings.crt_filter && !filtered {
+			commands.entity(camera).insert(CrtFilter::default());
+		} else if !settings.crt_filter && filtered {
+			commands.entity(camera).remove::<CrtFilter>();
+		}
+	}
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+struct CrtLabel;
+
+#[derive(Default)]
+struct CrtNode;
+
+impl ViewNode for CrtNode {
+	type ViewQuery = (&'static ViewTarget, &'static DynamicUniformIndex<CrtFilter>);
+
+	fn run(
+		&self,
+		_graph: &mut RenderGraphContext,
+		render_context: &mut RenderContext,
+		(view_target, uniform_index): QueryItem<Self::ViewQuery>,
+		world: &World,
+	) -> Result<(), NodeRunError> {
+		let crt_pipeline = world.resource::<CrtPipeline>();
+		// Still compiling, the frame goes out unfiltered.
+		let Some(pipeline) = world
+			.resource::<PipelineCache>()
+			.get_render_pipeline(crt_pipeline.pipeline_id)
+		else {
+			return Ok(());
+		};
+		let Some(uniforms) = world
+			.resource::<ComponentUniforms<CrtFilter>>()
+			.uniforms()
+			.binding()
+		else {
+			return Ok(());
+		};
+		let post_process = view_target.post_process_write();
+		let bind_group = render_context.render_device().create_bind_group(
+			"crt_bind_group",
+			&crt_pipeline.layout,
+			&BindGroupEntries::sequential((
+				post_process.source,
+				&crt_pipeline.sampler,
+				uniforms.clone(),
+			)),
+		);
+		let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+			label: Some("crt_pass"),
+			color_attachments: &[Some(RenderPassColorAttachment {
+				view: post_process.destination,
+				resolve_target: None,
+				ops: Operations::default(),
+			})],
+			depth_stencil_attachment: None,
+			timestamp_writes: None,
+			occlusion_query_set: None,
+		});
+		render_pass.set_render_pipeline(pipeline);
+		render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+		render_pass.draw(0..3, 0..1);
+		Ok(())
+	}
+}
+
+#[derive(Resource)]
+struct CrtPipeline {
+	layout: BindGroupLayout,
+	sampler: Sampler,
+	pipeline_id: CachedRenderPipelineId,
+}
+
+impl FromWorld for CrtPipeline {
+	fn from_world(world: &mut World) -> Self {
+		let render_device = world.resource::<RenderDevice>();
+		let layout = render_device.create_bind_group_layout(
+			"crt_bind_group_layout",
+			&BindGroupLayoutEntries::sequential(
+				ShaderStages::FRAGMENT,
+				(
+					texture_2d(TextureSampleType::Float { filterable: true }),
+					sampler(SamplerBindingType::Filtering),
+					uniform_buffer::<CrtFilter>(true),
+				),
+			),
+		);
+		let sampler = render_device.create_sampler(&SamplerDescriptor::default());
+		let shader = world.load_asset(SHADER_PATH);
+		let pipeline_id =
+			world
+				.resource_mut::<PipelineCache>()
+				.queue_render_pipeline(RenderPipelineDescriptor {
+					label: Some("crt_pipeline".into()),
+					layout: vec![layout.clone()],
+					vertex: fullscreen_shader_vertex_state(),
+					fragment: Some(FragmentState {
+						shader,
+						shader_defs: vec![],
+						entry_point: "fragment".into(),
+						targets: vec![Some(ColorTargetState {
+							format: TextureFormat::bevy_default(),
+							blend: None,
+							write_mask: ColorWrites::ALL,
+						})],
+					}),
+					primitive: PrimitiveState::default(),
+					depth_stencil: None,
+					multisample: MultisampleState::default(),
+					push_constant_ranges: vec![],
+					zero_initialize_workgroup_memory: false,
+				});
+		CrtPipeline {
+			layout,
+			sampler,
+			pipeline_id,
+		}
+	}
+}
+
+/// Needs the renderer, so it's left out when running headless.
+pub struct CrtPlugin;
+
+impl Plugin for CrtPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_plugins((
+			ExtractComponentPlugin::<CrtFilter>::default(),
+			UniformComponentPlugin::<CrtFilter>::default(),
+		))
+		.add_systems(Update, apply_setting);
+		let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+			return;
+		};
+		render_app
+			.add_render_graph_node::<ViewNodeRunner<CrtNode>>(Core2d, CrtLabel)
+			.add_render_graph_edges(
+				Core2d,
+				(
+					Node2d::Tonemapping,
+					CrtLabel,
+					Node2d::EndMainPassPostProcessing,
+				),
+			);
+	}
+
+	fn finish(&self, app: &mut App) {
+		let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+			return;
+		};
+		render_app.init_resource::<CrtPipeline>();
+	}
+}
@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+	config::GameConfig,
+	difficulty::{DifficultyPreset, DifficultySettings},
+	locale::Locale,
+	mutators::Mutators,
+	save::SaveData,
+	theme::UiTheme,
+	weather::Weather,
+	GameMode, GameScore, GameStates,
+};
+
+/// Today's date in UTC as a `YYYYMMDD` number, which doubles as the seed of
+/// the daily challenge.
+pub fn today() -> u32 {
+	let days = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |elapsed| elapsed.as_secs() / 86_400) as i64;
+	// Days to civil date, see http://howardhinnant.github.io/date_algorithms.html
+	let z = days + 719_468;
+	let era = z.div_euclid(146_097);
+	let day_of_era = z.rem_euclid(146_097);
+	let year_of_era =
+		(day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let shifted_month = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+	let month = if shifted_month < 10 {
+		shifted_month + 3
+	} else {
+		shifted_month - 9
+	};
+	let year = year_of_era + era * 400 + i64::from(month <= 2);
+	(year * 10_000 + month * 100 + day) as u32
+}
+
+fn format_day(day: u32) -> String {
+	format!("{}-{:02}-{:02}", day / 10_000, day / 100 % 100, day % 100)
+}
+
+/// Persisted daily challenge results. Only the first attempt of each day counts.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct DailyRecord {
+	/// Day of the last official attempt, as `YYYYMMDD`.
+	pub last_day: Option<u32>,
+	pub last_score: i64,
+	pub best_score: i64,
+}
+
+impl DailyRecord {
+	pub fn menu_entry(&self, locale: &Locale) -> String {
+		if self.last_day == Some(today()) {
+			locale.format(
+				"menu-daily-played",
+				&[("today", &self.last_score), ("best", &self.best_score)],
+			)
+		} else {
+			locale.format("menu-daily", &[("best", &self.best_score)])
+		}
+	}
+}
+
+/// The daily run currently being played.
+#[derive(Resource)]
+struct DailyAttempt {
+	day: u32,
+	official: bool,
+}
+
+#[derive(Component)]
+struct DailyText;
+
+fn spawn_daily_text(mut commands: Commands, theme: Res<UiTheme>) {
+	commands
+		.spawn(Node {
+			position_type: PositionType::Absolute,
+			width: Val::Percent(100.0),
+			top: Val::Px(5.0),
+			justify_content: JustifyContent::Center,
+			..default()
+		})
+		.with_child((DailyText, Text::default(), theme.text(28.0)));
+}
+
+/// The daily challenge is always played on Normal without mutators, in clear
+/// weather, so scores are comparable. Set when the run starts so it doesn't
+/// matter how it was started, the menu puts the preferred ones back.
+pub(crate) fn apply_fair_rules(
+	config: Res<GameConfig>,
+	mut settings: ResMut<DifficultySettings>,
+	mut mutators: ResMut<Mutators>,
+	mut weather: ResMut<Weather>,
+) {
+	*settings = DifficultySettings::new(DifficultyPreset::Normal, &config);
+	*mutators = Mutators::default();
+	*weather = Weather::Clear;
+}
+
+/// Whether the run is played by the rules [`apply_fair_rules`] sets, the only
+/// ones scores of different players can be ranked under.
+pub(crate) fn fair_rules(
+	settings: Res<DifficultySettings>,
+	mutators: Res<Mutators>,
+	weather: Res<Weather>,
+) -> bool {
+	settings.preset == DifficultyPreset::Normal
+		&& *mutators == Mutators::default()
+		&& *weather == Weather::Clear
+}
+
+fn start_daily_attempt(mut commands: Commands, mut save: ResMut<SaveData>) {
+	let day = today();
+	let official = save.daily.last_day != Some(day);
+	if official {
+		// Marked as played right away so quitting mid-run doesn't grant another try.
+		save.daily.last_day = Some(day);
+		save.daily.last_score = 0;
+	}
+	commands.insert_resource(DailyAttempt { day, official });
+}
+
+fn record_daily_score(
+	attempt: Res<DailyAttempt>,
+	score: Res<GameScore>,
+	mut save: ResMut<SaveData>,
+) {
+	if !attempt.official {
+		return;
+	}
+	save.daily.last_score = **score;
+	save.daily.best_score = save.daily.best_score.max(**score);
+}
+
+fn update_daily_text(
+	mode: Res<GameMode>,
+	attempt: Option<Res<DailyAttempt>>,
+	locale: Res<Locale>,
+	mut text: Single<&mut Text, With<DailyText>>,
+) {
+	text.0 = match (*mode, attempt) {
+		(GameMode::Daily, Some(attempt)) => locale.format(
+			if attempt.official {
+				"daily-official"
+			} else {
+				"daily-practice"
+			},
+			&[("day", &format_day(attempt.day))],
+		),
+		_ => String::new(),
+	};
+}
+
+pub struct DailyPlugin;
+
+impl Plugin for DailyPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(Startup, spawn_daily_text)
+			.add_systems(
+				OnEnter(GameStates::InGame),
+				(apply_fair_rules, start_daily_attempt).run_if(resource_equals(GameMode::Daily)),
+			)
+			.add_systems(
+				OnEnter(GameStates::GameOver),
+				record_daily_score
+					.run_if(resource_equals(GameMode::Daily).and(crate::counts_for_progress)),
+			)
+			.add_systems(
+				Update,
+				update_daily_text.run_if(
+					resource_changed::<GameMode>.or(resource_exists_and_changed::<DailyAttempt>),
+				),
+			);
+	}
+}
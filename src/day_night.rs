@@ -0,0 +1,126 @@
+//! The sky going through day, dusk, night and dawn as the score goes up, with
+//! the world and the score display tinted to match. High contrast keeps it day.
+
+use bevy::prelude::*;
+
+use crate::{settings::Settings, theme::UiTheme, GameScore, Scoretext};
+
+/// Points scored before the sky moves on to the next time of day.
+const POINTS_PER_PHASE: i64 = 10;
+/// How quickly the tint catches up with the time of day, per second.
+const TINT_RATE: f32 = 1.5;
+/// How much the time of day tints the score display.
+const TEXT_TINT: f32 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimeOfDay {
+	Day,
+	Dusk,
+	Night,
+	Dawn,
+}
+
+impl TimeOfDay {
+	/// In the order they come in, starting with the first one of a run.
+	const ALL: [TimeOfDay; 4] = [
+		TimeOfDay::Day,
+		TimeOfDay::Dusk,
+		TimeOfDay::Night,
+		TimeOfDay::Dawn,
+	];
+
+	fn for_score(score: i64) -> Self {
+		let phase = (score / POINTS_PER_PHASE).rem_euclid(Self::ALL.len() as i64);
+		Self::ALL[phase as usize]
+	}
+
+	fn tint(self) -> Color {
+		match self {
+			TimeOfDay::Day => Color::WHITE,
+			TimeOfDay::Dusk => Color::srgb(1.0, 0.72, 0.55),
+			TimeOfDay::Night => Color::srgb(0.35, 0.4, 0.65),
+			TimeOfDay::Dawn => Color::srgb(0.95, 0.8, 0.85),
+		}
+	}
+}
+
+/// How strongly the time of day tints a sprite, from 0 for not at all to 1 for
+/// the full tint of the sky.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Tinted(pub f32);
+
+/// The tint currently applied, easing towards the one for the time of day.
+#[derive(Resource, Deref, DerefMut, PartialEq)]
+struct SkyTint(Color);
+
+impl Default for SkyTint {
+	fn default() -> Self {
+		SkyTint(TimeOfDay::Day.tint())
+	}
+}
+
+fn ease_sky_tint(
+	score: Res<GameScore>,
+	settings: Res<Settings>,
+	mut sky: ResMut<SkyTint>,
+	time: Res<Time>,
+) {
+	let time_of_day = if settings.high_contrast {
+		TimeOfDay::Day
+	} else {
+		TimeOfDay::for_score(**score)
+	};
+	let target = time_of_day.tint();
+	let factor = 1.0 - (-TINT_RATE * time.delta_secs()).exp();
+	let mut next = sky.mix(&target, factor);
+	// Settles once the difference can't be seen anymore.
+	if next.to_srgba().to_u8_array() == target.to_srgba().to_u8_array() {
+		next = target;
+	}
+	sky.set_if_neq(SkyTint(next));
+}
+
+/// Tints `base`, white for sprites. Only touches the RGB part, fading effects
+/// keep their own alpha.
+fn tint(color: &mut Color, base: Color, sky: Color, strength: f32) {
+	let tint = Color::WHITE.mix(&sky, strength).to_srgba();
+	let base = base.to_srgba();
+	*color = Color::srgba(
+		base.red * tint.red,
+		base.green * tint.green,
+		base.blue * tint.blue,
+		color.alpha(),
+	);
+}
+
+/// The score display is tinted from the text color of the theme.
+fn apply_sky_tint(
+	sky: Res<SkyTint>,
+	theme: Res<UiTheme>,
+	sprites: Query<(&mut Sprite, &Tinted)>,
+	mut score_text: Single<&mut TextColor, With<Scoretext>>,
+) {
+	for (mut sprite, tinted) in sprites {
+		tint(&mut sprite.color, Color::WHITE, **sky, tinted.0);
+	}
+	tint(&mut score_text.0, theme.text, **sky, TEXT_TINT);
+}
+
+pub struct DayNightPlugin;
+
+impl Plugin for DayNightPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<SkyTint>().add_systems(
+			Update,
+			(
+				ease_sky_tint,
+				apply_sky_tint.run_if(
+					resource_changed::<SkyTint>
+						.or(resource_changed::<UiTheme>)
+						.or(any_match_filter::<Added<Tinted>>),
+				),
+			)
+				.chain(),
+		);
+	}
+}
@@ -0,0 +1,62 @@
+//! Crashing flashes the screen white for a few frames, and the game over
+//! screen plays out over a washed out, grayed scene. Both are overlays across
+//! the whole window, the gray one under the rest of the interface. With Reduce
+//! flashing on in the settings the white flash is left out.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit, settings::Settings, stages::stage_cleared, time_attack::target_reached,
+	tween::FadeOut, GameStates, PlayerDiedEvent,
+};
+
+const FLASH_ALPHA: f32 = 0.8;
+const FLASH_DURATION: f32 = 0.1;
+/// Gray the scene fades to, covering it this much.
+const GRAY: Color = Color::srgb(0.45, 0.45, 0.45);
+const GRAY_ALPHA: f32 = 0.55;
+const GRAY_FADE_DURATION: f32 = 0.5;
+
+fn overlay(color: Color, z_index: i32) -> impl Bundle {
+	(
+		Node {
+			position_type: PositionType::Absolute,
+			width: Val::Percent(100.0),
+			height: Val::Percent(100.0),
+			..default()
+		},
+		BackgroundColor(color),
+		GlobalZIndex(z_index),
+		Pickable::IGNORE,
+	)
+}
+
+fn flash_on_death(mut commands: Commands, settings: Res<Settings>) {
+	if settings.reduce_flashing {
+		return;
+	}
+	commands.spawn((
+		overlay(Color::WHITE.with_alpha(FLASH_ALPHA), 1),
+		FadeOut::between(FLASH_DURATION, FLASH_ALPHA, 0.0).in_real_time(),
+	));
+}
+
+fn gray_out(mut commands: Commands) {
+	commands.spawn((
+		overlay(GRAY.with_alpha(0.0), -1),
+		FadeOut::between(GRAY_FADE_DURATION, 0.0, GRAY_ALPHA).in_real_time(),
+		CleanupOnExit(GameStates::GameOver),
+	));
+}
+
+pub struct DeathFlashPlugin;
+
+impl Plugin for DeathFlashPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			OnEnter(GameStates::GameOver),
+			gray_out.run_if(not(stage_cleared.or(target_reached))),
+		)
+		.add_systems(Update, flash_on_death.run_if(on_event::<PlayerDiedEvent>));
+	}
+}
@@ -0,0 +1,226 @@
+//! F3 shows a debug overlay in the top left: frame rate and time, how many
+//! entities and pipes there are, where the bird is and how fast it's going, and
+//! the difficulty values in effect. Shift+F3 outlines every collider and the
+//! box the bird collides with, to see why a crash happened.
+//!
+//! Built with the `dev` feature, F5 and F6 also halve and double how fast the
+//! game runs and F7 stops and starts it, for tuning the physics. They're left
+//! out of other builds, since runs played that way would still count.
+
+use bevy::{
+	diagnostic::{DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin},
+	prelude::*,
+};
+
+#[cfg(feature = "dev")]
+use crate::pause::Pause;
+use crate::{
+	collision::{sprite_collider, Collider, ColliderShape, CollisionLayer},
+	difficulty::{Difficulty, DifficultySettings},
+	theme::UiTheme,
+	Pipe, Player, Velocity,
+};
+
+const PANEL_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+const PLAYER_COLOR: Color = Color::srgb(0.2, 0.6, 1.0);
+const OBSTACLE_COLOR: Color = Color::srgb(1.0, 0.2, 0.2);
+const PICKUP_COLOR: Color = Color::srgb(1.0, 0.9, 0.2);
+const GROUND_COLOR: Color = Color::srgb(0.4, 1.0, 0.4);
+#[cfg(feature = "dev")]
+const MIN_SPEED: f32 = 1.0 / 16.0;
+#[cfg(feature = "dev")]
+const MAX_SPEED: f32 = 4.0;
+
+/// Whether colliders are outlined.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct ShowColliders(bool);
+
+#[derive(Component)]
+struct DebugOverlay;
+
+fn spawn_overlay(mut commands: Commands, theme: Res<UiTheme>) {
+	commands.spawn((
+		DebugOverlay,
+		Text::default(),
+		theme.text(18.0),
+		Node {
+			position_type: PositionType::Absolute,
+			top: Val::Px(80.0),
+			left: Val::Px(5.0),
+			padding: UiRect::all(Val::Px(8.0)),
+			display: Display::None,
+			..default()
+		},
+		BackgroundColor(PANEL_COLOR),
+		GlobalZIndex(i32::MAX),
+	));
+}
+
+fn toggle_on_f3(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut overlay: Single<&mut Node, With<DebugOverlay>>,
+	mut show_colliders: ResMut<ShowColliders>,
+) {
+	if !keyboard_input.just_pressed(KeyCode::F3) {
+		return;
+	}
+	if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+		**show_colliders = !**show_colliders;
+	} else {
+		overlay.display = match overlay.display {
+			Display::None => Display::Flex,
+			_ => Display::None,
+		};
+	}
+}
+
+/// F7 is left alone while the pause menu is open, which stops time itself.
+#[cfg(feature = "dev")]
+fn control_time(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	pause: Option<Res<State<Pause>>>,
+	mut virtual_time: ResMut<Time<Virtual>>,
+) {
+	let speed = virtual_time.relative_speed();
+	if keyboard_input.just_pressed(KeyCode::F5) {
+		virtual_time.set_relative_speed((speed / 2.0).max(MIN_SPEED));
+	}
+	if keyboard_input.just_pressed(KeyCode::F6) {
+		virtual_time.set_relative_speed((speed * 2.0).min(MAX_SPEED));
+	}
+	let paused = pause.is_some_and(|pause| *pause.get() == Pause::Paused);
+	if keyboard_input.just_pressed(KeyCode::F7) && !paused {
+		if virtual_time.is_paused() {
+			virtual_time.unpause();
+		} else {
+			virtual_time.pause();
+		}
+	}
+}
+
+fn colliders_shown(show_colliders: Res<ShowColliders>) -> bool {
+	**show_colliders
+}
+
+/// Draws each shape the way the collision checks see it, and the box around
+/// the bird they check it against.
+fn draw_colliders(
+	mut gizmos: Gizmos,
+	players: Query<(&Transform, &Sprite), With<Player>>,
+	colliders: Query<(&Collider, &Transform, Option<&Sprite>), Without<Player>>,
+) {
+	for (transform, sprite) in &players {
+		let bounds = sprite_collider(transform, sprite);
+		gizmos.rect_2d(bounds.center(), bounds.half_size() * 2.0, PLAYER_COLOR);
+	}
+	for (collider, transform, sprite) in &colliders {
+		let color = match collider.layer {
+			CollisionLayer::Obstacle => OBSTACLE_COLOR,
+			CollisionLayer::Pickup => PICKUP_COLOR,
+			CollisionLayer::Ground => GROUND_COLOR,
+		};
+		let center = transform.translation.truncate();
+		match collider.shape {
+			ColliderShape::Sprite => {
+				let size = sprite
+					.and_then(|sprite| sprite.custom_size)
+					.unwrap_or_default();
+				let angle = transform.rotation.to_euler(EulerRot::ZYX).0;
+				gizmos.rect_2d(Isometry2d::new(center, Rot2::radians(angle)), size, color);
+			}
+			ColliderShape::Box(size) => {
+				gizmos.rect_2d(center, size, color);
+			}
+			ColliderShape::Circle(radius) => {
+				gizmos.circle_2d(center, radius, color);
+			}
+		}
+	}
+}
+
+fn overlay_shown(overlay: Single<&Node, With<DebugOverlay>>) -> bool {
+	overlay.display != Display::None
+}
+
+fn update_overlay(
+	diagnostics: Res<DiagnosticsStore>,
+	pipes: Query<(), With<Pipe>>,
+	players: Query<(&Transform, &Velocity), With<Player>>,
+	settings: Res<DifficultySettings>,
+	difficulty: Res<Difficulty>,
+	virtual_time: Res<Time<Virtual>>,
+	mut overlay: Single<&mut Text, With<DebugOverlay>>,
+) {
+	let smoothed = |path| {
+		diagnostics
+			.get(path)
+			.and_then(|diagnostic| diagnostic.smoothed())
+			.unwrap_or_default()
+	};
+	let mut lines = vec![
+		format!(
+			"FPS: {:.0} ({:.2} ms)",
+			smoothed(&FrameTimeDiagnosticsPlugin::FPS),
+			smoothed(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+		),
+		format!(
+			"Speed: x{}{}",
+			virtual_time.relative_speed(),
+			if virtual_time.is_paused() {
+				" (stopped)"
+			} else {
+				""
+			}
+		),
+		format!(
+			"Entities: {:.0}",
+			smoothed(&EntityCountDiagnosticsPlugin::ENTITY_COUNT)
+		),
+		format!("Pipes in play: {}", pipes.iter().count()),
+	];
+	for (transform, velocity) in &players {
+		lines.push(format!(
+			"Bird: ({:.1}, {:.1}) moving ({:.1}, {:.1})",
+			transform.translation.x, transform.translation.y, velocity.x, velocity.y
+		));
+	}
+	lines.extend([
+		format!(
+			"Difficulty: {}, gravity {:.0}, flap {:.0}",
+			settings.preset.name(),
+			settings.gravity,
+			settings.jump_strength
+		),
+		format!(
+			"Pipes: speed {:.1}, gap {:.1}, every {:.2} s",
+			difficulty.pipe_speed,
+			difficulty.pipe_gap,
+			difficulty.pipe_spawn_interval.as_secs_f32()
+		),
+	]);
+	overlay.0 = lines.join("\n");
+}
+
+pub struct DebugOverlayPlugin;
+
+impl Plugin for DebugOverlayPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_plugins((
+			FrameTimeDiagnosticsPlugin::default(),
+			EntityCountDiagnosticsPlugin,
+		))
+		.init_resource::<ShowColliders>()
+		.add_systems(Startup, spawn_overlay)
+		.add_systems(
+			Update,
+			(
+				toggle_on_f3,
+				update_overlay.run_if(overlay_shown),
+				draw_colliders.run_if(colliders_shown),
+			)
+				.chain(),
+		);
+		#[cfg(feature = "dev")]
+		app.add_systems(Update, control_time.before(update_overlay));
+	}
+}
@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	config::{GameConfig, PipeConfig},
+	weather::{Weather, SNOW_PIPE_SPEED_SCALE},
+	GameMode, GameScore, GameplaySet, InRun, PipeSpawnTimer,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Reflect)]
+pub enum DifficultyPreset {
+	Easy,
+	#[default]
+	Normal,
+	Hard,
+}
+
+impl DifficultyPreset {
+	pub const ALL: [DifficultyPreset; 3] = [
+		DifficultyPreset::Easy,
+		DifficultyPreset::Normal,
+		DifficultyPreset::Hard,
+	];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			DifficultyPreset::Easy => "Easy",
+			DifficultyPreset::Normal => "Normal",
+			DifficultyPreset::Hard => "Hard",
+		}
+	}
+}
+
+/// Physics and pipe parameters chosen before a run starts, as tuned in the
+/// [`GameConfig`].
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource)]
+pub struct DifficultySettings {
+	pub preset: DifficultyPreset,
+	pub gravity: f32,
+	pub jump_strength: f32,
+	pub pipe_gap: f32,
+	pub pipe_spawn_interval: f32,
+	pub pipes: PipeConfig,
+}
+
+impl Default for DifficultySettings {
+	fn default() -> Self {
+		DifficultySettings::new(DifficultyPreset::default(), &GameConfig::default())
+	}
+}
+
+impl DifficultySettings {
+	pub fn new(preset: DifficultyPreset, config: &GameConfig) -> Self {
+		let tuning = config.preset(preset);
+		DifficultySettings {
+			preset,
+			gravity: tuning.gravity,
+			jump_strength: tuning.jump_strength,
+			pipe_gap: tuning.pipe_gap,
+			pipe_spawn_interval: tuning.pipe_spawn_interval,
+			pipes: config.pipes,
+		}
+	}
+}
+
+/// The currently active pipe parameters, scaled up as the score increases.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource)]
+pub struct Difficulty {
+	pub pipe_speed: f32,
+	pub pipe_gap: f32,
+	pub pipe_spawn_interval: Duration,
+}
+
+/// Scales the pace of the pipes, both how fast they move and how often they
+/// come, for as long as something like a power-up needs it.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Deref, DerefMut)]
+pub struct PaceModifier(pub f32);
+
+impl Default for PaceModifier {
+	fn default() -> Self {
+		PaceModifier(1.0)
+	}
+}
+
+impl FromWorld for Difficulty {
+	fn from_world(world: &mut World) -> Self {
+		Difficulty::for_score(world.resource::<DifficultySettings>(), 0)
+	}
+}
+
+impl Difficulty {
+	pub fn for_score(settings: &DifficultySettings, score: i64) -> Self {
+		let pipes = &settings.pipes;
+		let progress = (score - pipes.ramp_start_score).max(0) as f32;
+		let spawn_interval =
+			settings.pipe_spawn_interval - progress * pipes.spawn_interval_per_point;
+		Difficulty {
+			pipe_speed: (pipes.speed + progress * pipes.speed_per_point).min(pipes.max_speed),
+			pipe_gap: (settings.pipe_gap - progress * pipes.gap_per_point).max(pipes.min_gap),
+			pipe_spawn_interval: Duration::from_secs_f32(
+				spawn_interval.max(pipes.min_spawn_interval),
+			),
+		}
+	}
+
+	fn for_mode(self, settings: &DifficultySettings, mode: GameMode) -> Self {
+		if mode != GameMode::Hardcore {
+			return self;
+		}
+		Difficulty {
+			pipe_speed: self.pipe_speed * settings.pipes.hardcore_speed_scale,
+			pipe_gap: self.pipe_gap * settings.pipes.hardcore_gap_scale,
+			..self
+		}
+	}
+
+	fn with_weather(self, weather: Weather) -> Self {
+		if weather != Weather::Snow {
+			return self;
+		}
+		Difficulty {
+			pipe_speed: self.pipe_speed * SNOW_PIPE_SPEED_SCALE,
+			..self
+		}
+	}
+
+	fn with_pace(self, pace: f32) -> Self {
+		Difficulty {
+			pipe_speed: self.pipe_speed * pace,
+			pipe_spawn_interval: self.pipe_spawn_interval.div_f32(pace),
+			..self
+		}
+	}
+}
+
+fn reset_difficulty(
+	settings: Res<DifficultySettings>,
+	mode: Res<GameMode>,
+	weather: Res<Weather>,
+	mut difficulty: ResMut<Difficulty>,
+	mut pace: ResMut<PaceModifier>,
+	mut pipe_spawn_timer: ResMut<PipeSpawnTimer>,
+) {
+	*difficulty = Difficulty::for_score(&settings, 0)
+		.for_mode(&settings, *mode)
+		.with_weather(*weather);
+	*pace = PaceModifier::default();
+	pipe_spawn_timer
+		.timer
+		.set_duration(difficulty.pipe_spawn_interval);
+}
+
+fn update_difficulty(
+	score: Res<GameScore>,
+	settings: Res<DifficultySettings>,
+	mode: Res<GameMode>,
+	weather: Res<Weather>,
+	pace: Res<PaceModifier>,
+	mut difficulty: ResMut<Difficulty>,
+	mut pipe_spawn_timer: ResMut<PipeSpawnTimer>,
+) {
+	if !score.is_changed() && !pace.is_changed() && !settings.is_changed() {
+		return;
+	}
+	let next = Difficulty::for_score(&settings, **score)
+		.for_mode(&settings, *mode)
+		.with_weather(*weather)
+		.with_pace(**pace);
+	if *difficulty == next {
+		return;
+	}
+	pipe_spawn_timer
+		.timer
+		.set_duration(next.pipe_spawn_interval);
+	*difficulty = next;
+}
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<DifficultySettings>()
+			.init_resource::<Difficulty>()
+			.init_resource::<PaceModifier>()
+			.add_systems(OnEnter(InRun), reset_difficulty)
+			.add_systems(FixedUpdate, update_difficulty.in_set(GameplaySet::Progress));
+	}
+}
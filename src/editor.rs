@@ -0,0 +1,517 @@
+//! A level editor for obstacle patterns. Pipes are placed and moved with the
+//! mouse along a timeline scrolling sideways, played on the spot, and exported
+//! in the format of [`layouts`](crate::layouts).
+
+use std::ops::Range;
+
+use bevy::{
+	ecs::system::SystemParam,
+	input::mouse::{MouseScrollUnit, MouseWheel},
+	prelude::*,
+	window::PrimaryWindow,
+};
+
+use crate::{
+	difficulty::{Difficulty, DifficultySettings},
+	ground::ground_top,
+	layouts::{AuthoredColumn, AuthoredGap, AuthoredPattern, AuthoredPatterns},
+	locale::Locale,
+	mirror::ScrollDirection,
+	pipe_patterns::Gap,
+	pipe_pool::PipeSpawner,
+	play_area::PlayArea,
+	save::{read_ron, write_ron},
+	sprites::Textures,
+	theme::UiTheme,
+	GameMode, GameStates, GameplaySet, InRun, MainCamera, PipesPassed, PIPE_WIDTH,
+};
+
+const EXPORT_FILE_NAME: &str = "editor.layouts.ron";
+/// Where the start of the timeline is, from the left of the play area.
+const TIMELINE_MARGIN: f32 = 160.0;
+/// Columns snap to multiples of this distance along the timeline.
+const GRID: f32 = 8.0;
+const SCROLL_SPEED: f32 = 800.0;
+/// Distance scrolled per line of a mouse wheel.
+const WHEEL_SCROLL: f32 = 40.0;
+const SIZE_STEP: f32 = 0.1;
+const SIZES: Range<f32> = 0.5..2.0;
+const COIN_SIZE: Vec2 = Vec2::splat(24.0);
+const BAR_THICKNESS: f32 = 16.0;
+const BAR_LENGTH_SCALE: f32 = 0.75;
+const HOVER_COLOR: Color = Color::srgb(1.0, 0.85, 0.4);
+
+/// A pair of pipes, `x` along the timeline.
+#[derive(Debug, Clone)]
+struct EditorColumn {
+	x: f32,
+	gap: AuthoredGap,
+}
+
+/// The pattern being edited, kept while it's played and between visits to
+/// the editor.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct EditorLayout(Vec<EditorColumn>);
+
+impl EditorLayout {
+	/// Only the first gap of each column, the editor places one per column.
+	fn from_pattern(pattern: &AuthoredPattern) -> Self {
+		let mut x = 0.0;
+		let columns = pattern
+			.columns
+			.iter()
+			.enumerate()
+			.filter_map(|(i, column)| {
+				if i > 0 {
+					x += column.spacing;
+				}
+				let gap = column.gaps.first()?.clone();
+				Some(EditorColumn { x, gap })
+			});
+		EditorLayout(columns.collect())
+	}
+
+	/// The columns in the order they come in, the first one at the start of
+	/// the pattern.
+	fn to_pattern(&self) -> AuthoredPattern {
+		let mut columns = self.0.clone();
+		columns.sort_by(|a, b| a.x.total_cmp(&b.x));
+		let mut last_x = columns.first().map_or(0.0, |column| column.x);
+		AuthoredPattern {
+			name: "Editor".to_string(),
+			from_score: 0,
+			weight: 1,
+			columns: columns
+				.into_iter()
+				.map(|column| {
+					let spacing = column.x - last_x;
+					last_x = column.x;
+					AuthoredColumn {
+						spacing,
+						gaps: vec![column.gap],
+					}
+				})
+				.collect(),
+		}
+	}
+
+	/// The column under `x` on the timeline, if any.
+	fn at(&self, x: f32) -> Option<usize> {
+		self.0
+			.iter()
+			.position(|column| (column.x - x).abs() <= PIPE_WIDTH / 2.0)
+	}
+}
+
+/// How far along the timeline the view has scrolled.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct TimelineScroll(f32);
+
+#[derive(Resource, Default, Deref, DerefMut, PartialEq)]
+struct Hovered(Option<usize>);
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct Dragging(Option<usize>);
+
+/// The pattern being played from the editor, and how many pipes it takes to
+/// get through it.
+#[derive(Resource)]
+struct Playtest {
+	pattern: AuthoredPattern,
+	goal: u32,
+}
+
+#[derive(Component)]
+struct EditorUi;
+
+#[derive(Component)]
+struct StatusText;
+
+/// Part of the preview of the column at this index.
+#[derive(Component)]
+struct EditorPiece(usize);
+
+/// Gaps are sized like at the start of a run.
+fn gap_height(settings: &DifficultySettings) -> f32 {
+	Difficulty::for_score(settings, 0).pipe_gap
+}
+
+fn gap_bounds(play_area: &PlayArea) -> Range<f32> {
+	ground_top(play_area)..play_area.max.y
+}
+
+/// Where the timeline is on screen.
+#[derive(SystemParam)]
+struct Timeline<'w> {
+	play_area: Res<'w, PlayArea>,
+	scroll: Res<'w, TimelineScroll>,
+	settings: Res<'w, DifficultySettings>,
+}
+
+impl Timeline<'_> {
+	/// World x coordinate of the start of the timeline.
+	fn start(&self) -> f32 {
+		self.play_area.min.x + TIMELINE_MARGIN - **self.scroll
+	}
+
+	fn place(&self, gap: &AuthoredGap) -> Gap {
+		gap.place(gap_height(&self.settings), &gap_bounds(&self.play_area))
+	}
+
+	/// The position of `gap` for it to be centered on the world y coordinate.
+	fn position_at(&self, y: f32, gap: &AuthoredGap) -> f32 {
+		let bounds = gap_bounds(&self.play_area);
+		let height = gap_height(&self.settings) * gap.size;
+		let range = bounds.end - height - bounds.start;
+		if range > 0.0 {
+			((y - height / 2.0 - bounds.start) / range).clamp(0.0, 1.0)
+		} else {
+			0.0
+		}
+	}
+}
+
+fn spawn_editor_ui(mut commands: Commands, locale: Res<Locale>, theme: Res<UiTheme>) {
+	commands.spawn((
+		EditorUi,
+		Node {
+			position_type: PositionType::Absolute,
+			bottom: Val::Px(5.0),
+			left: Val::Px(5.0),
+			right: Val::Px(5.0),
+			justify_content: JustifyContent::SpaceBetween,
+			align_items: AlignItems::End,
+			..default()
+		},
+		children![
+			(Text::new(locale.text("editor-help")), theme.text(20.0)),
+			(StatusText, Text::default(), theme.text(20.0)),
+		],
+	));
+}
+
+fn despawn_editor(
+	mut commands: Commands,
+	ui: Single<Entity, With<EditorUi>>,
+	pieces: Query<Entity, With<EditorPiece>>,
+	mut dragging: ResMut<Dragging>,
+) {
+	commands.entity(*ui).despawn();
+	for entity in pieces {
+		commands.entity(entity).despawn();
+	}
+	**dragging = None;
+}
+
+fn scroll_timeline(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut wheel: EventReader<MouseWheel>,
+	mut scroll: ResMut<TimelineScroll>,
+	time: Res<Time>,
+) {
+	let mut distance = 0.0;
+	if keyboard_input.pressed(KeyCode::ArrowRight) {
+		distance += SCROLL_SPEED * time.delta_secs();
+	}
+	if keyboard_input.pressed(KeyCode::ArrowLeft) {
+		distance -= SCROLL_SPEED * time.delta_secs();
+	}
+	for event in wheel.read() {
+		let lines = match event.unit {
+			MouseScrollUnit::Line => event.y,
+			MouseScrollUnit::Pixel => event.y / WHEEL_SCROLL,
+		};
+		distance -= lines * WHEEL_SCROLL;
+	}
+	if distance != 0.0 {
+		**scroll = (**scroll + distance).max(0.0);
+	}
+}
+
+/// Adds, drags and removes columns with the mouse.
+fn edit_with_mouse(
+	window: Single<&Window, With<PrimaryWindow>>,
+	camera: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+	mouse: Res<ButtonInput<MouseButton>>,
+	timeline: Timeline,
+	mut layout: ResMut<EditorLayout>,
+	mut hovered: ResMut<Hovered>,
+	mut dragging: ResMut<Dragging>,
+) {
+	let (camera, camera_transform) = *camera;
+	let Some(cursor) = window
+		.cursor_position()
+		.and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+	else {
+		return;
+	};
+	let x = cursor.x - timeline.start();
+	let snapped_x = (x / GRID).round() * GRID;
+	if mouse.just_released(MouseButton::Left) {
+		**dragging = None;
+	}
+	if mouse.just_pressed(MouseButton::Right) {
+		if let Some(index) = layout.at(x) {
+			layout.remove(index);
+			**dragging = None;
+		}
+	}
+	if mouse.just_pressed(MouseButton::Left) {
+		let index = layout.at(x).unwrap_or_else(|| {
+			layout.push(EditorColumn {
+				x: snapped_x,
+				gap: AuthoredGap {
+					position: 0.5,
+					size: 1.0,
+					safe: true,
+					coin: true,
+					spinning_bar: false,
+				},
+			});
+			layout.len() - 1
+		});
+		**dragging = Some(index);
+	}
+	if let Some(column) = dragging.and_then(|index| layout.get(index)) {
+		let moved = EditorColumn {
+			x: snapped_x,
+			gap: AuthoredGap {
+				position: timeline.position_at(cursor.y, &column.gap),
+				..column.gap.clone()
+			},
+		};
+		if moved.x != column.x || moved.gap.position != column.gap.position {
+			let index = dragging.unwrap_or_default();
+			layout[index] = moved;
+		}
+	}
+	hovered.set_if_neq(Hovered(dragging.or(layout.at(x))));
+}
+
+fn edit_with_keys(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	hovered: Res<Hovered>,
+	mut layout: ResMut<EditorLayout>,
+) {
+	let Some(index) = **hovered else {
+		return;
+	};
+	let Some(column) = layout.get(index) else {
+		return;
+	};
+	let mut gap = column.gap.clone();
+	if keyboard_input.just_pressed(KeyCode::KeyC) {
+		gap.coin = !gap.coin;
+	}
+	if keyboard_input.just_pressed(KeyCode::KeyB) {
+		gap.spinning_bar = !gap.spinning_bar;
+	}
+	if keyboard_input.just_pressed(KeyCode::Equal)
+		|| keyboard_input.just_pressed(KeyCode::NumpadAdd)
+	{
+		gap.size = (gap.size + SIZE_STEP).min(SIZES.end);
+	}
+	if keyboard_input.just_pressed(KeyCode::Minus)
+		|| keyboard_input.just_pressed(KeyCode::NumpadSubtract)
+	{
+		gap.size = (gap.size - SIZE_STEP).max(SIZES.start);
+	}
+	if keyboard_input.get_just_pressed().next().is_some() {
+		layout[index].gap = gap;
+	}
+}
+
+/// Redraws every column, which is plenty fast for a handful of pipes.
+fn draw_preview(
+	mut commands: Commands,
+	textures: Res<Textures>,
+	layout: Res<EditorLayout>,
+	timeline: Timeline,
+	pieces: Query<Entity, With<EditorPiece>>,
+) {
+	for entity in pieces {
+		commands.entity(entity).despawn();
+	}
+	let play_area = &timeline.play_area;
+	for (i, column) in layout.iter().enumerate() {
+		let x = timeline.start() + column.x;
+		let gap = timeline.place(&column.gap);
+		let below = gap.bottom - play_area.min.y;
+		let above = play_area.max.y - gap.top();
+		commands.spawn((
+			EditorPiece(i),
+			textures.pipe_sprite(Vec2::new(PIPE_WIDTH, below)),
+			Transform::from_xyz(x, play_area.min.y + below / 2.0, 0.0),
+		));
+		commands.spawn((
+			EditorPiece(i),
+			textures.pipe_sprite(Vec2::new(PIPE_WIDTH, above)),
+			Transform::from_xyz(x, gap.top() + above / 2.0, 0.0),
+		));
+		let middle = gap.bottom + gap.height / 2.0;
+		if gap.spinning_bar {
+			commands.spawn((
+				EditorPiece(i),
+				textures.pipe_sprite(Vec2::new(BAR_THICKNESS, gap.height * BAR_LENGTH_SCALE)),
+				Transform::from_xyz(x, middle, 0.0)
+					.with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2)),
+			));
+		}
+		if gap.coin {
+			commands.spawn((
+				EditorPiece(i),
+				textures.coin_sprite(COIN_SIZE),
+				Transform::from_xyz(x, middle, 1.0),
+			));
+		}
+	}
+}
+
+fn highlight_hovered(hovered: Res<Hovered>, pieces: Query<(&EditorPiece, &mut Sprite)>) {
+	for (piece, mut sprite) in pieces {
+		sprite.color = if **hovered == Some(piece.0) {
+			HOVER_COLOR
+		} else {
+			Color::WHITE
+		};
+	}
+}
+
+fn export_on_e(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	layout: Res<EditorLayout>,
+	locale: Res<Locale>,
+	mut status: Single<&mut Text, With<StatusText>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyE) {
+		write_ron(
+			EXPORT_FILE_NAME,
+			&AuthoredPatterns(vec![layout.to_pattern()]),
+		);
+		status.0 = locale.format("editor-exported", &[("file", &EXPORT_FILE_NAME)]);
+	}
+}
+
+fn play_on_p(
+	mut commands: Commands,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	layout: Res<EditorLayout>,
+	mut mode: ResMut<GameMode>,
+	mut next_state: ResMut<NextState<GameStates>>,
+	locale: Res<Locale>,
+	mut status: Single<&mut Text, With<StatusText>>,
+) {
+	if !keyboard_input.just_pressed(KeyCode::KeyP) {
+		return;
+	}
+	if layout.is_empty() {
+		status.0 = locale.text("editor-empty");
+		return;
+	}
+	let pattern = layout.to_pattern();
+	let goal = pattern
+		.columns
+		.iter()
+		.flat_map(|column| &column.gaps)
+		.filter(|gap| gap.safe)
+		.count() as u32;
+	commands.insert_resource(Playtest { pattern, goal });
+	*mode = GameMode::Playtest;
+	next_state.set(GameStates::InGame);
+}
+
+fn leave_on_escape(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::Escape) {
+		next_state.set(GameStates::Menu);
+	}
+}
+
+/// The whole pattern is laid out ahead of the bird right away.
+fn spawn_playtest(
+	mut pipes: PipeSpawner,
+	playtest: Res<Playtest>,
+	play_area: Res<PlayArea>,
+	direction: Res<ScrollDirection>,
+	settings: Res<DifficultySettings>,
+) {
+	let columns = playtest
+		.pattern
+		.columns(gap_height(&settings), &gap_bounds(&play_area));
+	for column in columns {
+		pipes.spawn_column(
+			direction.entry_x(&play_area) - direction.sign() * column.offset,
+			play_area.height(),
+			&column.gaps,
+		);
+	}
+}
+
+fn finish_playtest(
+	playtest: Res<Playtest>,
+	pipes_passed: Res<PipesPassed>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if **pipes_passed >= playtest.goal {
+		next_state.set(GameStates::GameOver);
+	}
+}
+
+/// Playtests end by going back to the editor, crashed or not.
+fn back_to_editor(mut next_state: ResMut<NextState<GameStates>>) {
+	next_state.set(GameStates::Editor);
+}
+
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+	fn build(&self, app: &mut App) {
+		let layout = read_ron::<AuthoredPatterns>(EXPORT_FILE_NAME)
+			.and_then(|patterns| patterns.0.first().map(EditorLayout::from_pattern))
+			.unwrap_or_default();
+		app.insert_resource(layout)
+			.init_resource::<TimelineScroll>()
+			.init_resource::<Hovered>()
+			.init_resource::<Dragging>()
+			.add_systems(OnEnter(GameStates::Editor), (spawn_editor_ui, draw_preview))
+			.add_systems(OnExit(GameStates::Editor), despawn_editor)
+			.add_systems(
+				OnEnter(InRun),
+				spawn_playtest
+					.after(crate::on_enter_game)
+					.run_if(resource_equals(GameMode::Playtest)),
+			)
+			.add_systems(
+				OnEnter(GameStates::GameOver),
+				back_to_editor.run_if(resource_equals(GameMode::Playtest)),
+			)
+			.add_systems(
+				FixedUpdate,
+				finish_playtest
+					.after(crate::apply_scored)
+					.in_set(GameplaySet::Scoring)
+					.run_if(resource_equals(GameMode::Playtest)),
+			)
+			.add_systems(
+				Update,
+				(
+					scroll_timeline,
+					edit_with_mouse,
+					edit_with_keys,
+					draw_preview.run_if(
+						resource_changed::<EditorLayout>
+							.or(resource_changed::<TimelineScroll>)
+							.or(resource_changed::<PlayArea>),
+					),
+					highlight_hovered,
+					export_on_e,
+					play_on_p,
+					leave_on_escape,
+				)
+					.chain()
+					.run_if(in_state(GameStates::Editor)),
+			);
+	}
+}
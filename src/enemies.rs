@@ -0,0 +1,108 @@
+//! Hostile birds flying at the player faster than the pipes, bobbing up and
+//! down on the way.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+	cleanup::RunScene,
+	difficulty::Difficulty,
+	ground::ground_top,
+	mirror::ScrollDirection,
+	moving_gaps::Oscillator,
+	offscreen::DespawnOffscreen,
+	seed::GameRng,
+	sprites::{SpriteAnimation, Textures},
+	GameplaySet, InRun, Obstacle, SpawnContext, Velocity, PLAYER_SIZE,
+};
+
+/// Score from which enemy birds show up. Nothing is drawn from the game's
+/// randomness before then, so the start of a run plays out the same as it did
+/// before there were enemies.
+const ENEMY_START_SCORE: i64 = 8;
+/// Seconds between two enemies, in game time.
+const ENEMY_INTERVAL: f32 = 6.0;
+/// How much faster than the pipes enemies fly.
+const ENEMY_SPEED_SCALE: f32 = 1.6;
+const ENEMY_BOB_HEIGHT: f32 = 16.0;
+const ENEMY_BOBS_PER_SECOND: f32 = 1.5;
+const ENEMY_COLOR: Color = Color::srgb(1.0, 0.45, 0.45);
+
+#[derive(Component)]
+#[require(Obstacle, Velocity, RunScene, DespawnOffscreen = DespawnOffscreen::new(PLAYER_SIZE.x))]
+pub struct Enemy;
+
+#[derive(Resource, Deref, DerefMut)]
+struct EnemySpawnTimer(Timer);
+
+impl Default for EnemySpawnTimer {
+	fn default() -> Self {
+		EnemySpawnTimer(Timer::from_seconds(ENEMY_INTERVAL, TimerMode::Repeating))
+	}
+}
+
+fn reset_enemy_timer(mut timer: ResMut<EnemySpawnTimer>) {
+	*timer = EnemySpawnTimer::default();
+}
+
+fn spawn_enemies(
+	mut commands: Commands,
+	mut timer: ResMut<EnemySpawnTimer>,
+	time: Res<Time>,
+	textures: Res<Textures>,
+	context: SpawnContext,
+	mut game_rng: ResMut<GameRng>,
+) {
+	let (play_area, direction) = (&context.play_area, &context.direction);
+	if **context.score < ENEMY_START_SCORE || !timer.tick(time.delta()).just_finished() {
+		return;
+	}
+	let lowest = ground_top(play_area) + PLAYER_SIZE.y;
+	let highest = play_area.max.y - PLAYER_SIZE.y;
+	let y = if highest > lowest {
+		game_rng.random_range(lowest..highest)
+	} else {
+		lowest
+	};
+	let mut sprite = textures.bird_sprite(PLAYER_SIZE);
+	sprite.color = ENEMY_COLOR;
+	// Faces the way it flies, towards the player.
+	sprite.flip_x = !direction.flip_x();
+	commands.spawn((
+		Enemy,
+		sprite,
+		SpriteAnimation::bird(),
+		Transform::from_xyz(direction.entry_x(play_area), y, 1.0),
+		Oscillator::new(ENEMY_BOB_HEIGHT, ENEMY_BOBS_PER_SECOND),
+	));
+}
+
+fn fly_enemies(
+	difficulty: Res<Difficulty>,
+	direction: Res<ScrollDirection>,
+	enemies: Query<&mut Velocity, With<Enemy>>,
+) {
+	for mut velocity in enemies {
+		velocity.x = direction.sign() * difficulty.pipe_speed * ENEMY_SPEED_SCALE;
+	}
+}
+
+pub struct EnemiesPlugin;
+
+impl Plugin for EnemiesPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<EnemySpawnTimer>()
+			.add_systems(OnEnter(InRun), reset_enemy_timer)
+			.add_systems(
+				FixedUpdate,
+				(
+					fly_enemies
+						.before(crate::apply_velocity)
+						.in_set(GameplaySet::Physics),
+					spawn_enemies
+						.after(crate::handle_pipe_spawn)
+						.in_set(GameplaySet::Spawn),
+				),
+			);
+	}
+}
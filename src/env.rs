@@ -0,0 +1,162 @@
+//! [`FlappyEnv`], for bots and reinforcement learning experiments to play
+//! endless runs through the real game logic, one simulation tick at a time, in
+//! the style of a Gym environment. It runs headless like `--headless` does, see
+//! [`headless`](crate::headless), and looks for the assets folder where the game
+//! does.
+
+use bevy::{app::PluginsState, prelude::*};
+
+use crate::{
+	cli::LaunchOptions, get_ready::RunPhase, next_gap, GameScore, GameStates, PendingFlap, Pipe,
+	Player, Velocity,
+};
+
+/// What the bot gets to see after every step. Positions are in world
+/// coordinates, with the origin in the middle of the screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Observation {
+	pub bird_y: f32,
+	/// Upwards is positive.
+	pub bird_velocity: f32,
+	/// Middle of the next gap to fly through, if one has come in yet.
+	pub next_gap: Option<Vec2>,
+	pub score: i64,
+	/// The run is over, it's time to [`reset`](FlappyEnv::reset).
+	pub done: bool,
+}
+
+/// The game, played by calling [`step`](FlappyEnv::step) once per tick.
+pub struct FlappyEnv {
+	app: App,
+}
+
+impl FlappyEnv {
+	/// Every run gets a fresh random seed.
+	pub fn new() -> Self {
+		Self::from_options(LaunchOptions::default())
+	}
+
+	/// Every run plays out with the same pipes.
+	pub fn with_seed(seed: u64) -> Self {
+		Self::from_options(LaunchOptions {
+			seed: Some(seed),
+			..default()
+		})
+	}
+
+	fn from_options(options: LaunchOptions) -> Self {
+		FlappyEnv {
+			app: headless_app(options),
+		}
+	}
+
+	/// Starts a new run and returns what it looks like before the first tick.
+	/// A run still going is given up.
+	pub fn reset(&mut self) -> Observation {
+		// Starting a run over from itself would leave the old one in place, so
+		// it goes by way of the menu.
+		if !is_in(self.app.world(), GameStates::Menu) {
+			self.set_state(GameStates::Menu);
+			self.advance_until(|world| is_in(world, GameStates::Menu));
+		}
+		self.set_state(GameStates::InGame);
+		self.advance_until(|world| {
+			world
+				.get_resource::<State<RunPhase>>()
+				.is_some_and(|phase| *phase.get() == RunPhase::GetReady)
+		});
+		self.observe()
+	}
+
+	/// Simulates one tick, flapping first if asked to. Does nothing once the
+	/// run is over.
+	pub fn step(&mut self, flap: bool) -> Observation {
+		if self.is_done() {
+			return self.observe();
+		}
+		if flap {
+			let now = self.app.world().resource::<Time<Real>>().elapsed_secs();
+			self.app
+				.world_mut()
+				.resource_mut::<PendingFlap>()
+				.press(now);
+		}
+		// Headless, every update simulates exactly one tick.
+		self.app.update();
+		self.observe()
+	}
+
+	fn set_state(&mut self, state: GameStates) {
+		self.app
+			.world_mut()
+			.resource_mut::<NextState<GameStates>>()
+			.set(state);
+	}
+
+	fn advance_until(&mut self, done: impl Fn(&World) -> bool) {
+		while !done(self.app.world()) {
+			self.app.update();
+		}
+	}
+
+	/// Also once the crash is certain, before the game over it leads to.
+	fn is_done(&self) -> bool {
+		let world = self.app.world();
+		!is_in(world, GameStates::InGame)
+			|| !matches!(
+				*world.resource::<NextState<GameStates>>(),
+				NextState::Unchanged
+			)
+	}
+
+	fn observe(&mut self) -> Observation {
+		let done = self.is_done();
+		let world = self.app.world_mut();
+		let (bird, velocity) = world
+			.query_filtered::<(&Transform, &Velocity), With<Player>>()
+			.single(world)
+			.map_or((Vec3::ZERO, 0.0), |(transform, velocity)| {
+				(transform.translation, velocity.y)
+			});
+		let next_gap = next_gap(
+			bird.x,
+			world.query::<(&Pipe, &Transform, &Sprite)>().iter(world),
+		);
+		Observation {
+			bird_y: bird.y,
+			bird_velocity: velocity,
+			next_gap,
+			score: **world.resource::<GameScore>(),
+			done,
+		}
+	}
+}
+
+impl Default for FlappyEnv {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// The game without a window, finished setting up and waiting in the menu.
+pub(crate) fn headless_app(options: LaunchOptions) -> App {
+	let mut app = crate::game_app(LaunchOptions {
+		headless: true,
+		..options
+	});
+	while app.plugins_state() == PluginsState::Adding {
+		bevy::tasks::tick_global_task_pools_on_main_thread();
+	}
+	app.finish();
+	app.cleanup();
+	while !is_in(app.world(), GameStates::Menu) {
+		app.update();
+	}
+	app
+}
+
+pub(crate) fn is_in(world: &World, state: GameStates) -> bool {
+	world
+		.get_resource::<State<GameStates>>()
+		.is_some_and(|current| *current.get() == state)
+}
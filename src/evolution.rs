@@ -0,0 +1,340 @@
+//! Neuroevolution, a showcase where a flock of birds learns to fly by itself,
+//! opened with V from the main menu. Each bird is flown by a tiny neural
+//! network. Once the whole generation has crashed, the networks of the birds
+//! that lasted longest are kept, and copied with small random changes for the
+//! rest of the next one.
+//!
+//! The flock flies through the plain pipes of the [`sandbox`](crate::sandbox).
+
+use std::cmp::Reverse;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::Rng;
+
+use crate::{
+	cleanup::CleanupOnExit,
+	collision::sprite_collider,
+	difficulty::{Difficulty, DifficultySettings},
+	locale::Locale,
+	menu::MenuScreen,
+	mirror::ScrollDirection,
+	next_gap,
+	pipe_pool::PipeSpawner,
+	play_area::PlayArea,
+	sandbox::{self, Sandbox, SandboxSet},
+	seed::{GameRng, SeedSettings},
+	sprites::{SpriteAnimation, Textures},
+	theme::UiTheme,
+	GameStates, Pipe, Velocity, PLAYER_SIZE,
+};
+
+const POPULATION: usize = 50;
+/// The best networks of a generation, flown again unchanged in the next.
+const ELITES: usize = 5;
+/// Only the best part of a generation gets copied into the next.
+const PARENT_SHARE: f32 = 0.25;
+/// Chance of each weight changing in a copy, and by how much at most.
+const MUTATION_RATE: f32 = 0.2;
+const MUTATION_SIZE: f32 = 0.5;
+
+/// Height of the bird, its speed, and how far off the next gap is across and
+/// up.
+const INPUTS: usize = 4;
+const HIDDEN: usize = 6;
+/// A bias and a weight per input for each hidden neuron, then a bias and a
+/// weight per hidden neuron for the output.
+const WEIGHTS: usize = (INPUTS + 1) * HIDDEN + HIDDEN + 1;
+
+const BIRD_ALPHA: f32 = 0.35;
+/// Birds flying with the networks kept from the last generation.
+const ELITE_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// The weights of a network with one hidden layer.
+#[derive(Clone)]
+struct Brain([f32; WEIGHTS]);
+
+impl Brain {
+	fn random(rng: &mut impl Rng) -> Self {
+		Brain(std::array::from_fn(|_| rng.random_range(-1.0..1.0)))
+	}
+
+	fn mutated(&self, rng: &mut impl Rng) -> Self {
+		let mut weights = self.0;
+		for weight in &mut weights {
+			if rng.random::<f32>() < MUTATION_RATE {
+				*weight += rng.random_range(-MUTATION_SIZE..MUTATION_SIZE);
+			}
+		}
+		Brain(weights)
+	}
+
+	fn wants_to_flap(&self, inputs: [f32; INPUTS]) -> bool {
+		let (hidden, output) = self.0.split_at((INPUTS + 1) * HIDDEN);
+		let activation = hidden
+			.chunks(INPUTS + 1)
+			.zip(output)
+			.map(|(neuron, weight)| {
+				let sum = neuron[INPUTS]
+					+ neuron
+						.iter()
+						.zip(inputs)
+						.map(|(weight, input)| weight * input)
+						.sum::<f32>();
+				sum.tanh() * weight
+			})
+			.sum::<f32>();
+		activation + output[HIDDEN] > 0.0
+	}
+}
+
+#[derive(Resource)]
+struct Population {
+	generation: u32,
+	brains: Vec<Brain>,
+	/// Ticks each bird of the current generation lasted.
+	fitness: Vec<u32>,
+	/// Most pipes any generation got through.
+	best_pipes: u32,
+}
+
+impl Population {
+	fn new(rng: &mut impl Rng) -> Self {
+		Population {
+			generation: 1,
+			brains: (0..POPULATION).map(|_| Brain::random(rng)).collect(),
+			fitness: vec![0; POPULATION],
+			best_pipes: 0,
+		}
+	}
+
+	fn breed(&mut self, rng: &mut impl Rng) {
+		let mut ranked = (0..POPULATION).collect::<Vec<_>>();
+		ranked.sort_by_key(|&i| Reverse(self.fitness[i]));
+		let parents = ((POPULATION as f32 * PARENT_SHARE) as usize).max(1);
+		let mut brains = ranked[..ELITES]
+			.iter()
+			.map(|&i| self.brains[i].clone())
+			.collect::<Vec<_>>();
+		while brains.len() < POPULATION {
+			let parent = ranked[rng.random_range(0..parents)];
+			brains.push(self.brains[parent].mutated(rng));
+		}
+		self.brains = brains;
+		self.fitness.fill(0);
+		self.generation += 1;
+	}
+}
+
+/// A bird of the flock, flown by the brain at this index of the population.
+#[derive(Component)]
+struct Specimen(usize);
+
+#[derive(Component)]
+struct EvolutionText;
+
+fn spawn_flock(
+	commands: &mut Commands,
+	textures: &Textures,
+	population: &Population,
+	direction: ScrollDirection,
+) {
+	for i in 0..POPULATION {
+		let elite = population.generation > 1 && i < ELITES;
+		let mut sprite = textures.bird_sprite(PLAYER_SIZE);
+		sprite.flip_x = direction.flip_x();
+		sprite.color = if elite {
+			ELITE_COLOR
+		} else {
+			Color::WHITE.with_alpha(BIRD_ALPHA)
+		};
+		let mut transform = Transform::from_translation(direction.player_start());
+		// Elites fly in front of the rest.
+		transform.translation.z = if elite { 2.0 } else { 1.0 };
+		commands.spawn((
+			Specimen(i),
+			CleanupOnExit(GameStates::Evolution),
+			sprite,
+			SpriteAnimation::bird(),
+			transform,
+			Velocity::default(),
+		));
+	}
+}
+
+fn start_evolution(
+	mut commands: Commands,
+	textures: Res<Textures>,
+	settings: Res<SeedSettings>,
+	difficulty: Res<Difficulty>,
+	direction: Res<ScrollDirection>,
+	theme: Res<UiTheme>,
+) {
+	let mut rng = GameRng::new(settings.fixed_seed.unwrap_or_else(|| rand::rng().random()));
+	let population = Population::new(&mut *rng);
+	spawn_flock(&mut commands, &textures, &population, *direction);
+	commands.insert_resource(population);
+	commands.insert_resource(rng);
+	commands.insert_resource(Sandbox::new(&difficulty));
+	commands.spawn((
+		EvolutionText,
+		CleanupOnExit(GameStates::Evolution),
+		Text::default(),
+		theme.text(28.0),
+		Node {
+			position_type: PositionType::Absolute,
+			bottom: Val::Px(5.0),
+			left: Val::Px(5.0),
+			..default()
+		},
+	));
+}
+
+fn fly_flock(
+	population: Res<Population>,
+	settings: Res<DifficultySettings>,
+	play_area: Res<PlayArea>,
+	direction: Res<ScrollDirection>,
+	mut flock: Query<(&Specimen, &mut Transform, &mut Velocity), Without<Pipe>>,
+	pipes: Query<(&Pipe, &Transform, &Sprite)>,
+	time: Res<Time>,
+) {
+	let half_height = play_area.height() / 2.0;
+	let bird_x = direction.player_start().x;
+	let gap = next_gap(bird_x, pipes);
+	for (specimen, mut transform, mut velocity) in &mut flock {
+		let y = transform.translation.y;
+		let inputs = [
+			y / half_height,
+			velocity.y / settings.jump_strength,
+			gap.map_or(1.0, |gap| {
+				-direction.distance_past(gap.x, bird_x) / play_area.width()
+			}),
+			gap.map_or(-y, |gap| gap.y - y) / half_height,
+		];
+		let flap = population.brains[specimen.0].wants_to_flap(inputs);
+		sandbox::fly(
+			&mut transform,
+			&mut velocity,
+			flap,
+			settings.jump_strength,
+			settings.gravity,
+			time.delta_secs(),
+		);
+	}
+}
+
+fn crash_flock(
+	mut commands: Commands,
+	mut population: ResMut<Population>,
+	sandbox: Res<Sandbox>,
+	play_area: Res<PlayArea>,
+	flock: Query<(Entity, &Specimen, &Transform, &Sprite)>,
+	pipes: Query<(&Transform, &Sprite), With<Pipe>>,
+) {
+	for (entity, specimen, transform, sprite) in &flock {
+		let bird = sprite_collider(transform, sprite);
+		if sandbox::hits_anything(bird, &play_area, pipes.iter()) {
+			population.fitness[specimen.0] = sandbox.ticks;
+			commands.entity(entity).despawn();
+		}
+	}
+}
+
+/// Everything that carries over from one generation to the next.
+#[derive(SystemParam)]
+struct Lineage<'w> {
+	population: ResMut<'w, Population>,
+	sandbox: ResMut<'w, Sandbox>,
+	rng: ResMut<'w, GameRng>,
+	difficulty: Res<'w, Difficulty>,
+}
+
+/// Once the whole flock has crashed, the next generation starts over from the
+/// beginning.
+fn next_generation(
+	mut commands: Commands,
+	mut lineage: Lineage,
+	mut pipes: PipeSpawner,
+	flock: Query<(), With<Specimen>>,
+	pipe_entities: Query<Entity, With<Pipe>>,
+	textures: Res<Textures>,
+	direction: Res<ScrollDirection>,
+) {
+	if !flock.is_empty() {
+		return;
+	}
+	let Lineage {
+		population,
+		sandbox,
+		rng,
+		difficulty,
+	} = &mut lineage;
+	population.best_pipes = population.best_pipes.max(sandbox.pipes_passed);
+	population.breed(&mut ***rng);
+	for entity in &pipe_entities {
+		pipes.recycle(entity);
+	}
+	**sandbox = Sandbox::new(difficulty);
+	spawn_flock(&mut commands, &textures, population, *direction);
+}
+
+fn update_evolution_text(
+	population: Res<Population>,
+	sandbox: Res<Sandbox>,
+	flock: Query<(), With<Specimen>>,
+	locale: Res<Locale>,
+	mut text: Single<&mut Text, With<EvolutionText>>,
+) {
+	text.0 = locale.format(
+		"evolution-status",
+		&[
+			("generation", &population.generation),
+			("flying", &flock.iter().count()),
+			("population", &POPULATION),
+			("pipes", &sandbox.pipes_passed),
+			("best", &population.best_pipes.max(sandbox.pipes_passed)),
+		],
+	);
+}
+
+fn open_evolution_on_v(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyV) {
+		next_state.set(GameStates::Evolution);
+	}
+}
+
+fn leave_on_escape(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::Escape) {
+		next_state.set(GameStates::Menu);
+	}
+}
+
+pub struct EvolutionPlugin;
+
+impl Plugin for EvolutionPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(OnEnter(GameStates::Evolution), start_evolution)
+			.add_systems(OnExit(GameStates::Evolution), sandbox::clear_sandbox)
+			.add_systems(
+				FixedUpdate,
+				(fly_flock, crash_flock, next_generation)
+					.chain()
+					.after(SandboxSet)
+					.run_if(in_state(GameStates::Evolution).and(resource_exists::<Sandbox>)),
+			)
+			.add_systems(
+				Update,
+				(
+					open_evolution_on_v.run_if(in_state(MenuScreen::Main)),
+					(update_evolution_text, leave_on_escape)
+						.run_if(in_state(GameStates::Evolution)),
+				),
+			);
+	}
+}
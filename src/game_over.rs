@@ -0,0 +1,267 @@
+//! The panel shown once a run is over, with its stats and what to do next.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit, crash::Crash, input::PlayerInput, locale::Locale, medals::Medal,
+	save::SaveData, stages, theme::UiTheme, time_attack, versus::Versus, GameMode, GameOverAt,
+	GameScore, GameStates, InRun, RunResult, RESTART_DELAY,
+};
+
+/// The best score to compare the run that just ended against.
+#[derive(Resource)]
+struct RunBest {
+	/// Message key of what the best is called.
+	label: &'static str,
+	score: i64,
+	/// Whether the run that just ended set it.
+	is_new: bool,
+}
+
+#[derive(Component)]
+struct GameOverUi;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum GameOverButton {
+	Restart,
+	Menu,
+}
+
+impl GameOverButton {
+	const ALL: [GameOverButton; 2] = [GameOverButton::Restart, GameOverButton::Menu];
+
+	/// The next race starts from the lobby, in the menu.
+	fn label(self, mode: GameMode) -> &'static str {
+		match self {
+			GameOverButton::Restart if mode == GameMode::Race => "button-lobby",
+			GameOverButton::Restart => "button-restart",
+			GameOverButton::Menu => "button-menu",
+		}
+	}
+
+	fn target(self, mode: GameMode) -> GameStates {
+		match self {
+			GameOverButton::Restart if mode != GameMode::Race => GameStates::InGame,
+			GameOverButton::Restart | GameOverButton::Menu => GameStates::Menu,
+		}
+	}
+}
+
+/// Index of the highlighted button in [`GameOverButton::ALL`].
+#[derive(Resource, Default, Deref, DerefMut)]
+struct SelectedButton(usize);
+
+/// Hardcore runs keep a best of their own, and stages show their goal instead.
+/// Other modes show the best endless score, but only endless runs can beat it,
+/// daily challenges keep their own records. Replays never beat the run they
+/// recorded.
+fn check_new_best(
+	mut commands: Commands,
+	score: Res<GameScore>,
+	mode: Res<GameMode>,
+	mut save: ResMut<SaveData>,
+) {
+	let (label, best) = match *mode {
+		GameMode::Hardcore => ("best-hardcore", save.hardcore_best),
+		GameMode::Stage(_) => (
+			"best-goal",
+			stages::stage(*mode).map_or(0, |stage| i64::from(stage.goal())),
+		),
+		_ => ("best-endless", save.high_scores.best()),
+	};
+	let is_new = matches!(*mode, GameMode::Endless | GameMode::Hardcore) && **score > best;
+	if is_new && *mode == GameMode::Hardcore {
+		save.hardcore_best = **score;
+	}
+	commands.insert_resource(RunBest {
+		label,
+		score: if is_new { **score } else { best },
+		is_new,
+	});
+}
+
+fn format_duration(seconds: f32) -> String {
+	let seconds = seconds as u32;
+	format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn spawn_game_over_panel(
+	mut commands: Commands,
+	result: RunResult,
+	save: Res<SaveData>,
+	run_best: Res<RunBest>,
+	mode: Res<GameMode>,
+	versus: Option<Res<Versus>>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	let best = locale.format(
+		if run_best.is_new {
+			"game-over-new-best"
+		} else {
+			"game-over-best"
+		},
+		&[
+			("label", &locale.text(run_best.label)),
+			("score", &run_best.score),
+		],
+	);
+	let time = locale.format(
+		"game-over-time",
+		&[("time", &format_duration(result.seconds()))],
+	);
+	// Versus runs are about who lasted longest instead.
+	let (title, stats) = match versus.filter(|_| *mode == GameMode::Versus) {
+		Some(versus) => (
+			versus.outcome(&locale),
+			versus.scores(&locale).chain([time]).collect::<Vec<_>>(),
+		),
+		None => (
+			locale.text("game-over"),
+			vec![
+				locale.format("game-over-score", &[("score", &**result.score)]),
+				best,
+				locale.format("game-over-pipes", &[("pipes", &**result.pipes_passed)]),
+				time,
+				locale.format("game-over-coins", &[("coins", &save.coins)]),
+			],
+		),
+	};
+	commands
+		.spawn((
+			GameOverUi,
+			CleanupOnExit(GameStates::GameOver),
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				..default()
+			},
+		))
+		.with_children(|parent| {
+			parent.spawn(theme.panel()).with_children(|panel| {
+				panel.spawn((Text::new(title), theme.text(72.0)));
+				if let Some(medal) = Medal::for_score(**result.score) {
+					panel
+						.spawn(Node {
+							align_items: AlignItems::Center,
+							column_gap: Val::Px(12.0),
+							..default()
+						})
+						.with_children(|row| {
+							row.spawn(medal.badge(48.0));
+							row.spawn((
+								Text::new(locale.format(
+									"game-over-medal",
+									&[(
+										"medal",
+										&locale.text(&format!(
+											"medal-{}",
+											medal.name().to_lowercase()
+										)),
+									)],
+								)),
+								theme.text(32.0),
+							));
+						});
+				}
+				for line in stats {
+					panel.spawn((Text::new(line), theme.text(32.0)));
+				}
+				panel
+					.spawn(Node {
+						column_gap: Val::Px(24.0),
+						margin: UiRect::top(Val::Px(16.0)),
+						..default()
+					})
+					.with_children(|buttons| {
+						for button in GameOverButton::ALL {
+							buttons
+								.spawn((button, Button, theme.button(false)))
+								.with_child((
+									Text::new(locale.text(button.label(*mode))),
+									theme.text(32.0),
+								));
+						}
+					});
+			});
+		});
+}
+
+fn reset_selected_button(mut selected: ResMut<SelectedButton>) {
+	**selected = 0;
+}
+
+fn navigate_game_over_panel(
+	input: PlayerInput,
+	buttons: Query<(&GameOverButton, &Interaction), Changed<Interaction>>,
+	mut selected: ResMut<SelectedButton>,
+	game_over_at: Res<GameOverAt>,
+	time: Res<Time>,
+	mode: Res<GameMode>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	let count = GameOverButton::ALL.len() as i32;
+	let step = input.menu_step();
+	if step != 0 {
+		**selected = (**selected as i32 + step).rem_euclid(count) as usize;
+	}
+	let mut confirmed = input.confirm_pressed();
+	for (button, interaction) in &buttons {
+		if *interaction == Interaction::None {
+			continue;
+		}
+		**selected = GameOverButton::ALL
+			.iter()
+			.position(|other| other == button)
+			.unwrap_or_default();
+		confirmed |= *interaction == Interaction::Pressed;
+	}
+	// Same as restarting with a tap, a flap meant for the last moments of the
+	// run shouldn't pick a button.
+	if confirmed && time.elapsed_secs() - **game_over_at > RESTART_DELAY {
+		next_state.set(GameOverButton::ALL[**selected].target(*mode));
+	}
+}
+
+fn highlight_selected_button(
+	selected: Res<SelectedButton>,
+	theme: Res<UiTheme>,
+	buttons: Query<(&GameOverButton, &mut BackgroundColor)>,
+) {
+	for (button, mut color) in buttons {
+		color.0 = theme.button_color(GameOverButton::ALL[**selected] == *button);
+	}
+}
+
+pub struct GameOverPlugin;
+
+impl Plugin for GameOverPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<SelectedButton>()
+			.add_systems(
+				OnExit(InRun),
+				check_new_best.run_if(in_state(GameStates::GameOver)),
+			)
+			.add_systems(
+				OnEnter(Crash::Over),
+				spawn_game_over_panel
+					.run_if(not(stages::stage_cleared).and(not(time_attack::target_reached))),
+			)
+			.add_systems(OnExit(GameStates::GameOver), reset_selected_button)
+			.add_systems(
+				Update,
+				(
+					navigate_game_over_panel,
+					highlight_selected_button.run_if(resource_changed::<SelectedButton>),
+				)
+					.chain()
+					.run_if(
+						in_state(Crash::Over)
+							.and(not(stages::stage_cleared))
+							.and(not(time_attack::target_reached)),
+					),
+			);
+	}
+}
@@ -0,0 +1,107 @@
+//! The moment before a live run where the bird hovers in place, waiting for
+//! the first flap.
+//!
+//! The simulation doesn't run at all until then, so runs still start with the
+//! first flap on tick 0 and replays don't need to wait.
+
+use bevy::prelude::*;
+
+use crate::{
+	input::{key_name, InputAction},
+	locale::Locale,
+	mirror::ScrollDirection,
+	settings::{motion_allowed, Settings},
+	theme::UiTheme,
+	GameMode, GameStates, PendingFlap, Player, PLAYER_START,
+};
+
+const HOVER_HEIGHT: f32 = 8.0;
+const HOVER_SPEED: f32 = 3.0;
+
+#[derive(SubStates, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[source(GameStates = GameStates::InGame)]
+pub(crate) enum RunPhase {
+	#[default]
+	GetReady,
+	Flying,
+}
+
+#[derive(Component)]
+struct GetReadyUi;
+
+fn spawn_prompt(
+	mut commands: Commands,
+	settings: Res<Settings>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	commands.spawn((
+		GetReadyUi,
+		Node {
+			width: Val::Percent(100.0),
+			height: Val::Percent(100.0),
+			flex_direction: FlexDirection::Column,
+			justify_content: JustifyContent::Center,
+			align_items: AlignItems::Center,
+			row_gap: Val::Px(16.0),
+			..default()
+		},
+		children![
+			(Text::new(locale.text("get-ready")), theme.text(96.0)),
+			(
+				Text::new(locale.format(
+					"get-ready-flap",
+					&[("key", &key_name(settings.controls.key(InputAction::Flap)))],
+				)),
+				theme.text(32.0),
+			),
+		],
+	));
+}
+
+/// Puts the bird back where the simulation expects it to start.
+fn start_flying(
+	mut commands: Commands,
+	ui: Single<Entity, With<GetReadyUi>>,
+	players: Query<&mut Transform, With<Player>>,
+	direction: Res<ScrollDirection>,
+) {
+	commands.entity(*ui).despawn();
+	for mut transform in players {
+		transform.translation = direction.player_start().with_z(transform.translation.z);
+	}
+}
+
+fn hover_player(players: Query<&mut Transform, With<Player>>, time: Res<Time>) {
+	let offset = (time.elapsed_secs() * HOVER_SPEED).sin() * HOVER_HEIGHT;
+	for mut transform in players {
+		transform.translation.y = PLAYER_START.y + offset;
+	}
+}
+
+/// The first flap is left pending, so it is applied on the first tick.
+fn fly_on_first_flap(pending_flap: Res<PendingFlap>, mut next_phase: ResMut<NextState<RunPhase>>) {
+	if pending_flap.is_pending() {
+		next_phase.set(RunPhase::Flying);
+	}
+}
+
+pub struct GetReadyPlugin;
+
+impl Plugin for GetReadyPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(OnEnter(RunPhase::GetReady), spawn_prompt)
+			.add_systems(OnExit(RunPhase::GetReady), start_flying)
+			.add_systems(
+				Update,
+				(
+					hover_player.run_if(motion_allowed),
+					// Races take off together after a countdown instead.
+					fly_on_first_flap
+						.after(crate::handle_movement)
+						.run_if(not(resource_equals(GameMode::Race))),
+				)
+					.run_if(in_state(RunPhase::GetReady)),
+			);
+	}
+}
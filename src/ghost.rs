@@ -0,0 +1,148 @@
+//! A translucent bird retracing the best run so far, to race against.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	mirror::ScrollDirection,
+	player_tilt,
+	profiles::profile_file,
+	save::{read_ron, write_ron},
+	settings::Settings,
+	sprites::{SpriteAnimation, Textures},
+	GameScore, GameStates, GameplaySet, Player, RunTick, PLAYER_SIZE, PLAYER_START,
+};
+
+pub(crate) const GHOST_FILE_NAME: &str = "ghost.ron";
+const GHOST_ALPHA: f32 = 0.35;
+
+/// Height of the bird on every tick of a run. The bird never moves
+/// horizontally, so that is all it takes to retrace its flight.
+#[derive(Serialize, Deserialize, Default, Clone)]
+struct GhostTrack {
+	score: i64,
+	heights: Vec<f32>,
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct BestGhost(Option<GhostTrack>);
+
+impl BestGhost {
+	/// The best run of the profile being played.
+	pub(crate) fn load() -> Self {
+		BestGhost(read_ron(&profile_file(GHOST_FILE_NAME)))
+	}
+}
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct CurrentTrack(Vec<f32>);
+
+#[derive(Component)]
+struct Ghost;
+
+fn spawn_ghost(
+	mut commands: Commands,
+	textures: Res<Textures>,
+	best: Res<BestGhost>,
+	mut current: ResMut<CurrentTrack>,
+) {
+	current.clear();
+	if best.0.is_none() {
+		return;
+	}
+	let mut sprite = textures.bird_sprite(PLAYER_SIZE);
+	sprite.color = Color::WHITE.with_alpha(GHOST_ALPHA);
+	commands.spawn((
+		Ghost,
+		sprite,
+		SpriteAnimation::bird(),
+		Transform::from_translation(PLAYER_START.with_z(-1.0)),
+	));
+}
+
+fn record_track(mut current: ResMut<CurrentTrack>, player: Single<&Transform, With<Player>>) {
+	current.push(player.translation.y);
+}
+
+fn move_ghost(
+	mut commands: Commands,
+	best: Res<BestGhost>,
+	run_tick: Res<RunTick>,
+	ghost: Single<(Entity, &mut Transform, &mut Sprite), With<Ghost>>,
+	direction: Res<ScrollDirection>,
+	settings: Res<Settings>,
+	time: Res<Time>,
+) {
+	let (entity, mut transform, mut sprite) = ghost.into_inner();
+	let Some(track) = &best.0 else {
+		return;
+	};
+	let tick = **run_tick as usize;
+	let Some(&height) = track.heights.get(tick) else {
+		// The best run ended here.
+		commands.entity(entity).despawn();
+		return;
+	};
+	let previous = tick
+		.checked_sub(1)
+		.and_then(|tick| track.heights.get(tick))
+		.copied()
+		.unwrap_or(height);
+	// The best run may have been flown the other way.
+	transform.translation.x = direction.player_start().x;
+	transform.translation.y = height;
+	sprite.flip_x = direction.flip_x();
+	if settings.reduced_motion {
+		return;
+	}
+	transform.rotation = Quat::from_rotation_z(player_tilt(
+		(height - previous) / time.delta_secs(),
+		*direction,
+	));
+}
+
+fn finish_track(
+	mut commands: Commands,
+	mut best: ResMut<BestGhost>,
+	mut current: ResMut<CurrentTrack>,
+	score: Res<GameScore>,
+	ghost: Option<Single<Entity, With<Ghost>>>,
+) {
+	if let Some(ghost) = ghost {
+		commands.entity(*ghost).despawn();
+	}
+	if best.0.as_ref().is_some_and(|best| best.score >= **score) {
+		return;
+	}
+	let track = GhostTrack {
+		score: **score,
+		heights: std::mem::take(&mut **current),
+	};
+	write_ron(&profile_file(GHOST_FILE_NAME), &track);
+	best.0 = Some(track);
+}
+
+pub struct GhostPlugin;
+
+impl Plugin for GhostPlugin {
+	fn build(&self, app: &mut App) {
+		app.insert_resource(BestGhost::load())
+			.init_resource::<CurrentTrack>()
+			.add_systems(
+				OnEnter(GameStates::InGame),
+				spawn_ghost.run_if(crate::counts_for_progress),
+			)
+			.add_systems(
+				OnExit(GameStates::InGame),
+				finish_track.run_if(crate::counts_for_progress),
+			)
+			.add_systems(
+				FixedUpdate,
+				(
+					move_ghost.in_set(GameplaySet::Physics),
+					record_track.in_set(GameplaySet::Progress),
+				)
+					.run_if(in_state(GameStates::InGame).and(crate::counts_for_progress)),
+			);
+	}
+}
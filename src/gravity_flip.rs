@@ -0,0 +1,130 @@
+//! The gravity flip mutator. Every so often gravity turns around, the ceiling
+//! becomes the floor and the bird has to flap downwards until it turns back.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit, difficulty::DifficultySettings, locale::Locale,
+	mutators::gravity_flip_enabled, play_area::PlayArea, theme::UiTheme, Acceleration, GameMode,
+	GameplayEvent, GameplaySet, InRun, Player, PlayerDiedEvent, Velocity, PLAYER_SIZE,
+};
+
+/// Seconds between two flips.
+const FLIP_INTERVAL: f32 = 10.0;
+/// How long before a flip the warning is shown.
+const FLIP_WARNING: f32 = 2.0;
+const WARNING_BLINKS_PER_SECOND: f32 = 4.0;
+
+/// Counts down to the next flip, ticked with the simulation so replays flip on
+/// the same ticks.
+#[derive(Resource, Deref, DerefMut)]
+struct FlipTimer(Timer);
+
+impl Default for FlipTimer {
+	fn default() -> Self {
+		FlipTimer(Timer::from_seconds(FLIP_INTERVAL, TimerMode::Repeating))
+	}
+}
+
+#[derive(Component)]
+struct FlipWarning;
+
+fn start_flip_run(mut commands: Commands, theme: Res<UiTheme>) {
+	commands.insert_resource(FlipTimer::default());
+	commands.spawn((
+		FlipWarning,
+		CleanupOnExit(InRun),
+		Text::default(),
+		theme.font(40.0),
+		TextColor(Color::srgb(1.0, 0.3, 0.2)),
+		Node {
+			position_type: PositionType::Absolute,
+			top: Val::Px(120.0),
+			width: Val::Percent(100.0),
+			justify_content: JustifyContent::Center,
+			..default()
+		},
+		TextLayout::new_with_justify(JustifyText::Center),
+		Visibility::Hidden,
+	));
+}
+
+/// Turns gravity around, and with it the direction the bird flaps in.
+fn flip_gravity(
+	time: Res<Time>,
+	mut timer: ResMut<FlipTimer>,
+	player: Single<(&mut Acceleration, &mut Sprite), With<Player>>,
+) {
+	if !timer.tick(time.delta()).just_finished() {
+		return;
+	}
+	let (mut acceleration, mut sprite) = player.into_inner();
+	acceleration.y = -acceleration.y;
+	sprite.flip_y = acceleration.y > 0.0;
+}
+
+/// While gravity is flipped the top of the screen works like the ground.
+fn check_ceiling(
+	play_area: Res<PlayArea>,
+	player: Single<(Entity, &Transform, &Acceleration, &mut Velocity), With<Player>>,
+	mode: Res<GameMode>,
+	settings: Res<DifficultySettings>,
+	mut events: EventWriter<GameplayEvent>,
+	mut deaths: EventWriter<PlayerDiedEvent>,
+) {
+	let (entity, transform, acceleration, mut velocity) = player.into_inner();
+	if acceleration.y <= 0.0 || transform.translation.y + PLAYER_SIZE.y / 2.0 < play_area.max.y {
+		return;
+	}
+	if *mode == GameMode::Zen {
+		if velocity.y > 0.0 {
+			events.write(GameplayEvent::Missed);
+		}
+		velocity.y = -settings.jump_strength;
+	} else {
+		deaths.write(PlayerDiedEvent(entity));
+	}
+}
+
+fn show_flip_warning(
+	timer: Res<FlipTimer>,
+	time: Res<Time<Real>>,
+	locale: Res<Locale>,
+	warning: Single<(&mut Text, &mut Visibility), With<FlipWarning>>,
+	player: Single<&Acceleration, With<Player>>,
+) {
+	let (mut text, mut visibility) = warning.into_inner();
+	let remaining = timer.remaining_secs();
+	let blink_on =
+		((time.elapsed_secs() * WARNING_BLINKS_PER_SECOND * 2.0) as u32).is_multiple_of(2);
+	*visibility = if remaining <= FLIP_WARNING && blink_on {
+		Visibility::Inherited
+	} else {
+		Visibility::Hidden
+	};
+	let arrow = if player.y < 0.0 { "^" } else { "v" };
+	text.0 = locale.format(
+		"gravity-flip-warning",
+		&[("arrow", &arrow), ("seconds", &remaining.ceil())],
+	);
+}
+
+pub struct GravityFlipPlugin;
+
+impl Plugin for GravityFlipPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(OnEnter(InRun), start_flip_run.run_if(gravity_flip_enabled))
+			.add_systems(
+				FixedUpdate,
+				(
+					flip_gravity.in_set(GameplaySet::Progress),
+					check_ceiling.in_set(GameplaySet::Collision),
+				)
+					.run_if(gravity_flip_enabled),
+			)
+			.add_systems(
+				Update,
+				show_flip_warning.run_if(gravity_flip_enabled.and(in_state(InRun))),
+			);
+	}
+}
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+
+use crate::{
+	collision::{Collider, ColliderShape, CollisionLayer, OnCollision},
+	day_night::Tinted,
+	difficulty::DifficultySettings,
+	interpolation::Interpolated,
+	mirror::ScrollDirection,
+	play_area::{PlayArea, VisibleArea},
+	sprites::Textures,
+	GameMode, GameplayEvent, GameplaySet, Player, PlayerDiedEvent, Scrolling, Velocity,
+};
+
+const GROUND_HEIGHT: f32 = 64.0;
+
+/// Y coordinate of the walkable top of the ground strip.
+pub fn ground_top(play_area: &PlayArea) -> f32 {
+	play_area.min.y + GROUND_HEIGHT
+}
+
+/// One of the two window-wide ground segments, scrolled and wrapped around
+/// so the strip looks endless.
+#[derive(Component)]
+#[require(Collider = Collider::new(ColliderShape::Sprite, CollisionLayer::Ground))]
+struct Ground;
+
+fn spawn_ground(mut commands: Commands, textures: Res<Textures>) {
+	for _ in 0..2 {
+		commands.spawn((
+			Ground,
+			Tinted(1.0),
+			textures.ground_sprite(Vec2::new(0.0, GROUND_HEIGHT)),
+			Transform::from_xyz(0.0, 0.0, 1.0),
+			Scrolling,
+		));
+	}
+}
+
+/// Lays the segments out side by side along the bottom of the play area, as
+/// wide as the visible area and reaching down to its bottom.
+fn fit_ground(
+	play_area: Res<PlayArea>,
+	visible_area: Res<VisibleArea>,
+	query: Query<(&mut Transform, &mut Sprite), With<Ground>>,
+) {
+	let width = visible_area.width();
+	let top = ground_top(&play_area);
+	let height = top - visible_area.min.y.min(play_area.min.y);
+	for (i, (mut transform, mut sprite)) in query.into_iter().enumerate() {
+		sprite.custom_size = Some(Vec2::new(width, height));
+		transform.translation.x = visible_area.center().x + i as f32 * width;
+		transform.translation.y = top - height / 2.0;
+	}
+}
+
+fn wrap_ground(
+	visible_area: Res<VisibleArea>,
+	direction: Res<ScrollDirection>,
+	query: Query<(&mut Transform, &mut Interpolated), With<Ground>>,
+) {
+	let width = visible_area.width();
+	for (mut transform, mut interpolated) in query {
+		if direction.has_left(&visible_area, transform.translation.x, width / 2.0) {
+			transform.translation.x -= direction.sign() * 2.0 * width;
+			interpolated.teleported();
+		}
+	}
+}
+
+/// In zen mode the bird bounces off the ground instead of crashing.
+fn hit_ground(
+	trigger: Trigger<OnCollision>,
+	mut players: Query<&mut Velocity, With<Player>>,
+	mode: Res<GameMode>,
+	difficulty: Res<DifficultySettings>,
+	mut events: EventWriter<GameplayEvent>,
+	mut deaths: EventWriter<PlayerDiedEvent>,
+) {
+	if trigger.layer != CollisionLayer::Ground {
+		return;
+	}
+	let Ok(mut velocity) = players.get_mut(trigger.target()) else {
+		return;
+	};
+	if *mode == GameMode::Zen {
+		// Only counts once, the bounce takes the bird clear of the ground.
+		if velocity.y < 0.0 {
+			events.write(GameplayEvent::Missed);
+		}
+		velocity.y = difficulty.jump_strength;
+	} else {
+		deaths.write(PlayerDiedEvent(trigger.target()));
+	}
+}
+
+pub struct GroundPlugin;
+
+impl Plugin for GroundPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(Startup, spawn_ground)
+			.add_observer(hit_ground)
+			.add_systems(Update, fit_ground.run_if(resource_changed::<VisibleArea>))
+			.add_systems(FixedUpdate, wrap_ground.in_set(GameplaySet::Spawn));
+	}
+}
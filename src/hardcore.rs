@@ -0,0 +1,47 @@
+//! Hardcore mode: tighter and faster pipes, no room above the screen, and
+//! straight back into the next run after crashing.
+
+use bevy::prelude::*;
+
+use crate::{
+	input::PlayerInput, play_area::PlayArea, GameMode, GameStates, GameplaySet, Player,
+	PlayerDiedEvent, PLAYER_SIZE,
+};
+
+/// The bird crashes as soon as it leaves the top of the screen, rather than
+/// being held up there.
+fn check_ceiling(
+	play_area: Res<PlayArea>,
+	player: Single<(Entity, &Transform), With<Player>>,
+	mut deaths: EventWriter<PlayerDiedEvent>,
+) {
+	let (entity, transform) = *player;
+	if transform.translation.y + PLAYER_SIZE.y / 2.0 > play_area.max.y {
+		deaths.write(PlayerDiedEvent(entity));
+	}
+}
+
+/// Skips waiting for the game over panel, one press starts the next run.
+fn restart_instantly(input: PlayerInput, mut next_state: ResMut<NextState<GameStates>>) {
+	if input.flap_pressed() || input.restart_pressed() {
+		next_state.set(GameStates::InGame);
+	}
+}
+
+pub struct HardcorePlugin;
+
+impl Plugin for HardcorePlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			FixedUpdate,
+			check_ceiling
+				.in_set(GameplaySet::Collision)
+				.run_if(resource_equals(GameMode::Hardcore)),
+		)
+		.add_systems(
+			Update,
+			restart_instantly
+				.run_if(in_state(GameStates::GameOver).and(resource_equals(GameMode::Hardcore))),
+		);
+	}
+}
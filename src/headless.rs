@@ -0,0 +1,98 @@
+//! `--headless`, which plays runs without a window, rendering or sound, for CI
+//! and for training bots on a server. Every frame advances the game by exactly
+//! one simulation tick and frames follow each other as fast as they can, so
+//! runs play out far quicker than in real time but exactly the same.
+//!
+//! From the command line, each run's result is printed once it ends, and the
+//! game quits after the number of runs asked for with `--runs`. The save files
+//! are neither read nor written, so every run starts from the default settings
+//! and an empty save.
+
+use std::time::Duration;
+
+use bevy::{
+	app::ScheduleRunnerPlugin, asset::AssetMetaCheck, input::InputPlugin, prelude::*,
+	state::app::StatesPlugin, time::TimeUpdateStrategy, window::ExitCondition,
+};
+
+use crate::{cli::LaunchOptions, get_ready::RunPhase, save, GameStates, RunResult};
+
+/// What the game needs in place of `DefaultPlugins` to run without a window.
+/// Assets with nothing to load them, like sprites, sounds and fonts, fail to load and
+/// are left out.
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+	fn build(&self, app: &mut App) {
+		save::detach_from_disk();
+		app.add_plugins((
+			MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::ZERO)),
+			AssetPlugin {
+				meta_check: AssetMetaCheck::Never,
+				..default()
+			},
+			StatesPlugin,
+			TransformPlugin,
+			InputPlugin,
+			WindowPlugin {
+				primary_window: None,
+				exit_condition: ExitCondition::DontExit,
+				..default()
+			},
+		))
+		.init_asset::<Image>()
+		.init_asset::<TextureAtlasLayout>()
+		.init_asset::<AudioSource>()
+		.init_asset::<Font>()
+		.init_resource::<GlobalVolume>()
+		.insert_resource(TimeUpdateStrategy::ManualDuration(
+			Time::<Fixed>::default().timestep(),
+		))
+		.add_systems(OnEnter(RunPhase::GetReady), skip_get_ready);
+	}
+}
+
+/// Plays the runs asked for on the command line one after the other.
+pub struct RunReportPlugin;
+
+impl Plugin for RunReportPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<RunsPlayed>()
+			.add_systems(OnEnter(GameStates::GameOver), report_run);
+	}
+}
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct RunsPlayed(u32);
+
+pub fn is_headless(options: Res<LaunchOptions>) -> bool {
+	options.headless
+}
+
+/// There's nobody to wait for.
+fn skip_get_ready(mut next_phase: ResMut<NextState<RunPhase>>) {
+	next_phase.set(RunPhase::Flying);
+}
+
+fn report_run(
+	mut runs_played: ResMut<RunsPlayed>,
+	options: Res<LaunchOptions>,
+	result: RunResult,
+	mut next_state: ResMut<NextState<GameStates>>,
+	mut exit: EventWriter<AppExit>,
+) {
+	**runs_played += 1;
+	println!(
+		"Run {}: seed {}, score {}, {} pipes, {:.2} s",
+		**runs_played,
+		result.game_rng.seed,
+		**result.score,
+		**result.pipes_passed,
+		result.seconds()
+	);
+	if **runs_played >= options.runs.unwrap_or(1) {
+		exit.write(AppExit::Success);
+	} else {
+		next_state.set(GameStates::InGame);
+	}
+}
@@ -0,0 +1,98 @@
+//! High contrast, turned on under High contrast in the settings, for players
+//! who have trouble making out the bird and the gaps: the bird becomes a plain
+//! yellow silhouette outlined in black, the pipes and the ground dark shapes
+//! outlined in white, all over a plain black background. The sky doesn't
+//! change with the time of day either.
+//!
+//! The textures are redrawn along with the palette ones, see
+//! [`palette`](crate::palette), and take over from the palette while on.
+
+use bevy::prelude::*;
+
+use crate::{settings::Settings, Background};
+
+const BIRD_FILL: Color = Color::srgb(1.0, 0.9, 0.0);
+const BIRD_OUTLINE: Color = Color::BLACK;
+const OBSTACLE_FILL: Color = Color::srgb(0.2, 0.2, 0.2);
+const OBSTACLE_OUTLINE: Color = Color::WHITE;
+/// In texture pixels, which the bird and the pipes are drawn at about one to
+/// one.
+const OUTLINE_WIDTH: u32 = 3;
+
+fn is_opaque(image: &Image, x: i64, y: i64) -> bool {
+	let (Ok(x), Ok(y)) = (u32::try_from(x), u32::try_from(y)) else {
+		return false;
+	};
+	image
+		.get_color_at(x, y)
+		.is_ok_and(|color| color.alpha() > 0.5)
+}
+
+/// Fills the opaque part of `original` with `fill`, and the part of it within
+/// [`OUTLINE_WIDTH`] of a see-through pixel or the edge of the texture with
+/// `outline`.
+fn silhouette(original: &Image, fill: Color, outline: Color) -> Image {
+	let mut image = original.clone();
+	let reach = i64::from(OUTLINE_WIDTH);
+	for y in 0..image.height() {
+		for x in 0..image.width() {
+			let (x_i, y_i) = (i64::from(x), i64::from(y));
+			if !is_opaque(original, x_i, y_i) {
+				continue;
+			}
+			let edge = (-reach..=reach).any(|dy| {
+				(-reach..=reach).any(|dx| {
+					dx * dx + dy * dy <= reach * reach && !is_opaque(original, x_i + dx, y_i + dy)
+				})
+			});
+			let _ = image.set_color_at(x, y, if edge { outline } else { fill });
+		}
+	}
+	image
+}
+
+pub fn bird(original: &Image) -> Image {
+	silhouette(original, BIRD_FILL, BIRD_OUTLINE)
+}
+
+pub fn pipe(original: &Image) -> Image {
+	silhouette(original, OBSTACLE_FILL, OBSTACLE_OUTLINE)
+}
+
+/// The ground is tiled across the screen, so only its top gets an outline.
+pub fn ground(original: &Image) -> Image {
+	let mut image = original.clone();
+	for y in 0..image.height() {
+		for x in 0..image.width() {
+			if is_opaque(original, i64::from(x), i64::from(y)) {
+				let color = if y < OUTLINE_WIDTH {
+					OBSTACLE_OUTLINE
+				} else {
+					OBSTACLE_FILL
+				};
+				let _ = image.set_color_at(x, y, color);
+			}
+		}
+	}
+	image
+}
+
+/// Hides the background, leaving the black the window is cleared to.
+fn show_background(
+	settings: Res<Settings>,
+	mut background: Single<&mut Visibility, With<Background>>,
+) {
+	background.set_if_neq(if settings.high_contrast {
+		Visibility::Hidden
+	} else {
+		Visibility::Inherited
+	});
+}
+
+pub struct HighContrastPlugin;
+
+impl Plugin for HighContrastPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(Update, show_background.run_if(resource_changed::<Settings>));
+	}
+}
@@ -0,0 +1,324 @@
+//! The table of best endless scores, and entering initials to get on it.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	crash::Crash,
+	input::{key_name, PlayerInput},
+	locale::Locale,
+	menu::MenuScreen,
+	save::SaveData,
+	tas::TasScript,
+	theme::UiTheme,
+	GameMode, GameScore, GameStates,
+};
+
+/// How many scores the table keeps.
+const TABLE_SIZE: usize = 10;
+const INITIALS_LENGTH: usize = 3;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HighScore {
+	pub initials: String,
+	pub score: i64,
+}
+
+/// Best scores of live endless runs, highest first.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(transparent)]
+pub struct HighScores(Vec<HighScore>);
+
+impl HighScores {
+	/// The top score, or 0 while the table is empty.
+	pub fn best(&self) -> i64 {
+		self.0.first().map_or(0, |entry| entry.score)
+	}
+
+	pub fn entries(&self) -> &[HighScore] {
+		&self.0
+	}
+
+	/// Whether a score would make it onto the table.
+	fn qualifies(&self, score: i64) -> bool {
+		score > 0 && (self.0.len() < TABLE_SIZE || self.0.iter().any(|entry| score > entry.score))
+	}
+
+	/// Adds an entry below any equal scores, dropping whatever falls off the end.
+	fn insert(&mut self, entry: HighScore) {
+		let position = self
+			.0
+			.iter()
+			.position(|other| entry.score > other.score)
+			.unwrap_or(self.0.len());
+		self.0.insert(position, entry);
+		self.0.truncate(TABLE_SIZE);
+	}
+}
+
+/// Whether the run that just ended made it onto the table, and still needs
+/// initials for it.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub(crate) struct PendingHighScore(bool);
+
+/// Initials being entered, one letter per slot.
+#[derive(Resource)]
+struct Initials {
+	letters: [u8; INITIALS_LENGTH],
+	cursor: usize,
+}
+
+impl Default for Initials {
+	fn default() -> Self {
+		Initials {
+			letters: [b'A'; INITIALS_LENGTH],
+			cursor: 0,
+		}
+	}
+}
+
+#[derive(Component)]
+struct InitialsUi;
+
+/// Shows the letter at an index of [`Initials::letters`], highlighted while
+/// the cursor is on it.
+#[derive(Component)]
+struct InitialsSlot(usize);
+
+#[derive(Component)]
+struct HighScoresUi;
+
+fn check_high_score(
+	score: Res<GameScore>,
+	mode: Res<GameMode>,
+	script: Option<Res<TasScript>>,
+	save: Res<SaveData>,
+	mut pending: ResMut<PendingHighScore>,
+) {
+	**pending =
+		*mode == GameMode::Endless && script.is_none() && save.high_scores.qualifies(**score);
+}
+
+fn spawn_initials_entry(
+	mut commands: Commands,
+	score: Res<GameScore>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	commands.insert_resource(Initials::default());
+	commands
+		.spawn((
+			InitialsUi,
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				row_gap: Val::Px(16.0),
+				..default()
+			},
+			BackgroundColor(Color::BLACK.with_alpha(0.6)),
+		))
+		.with_children(|parent| {
+			parent.spawn((Text::new(locale.text("new-high-score")), theme.text(72.0)));
+			parent.spawn((
+				Text::new(locale.format("score", &[("score", &**score)])),
+				theme.text(40.0),
+			));
+			parent
+				.spawn(Node {
+					column_gap: Val::Px(12.0),
+					..default()
+				})
+				.with_children(|slots| {
+					for i in 0..INITIALS_LENGTH {
+						slots.spawn((
+							InitialsSlot(i),
+							Text::default(),
+							theme.text(56.0),
+							TextLayout::new_with_justify(JustifyText::Center),
+							Node {
+								width: Val::Px(64.0),
+								padding: UiRect::vertical(Val::Px(8.0)),
+								..default()
+							},
+							BackgroundColor(theme.button),
+							BorderRadius::all(Val::Px(theme.button_radius)),
+						));
+					}
+				});
+			parent.spawn((Text::new(locale.text("initials-help")), theme.text(24.0)));
+		});
+}
+
+fn despawn_initials_entry(mut commands: Commands, ui: Single<Entity, With<InitialsUi>>) {
+	commands.entity(*ui).despawn();
+	commands.remove_resource::<Initials>();
+}
+
+fn enter_initials(
+	input: PlayerInput,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut initials: ResMut<Initials>,
+	score: Res<GameScore>,
+	mut save: ResMut<SaveData>,
+	mut pending: ResMut<PendingHighScore>,
+	mut next_crash: ResMut<NextState<Crash>>,
+) {
+	let last = INITIALS_LENGTH - 1;
+	for key in keyboard_input.get_just_pressed() {
+		let name = key_name(*key);
+		if let [letter] = name.as_bytes() {
+			if letter.is_ascii_uppercase() {
+				let cursor = initials.cursor;
+				initials.letters[cursor] = *letter;
+				initials.cursor = (cursor + 1).min(last);
+			}
+		}
+	}
+	if keyboard_input.just_pressed(KeyCode::Backspace) {
+		initials.cursor = initials.cursor.saturating_sub(1);
+	}
+	let direction = input.menu_direction();
+	initials.cursor = (initials.cursor as i32 + direction.x).clamp(0, last as i32) as usize;
+	if direction.y != 0 {
+		let cursor = initials.cursor;
+		let letter = initials.letters[cursor] - b'A';
+		initials.letters[cursor] = b'A' + (letter as i32 - direction.y).rem_euclid(26) as u8;
+	}
+	if input.confirm_pressed() {
+		let initials = String::from_utf8_lossy(&initials.letters).into_owned();
+		save.high_scores.insert(HighScore {
+			initials: initials.clone(),
+			score: **score,
+		});
+		save.initials = Some(initials);
+		**pending = false;
+		next_crash.set(Crash::Over);
+	}
+}
+
+fn update_initials_slots(
+	initials: Res<Initials>,
+	theme: Res<UiTheme>,
+	slots: Query<(&InitialsSlot, &mut Text, &mut BackgroundColor)>,
+) {
+	for (slot, mut text, mut color) in slots {
+		text.0 = char::from(initials.letters[slot.0]).to_string();
+		color.0 = theme.button_color(slot.0 == initials.cursor);
+	}
+}
+
+fn spawn_high_scores(
+	mut commands: Commands,
+	save: Res<SaveData>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	commands
+		.spawn((
+			HighScoresUi,
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				row_gap: Val::Px(16.0),
+				..default()
+			},
+			BackgroundColor(theme.screen),
+		))
+		.with_children(|parent| {
+			parent.spawn((
+				Text::new(locale.text("high-scores-title")),
+				theme.text(72.0),
+			));
+			if save.high_scores.0.is_empty() {
+				parent.spawn((
+					Text::new(locale.text("high-scores-empty")),
+					theme.text(32.0),
+				));
+			}
+			spawn_score_table(parent, &save.high_scores.0, &theme);
+			parent.spawn((Text::new(locale.text("back")), theme.text(24.0)));
+		});
+}
+
+/// Ranks, initials and scores in three columns.
+pub(crate) fn spawn_score_table(
+	parent: &mut ChildSpawnerCommands,
+	entries: &[HighScore],
+	theme: &UiTheme,
+) {
+	parent
+		.spawn(Node {
+			display: Display::Grid,
+			grid_template_columns: vec![GridTrack::auto(); 3],
+			column_gap: Val::Px(32.0),
+			row_gap: Val::Px(4.0),
+			..default()
+		})
+		.with_children(|table| {
+			for (i, entry) in entries.iter().enumerate() {
+				let cells = [
+					format!("{}.", i + 1),
+					entry.initials.clone(),
+					entry.score.to_string(),
+				];
+				for cell in cells {
+					table.spawn((Text::new(cell), theme.text(32.0)));
+				}
+			}
+		});
+}
+
+fn despawn_high_scores(mut commands: Commands, ui: Single<Entity, With<HighScoresUi>>) {
+	commands.entity(*ui).despawn();
+}
+
+fn open_high_scores_on_h(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyH) {
+		next_screen.set(MenuScreen::HighScores);
+	}
+}
+
+fn close_high_scores(
+	input: PlayerInput,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::Escape) || input.confirm_pressed() {
+		next_screen.set(MenuScreen::Main);
+	}
+}
+
+pub struct HighScoresPlugin;
+
+impl Plugin for HighScoresPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<PendingHighScore>()
+			.add_systems(OnExit(GameStates::InGame), check_high_score)
+			.add_systems(OnEnter(Crash::EnteringInitials), spawn_initials_entry)
+			.add_systems(OnExit(Crash::EnteringInitials), despawn_initials_entry)
+			.add_systems(OnEnter(MenuScreen::HighScores), spawn_high_scores)
+			.add_systems(OnExit(MenuScreen::HighScores), despawn_high_scores)
+			.add_systems(
+				Update,
+				(
+					(
+						enter_initials,
+						update_initials_slots.run_if(resource_exists_and_changed::<Initials>),
+					)
+						.chain()
+						.run_if(in_state(Crash::EnteringInitials)),
+					open_high_scores_on_h.run_if(in_state(MenuScreen::Main)),
+					close_high_scores.run_if(in_state(MenuScreen::HighScores)),
+				),
+			);
+	}
+}
@@ -0,0 +1,209 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+
+/// Something the player can do with a key of their choosing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputAction {
+	Flap,
+	Restart,
+	Pause,
+}
+
+impl InputAction {
+	const ALL: [InputAction; 3] = [InputAction::Flap, InputAction::Restart, InputAction::Pause];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			InputAction::Flap => "Flap",
+			InputAction::Restart => "Restart",
+			InputAction::Pause => "Pause",
+		}
+	}
+
+	/// Gamepads aren't rebindable, these are the usual buttons for each action.
+	fn gamepad_button(self) -> GamepadButton {
+		match self {
+			InputAction::Flap => GamepadButton::South,
+			InputAction::Restart | InputAction::Pause => GamepadButton::Start,
+		}
+	}
+}
+
+/// The key bound to each [`InputAction`], saved along with the settings.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct KeyBindings {
+	pub flap: KeyCode,
+	pub restart: KeyCode,
+	pub pause: KeyCode,
+}
+
+impl Default for KeyBindings {
+	fn default() -> Self {
+		KeyBindings {
+			flap: KeyCode::Space,
+			restart: KeyCode::KeyR,
+			pause: KeyCode::KeyP,
+		}
+	}
+}
+
+impl KeyBindings {
+	pub fn key(&self, action: InputAction) -> KeyCode {
+		match action {
+			InputAction::Flap => self.flap,
+			InputAction::Restart => self.restart,
+			InputAction::Pause => self.pause,
+		}
+	}
+
+	/// Binds `key` to `action`. The action that had it takes over the key
+	/// `action` had, so no two actions share a key.
+	pub fn bind(&mut self, action: InputAction, key: KeyCode) {
+		let mut old = self.key(action);
+		// Only from settings saved before keys were reserved.
+		if reserved_key(old) {
+			old = KeyBindings::default().key(action);
+		}
+		for other in InputAction::ALL {
+			if other != action && self.key(other) == key {
+				self.set(other, old);
+			}
+		}
+		self.set(action, key);
+	}
+
+	fn set(&mut self, action: InputAction, key: KeyCode) {
+		match action {
+			InputAction::Flap => self.flap = key,
+			InputAction::Restart => self.restart = key,
+			InputAction::Pause => self.pause = key,
+		}
+	}
+}
+
+/// Keys the menus rely on, which can't be bound to an action. Escape always
+/// pauses, whatever Pause is bound to.
+pub fn reserved_key(key: KeyCode) -> bool {
+	matches!(
+		key,
+		KeyCode::Escape
+			| KeyCode::Enter
+			| KeyCode::NumpadEnter
+			| KeyCode::Tab
+			| KeyCode::ArrowUp
+			| KeyCode::ArrowDown
+			| KeyCode::ArrowLeft
+			| KeyCode::ArrowRight
+			| KeyCode::F1
+			| KeyCode::F2
+			| KeyCode::F3
+			| KeyCode::F4
+			| KeyCode::F5
+			| KeyCode::F6
+			| KeyCode::F7
+			| KeyCode::F8
+			| KeyCode::F9
+			| KeyCode::F10
+			| KeyCode::F11
+			| KeyCode::F12
+	)
+}
+
+/// A short name for a key, as shown to the player.
+pub fn key_name(key: KeyCode) -> String {
+	let name = format!("{key:?}");
+	name.strip_prefix("Key")
+		.or_else(|| name.strip_prefix("Digit"))
+		.unwrap_or(&name)
+		.to_string()
+}
+
+/// All the devices the player can control the game with.
+#[derive(SystemParam)]
+pub struct PlayerInput<'w, 's> {
+	keyboard: Res<'w, ButtonInput<KeyCode>>,
+	mouse: Res<'w, ButtonInput<MouseButton>>,
+	touches: Res<'w, Touches>,
+	gamepads: Query<'w, 's, &'static Gamepad>,
+	settings: Res<'w, Settings>,
+}
+
+impl PlayerInput<'_, '_> {
+	fn gamepad_pressed(&self, button: GamepadButton) -> bool {
+		self.gamepads
+			.iter()
+			.any(|gamepad| gamepad.just_pressed(button))
+	}
+
+	fn key(&self, action: InputAction) -> KeyCode {
+		self.settings.controls.key(action)
+	}
+
+	/// A left click or a tap anywhere on the screen.
+	pub fn pointer_pressed(&self) -> bool {
+		self.mouse.just_pressed(MouseButton::Left) || self.touches.any_just_pressed()
+	}
+
+	/// Any key or button at all, for screens that just wait for the player.
+	pub fn any_pressed(&self) -> bool {
+		self.keyboard.get_just_pressed().next().is_some()
+			|| self.mouse.get_just_pressed().next().is_some()
+			|| self.touches.any_just_pressed()
+			|| self
+				.gamepads
+				.iter()
+				.any(|gamepad| gamepad.get_just_pressed().next().is_some())
+	}
+
+	pub fn flap_pressed(&self) -> bool {
+		self.keyboard.just_pressed(self.key(InputAction::Flap))
+			|| self.pointer_pressed()
+			|| self.gamepad_pressed(InputAction::Flap.gamepad_button())
+	}
+
+	pub fn start_pressed(&self) -> bool {
+		self.keyboard.just_pressed(self.key(InputAction::Flap))
+			|| self.pointer_pressed()
+			|| self.gamepad_pressed(GamepadButton::Start)
+	}
+
+	/// Restarting with the pointer is left to the caller, since a tap meant as
+	/// a flap right before dying shouldn't restart the game immediately.
+	pub fn restart_pressed(&self) -> bool {
+		self.keyboard.just_released(self.key(InputAction::Restart))
+			|| self.gamepad_pressed(InputAction::Restart.gamepad_button())
+	}
+
+	fn direction_pressed(&self, key: KeyCode, button: GamepadButton) -> bool {
+		self.keyboard.just_pressed(key) || self.gamepad_pressed(button)
+	}
+
+	/// Arrow keys or D-pad pressed this frame, with x to the right and y down.
+	pub fn menu_direction(&self) -> IVec2 {
+		let x = i32::from(self.direction_pressed(KeyCode::ArrowRight, GamepadButton::DPadRight))
+			- i32::from(self.direction_pressed(KeyCode::ArrowLeft, GamepadButton::DPadLeft));
+		let y = i32::from(self.direction_pressed(KeyCode::ArrowDown, GamepadButton::DPadDown))
+			- i32::from(self.direction_pressed(KeyCode::ArrowUp, GamepadButton::DPadUp));
+		IVec2::new(x, y)
+	}
+
+	/// -1 or 1 to move through the buttons of a menu, 0 when not moving.
+	pub fn menu_step(&self) -> i32 {
+		let direction = self.menu_direction();
+		(direction.x + direction.y).signum()
+	}
+
+	/// Picks the highlighted button of a menu.
+	pub fn confirm_pressed(&self) -> bool {
+		self.keyboard.just_pressed(KeyCode::Enter) || self.gamepad_pressed(GamepadButton::South)
+	}
+
+	pub fn pause_pressed(&self) -> bool {
+		self.keyboard
+			.any_just_pressed([self.key(InputAction::Pause), KeyCode::Escape])
+			|| self.gamepad_pressed(InputAction::Pause.gamepad_button())
+	}
+}
@@ -0,0 +1,46 @@
+//! The world inspector from `bevy-inspector-egui`, built in with the `dev`
+//! feature and shown with F9. Every entity can be looked at, and the score,
+//! the pipe spawn timer and the difficulty values tweaked while the game runs.
+//!
+//! Tweaked difficulty values only last until the game works them out again,
+//! when the score changes or the config file is reloaded.
+
+use bevy::prelude::*;
+use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::WorldInspectorPlugin};
+
+use crate::{
+	difficulty::{Difficulty, DifficultySettings},
+	GameScore, PipeSpawnTimer,
+};
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct InspectorShown(bool);
+
+fn inspector_shown(shown: Res<InspectorShown>) -> bool {
+	**shown
+}
+
+fn toggle_on_f9(keyboard_input: Res<ButtonInput<KeyCode>>, mut shown: ResMut<InspectorShown>) {
+	if keyboard_input.just_pressed(KeyCode::F9) {
+		**shown = !**shown;
+	}
+}
+
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+	fn build(&self, app: &mut App) {
+		app.register_type::<GameScore>()
+			.register_type::<PipeSpawnTimer>()
+			.register_type::<Difficulty>()
+			.register_type::<DifficultySettings>()
+			.init_resource::<InspectorShown>()
+			.add_plugins((
+				EguiPlugin {
+					enable_multipass_for_primary_context: true,
+				},
+				WorldInspectorPlugin::new().run_if(inspector_shown),
+			))
+			.add_systems(Update, toggle_on_f9);
+	}
+}
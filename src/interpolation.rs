@@ -0,0 +1,75 @@
+//! Smooths out the movement simulated in fixed ticks for screens refreshing
+//! faster than the simulation runs.
+//!
+//! Between two runs of the fixed loop the transform of anything moving shows
+//! a point between its last two simulated positions. Just before the next run
+//! the simulated position is put back, so the simulation never sees anything
+//! but its own positions and plays out the same as without smoothing.
+
+use bevy::{app::RunFixedMainLoopSystem, prelude::*};
+
+/// Added to everything with a [`Velocity`](crate::Velocity).
+#[derive(Component, Default)]
+pub struct Interpolated {
+	/// Position at the start of the latest tick, none if it only just
+	/// appeared.
+	previous: Option<Vec3>,
+	/// Position as simulated, while the transform shows `shown`.
+	simulated: Vec3,
+	shown: Option<Vec3>,
+}
+
+impl Interpolated {
+	/// Skips smoothing over the latest tick, for jumps that aren't movement.
+	pub fn teleported(&mut self) {
+		self.previous = None;
+	}
+}
+
+/// Anything moved since it was last shown was moved on purpose, like the
+/// ground being laid out again, and is taken as the new simulated position.
+fn restore_simulated(query: Query<(&mut Transform, &mut Interpolated)>) {
+	for (mut transform, mut interpolated) in query {
+		if interpolated.shown == Some(transform.translation) {
+			transform.translation = interpolated.simulated;
+		} else {
+			interpolated.previous = Some(transform.translation);
+		}
+		interpolated.shown = None;
+	}
+}
+
+fn remember_previous(query: Query<(&Transform, &mut Interpolated)>) {
+	for (transform, mut interpolated) in query {
+		interpolated.previous = Some(transform.translation);
+	}
+}
+
+fn show_interpolated(
+	query: Query<(&mut Transform, &mut Interpolated)>,
+	fixed_time: Res<Time<Fixed>>,
+) {
+	let fraction = fixed_time.overstep_fraction();
+	for (mut transform, mut interpolated) in query {
+		let simulated = transform.translation;
+		let previous = interpolated.previous.unwrap_or(simulated);
+		transform.translation = previous.lerp(simulated, fraction);
+		interpolated.simulated = simulated;
+		interpolated.shown = Some(transform.translation);
+	}
+}
+
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			RunFixedMainLoop,
+			(
+				restore_simulated.in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop),
+				show_interpolated.in_set(RunFixedMainLoopSystem::AfterFixedMainLoop),
+			),
+		)
+		.add_systems(FixedFirst, remember_previous);
+	}
+}
@@ -0,0 +1,186 @@
+//! Obstacle patterns authored in `assets/layouts/patterns.layouts.ron`, loaded
+//! as an asset so they can be changed without recompiling the game. The pipe
+//! spawner picks from them alongside the built-in ones, see
+//! [`pipe_patterns`](crate::pipe_patterns).
+
+use std::ops::Range;
+
+use bevy::{
+	asset::{io::Reader, AssetLoader, LoadContext},
+	ecs::system::SystemParam,
+	prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	loading::Preload,
+	pipe_patterns::{Column, Gap},
+};
+
+const PATTERNS_PATH: &str = "layouts/patterns.layouts.ron";
+
+/// A sequence of columns of pipes, spawned together like a built-in pattern.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AuthoredPattern {
+	pub name: String,
+	/// Score from which the pattern can come up.
+	pub from_score: i64,
+	/// How likely the pattern is compared to the others, see
+	/// [`PipePattern::choose`](crate::pipe_patterns::PipePattern::choose).
+	pub weight: u32,
+	pub columns: Vec<AuthoredColumn>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AuthoredColumn {
+	/// Distance from the column before, ignored for the first one.
+	#[serde(default)]
+	pub spacing: f32,
+	pub gaps: Vec<AuthoredGap>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AuthoredGap {
+	/// From 0 right above the ground to 1 right below the top of the play area.
+	pub position: f32,
+	/// Height of the gap, relative to the one set by the difficulty.
+	#[serde(default = "default_size")]
+	pub size: f32,
+	/// Whether the bird is meant to fly through, giving score.
+	#[serde(default = "yes")]
+	pub safe: bool,
+	#[serde(default = "yes")]
+	pub coin: bool,
+	#[serde(default)]
+	pub spinning_bar: bool,
+}
+
+fn default_size() -> f32 {
+	1.0
+}
+
+fn yes() -> bool {
+	true
+}
+
+impl AuthoredGap {
+	/// Where the gap goes for the current gap height, between `bounds`.
+	pub fn place(&self, gap: f32, bounds: &Range<f32>) -> Gap {
+		let height = gap * self.size;
+		let highest = (bounds.end - height).max(bounds.start);
+		Gap {
+			bottom: bounds.start.lerp(highest, self.position.clamp(0.0, 1.0)),
+			height,
+			safe: self.safe,
+			// The middle of a gap with a spinning bar is never clear.
+			coin: self.coin && self.safe && !self.spinning_bar,
+			spinning_bar: self.spinning_bar,
+		}
+	}
+}
+
+impl AuthoredPattern {
+	/// Lays the columns out for the current gap height, with every gap between
+	/// `bounds`. Overlapping gaps are pushed up out of each other's way.
+	pub fn columns(&self, gap: f32, bounds: &Range<f32>) -> Vec<Column> {
+		let mut offset = 0.0;
+		self.columns
+			.iter()
+			.enumerate()
+			.map(|(i, column)| {
+				if i > 0 {
+					offset += column.spacing;
+				}
+				let mut gaps = column
+					.gaps
+					.iter()
+					.map(|authored| authored.place(gap, bounds))
+					.collect::<Vec<_>>();
+				gaps.sort_by(|a, b| a.bottom.total_cmp(&b.bottom));
+				for i in 1..gaps.len() {
+					gaps[i].bottom = gaps[i].bottom.max(gaps[i - 1].top());
+				}
+				Column { offset, gaps }
+			})
+			.collect()
+	}
+}
+
+#[derive(Asset, TypePath, Serialize, Deserialize, Debug)]
+#[serde(transparent)]
+pub struct AuthoredPatterns(pub Vec<AuthoredPattern>);
+
+#[derive(Default)]
+struct AuthoredPatternsLoader;
+
+impl AssetLoader for AuthoredPatternsLoader {
+	type Asset = AuthoredPatterns;
+	type Settings = ();
+	type Error = Box<dyn std::error::Error + Send + Sync>;
+
+	async fn load(
+		&self,
+		reader: &mut dyn Reader,
+		_settings: &(),
+		_load_context: &mut LoadContext<'_>,
+	) -> Result<AuthoredPatterns, Self::Error> {
+		let mut bytes = Vec::new();
+		reader.read_to_end(&mut bytes).await?;
+		Ok(ron::de::from_bytes(&bytes)?)
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["layouts.ron"]
+	}
+}
+
+#[derive(Resource)]
+struct PatternsHandle(Handle<AuthoredPatterns>);
+
+impl FromWorld for PatternsHandle {
+	fn from_world(world: &mut World) -> Self {
+		let handle = world.resource::<AssetServer>().load(PATTERNS_PATH);
+		Preload::add(world, [handle.clone().untyped()]);
+		PatternsHandle(handle)
+	}
+}
+
+/// The authored patterns, none until they are loaded.
+#[derive(SystemParam)]
+pub struct Layouts<'w> {
+	handle: Res<'w, PatternsHandle>,
+	assets: Res<'w, Assets<AuthoredPatterns>>,
+}
+
+impl Layouts<'_> {
+	pub fn patterns(&self) -> &[AuthoredPattern] {
+		self.assets
+			.get(&self.handle.0)
+			.map_or(&[], |patterns| &patterns.0)
+	}
+}
+
+/// Lets whoever is authoring patterns know they were picked up.
+fn log_loaded_patterns(mut events: EventReader<AssetEvent<AuthoredPatterns>>, layouts: Layouts) {
+	for event in events.read() {
+		if event.is_loaded_with_dependencies(&layouts.handle.0) {
+			let names = layouts
+				.patterns()
+				.iter()
+				.map(|pattern| pattern.name.as_str())
+				.collect::<Vec<_>>();
+			info!("Loaded obstacle patterns: {}", names.join(", "));
+		}
+	}
+}
+
+pub struct LayoutsPlugin;
+
+impl Plugin for LayoutsPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_asset::<AuthoredPatterns>()
+			.init_asset_loader::<AuthoredPatternsLoader>()
+			.init_resource::<PatternsHandle>()
+			.add_systems(Update, log_loaded_patterns);
+	}
+}
@@ -0,0 +1,354 @@
+//! The online leaderboard: endless scores are submitted to an HTTP endpoint
+//! passed with `--leaderboard <url>`, and the global top 10 is fetched from it
+//! for the screen opened with O from the main menu. Only runs on Normal,
+//! without mutators and in clear weather are submitted, like the daily
+//! challenge, so they all rank against each other fairly.
+//!
+//! The endpoint takes a JSON score with a POST, `{"initials": "ABC", "score":
+//! 12, "seed": 1234}`, and answers a GET with the best scores as a JSON array
+//! of `{"initials": "ABC", "score": 12}`, highest first. Only plain `http://`
+//! URLs are supported, and none in the browser, where the leaderboard can't be
+//! reached.
+//!
+//! Requests are made in the background and never hold up the game. Scores that
+//! couldn't be sent are tried again after the next run, and the screen falls
+//! back to the local high scores while the leaderboard can't be reached.
+
+use std::io;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+	io::{Read, Write},
+	net::{TcpStream, ToSocketAddrs},
+	time::Duration,
+};
+
+use bevy::{
+	prelude::*,
+	tasks::{futures::check_ready, IoTaskPool, Task},
+};
+use serde::Serialize;
+
+use crate::{
+	cli::LaunchOptions,
+	daily::fair_rules,
+	headless::is_headless,
+	high_scores::{spawn_score_table, HighScore},
+	input::PlayerInput,
+	locale::Locale,
+	menu::MenuScreen,
+	save::SaveData,
+	seed::GameRng,
+	theme::UiTheme,
+	GameMode, GameScore, GameStates,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+const TIMEOUT: Duration = Duration::from_secs(5);
+/// How many scores the screen shows.
+const TOP_SIZE: usize = 10;
+/// Scores waiting to be sent are dropped beyond this many, oldest first.
+const MAX_UNSENT: usize = 50;
+/// Submitted for players that never entered initials.
+const ANONYMOUS: &str = "???";
+
+/// Where the leaderboard lives, parsed from an `http://host[:port]/path` URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+	host: String,
+	port: u16,
+	path: String,
+}
+
+impl Endpoint {
+	pub fn parse(url: &str) -> Option<Self> {
+		let rest = url.strip_prefix("http://")?;
+		let (authority, path) = match rest.find('/') {
+			Some(slash) => rest.split_at(slash),
+			None => (rest, "/"),
+		};
+		let (host, port) = match authority.rsplit_once(':') {
+			Some((host, port)) => (host, port.parse().ok()?),
+			None => (authority, 80),
+		};
+		(!host.is_empty()).then(|| Endpoint {
+			host: host.to_string(),
+			port,
+			path: path.to_string(),
+		})
+	}
+
+	/// The same endpoint, one level further down its path.
+	pub(crate) fn join(&self, segment: &str) -> Endpoint {
+		Endpoint {
+			path: format!("{}/{segment}", self.path.trim_end_matches('/')),
+			..self.clone()
+		}
+	}
+
+	/// The `Host` header, which leaves the port out only when it's the default.
+	#[cfg(not(target_arch = "wasm32"))]
+	fn host_header(&self) -> String {
+		match self.port {
+			80 => self.host.clone(),
+			port => format!("{}:{port}", self.host),
+		}
+	}
+
+	/// Sends a request and returns the body of a successful response. Asks for
+	/// HTTP/1.0 so the response can simply be read until the connection closes.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub(crate) fn request(&self, method: &str, body: &str) -> io::Result<String> {
+		let address = (self.host.as_str(), self.port)
+			.to_socket_addrs()?
+			.next()
+			.ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no address for host"))?;
+		let mut stream = TcpStream::connect_timeout(&address, TIMEOUT)?;
+		stream.set_read_timeout(Some(TIMEOUT))?;
+		stream.set_write_timeout(Some(TIMEOUT))?;
+		write!(
+			stream,
+			"{method} {} HTTP/1.0\r\nHost: {}\r\nAccept: application/json\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+			self.path,
+			self.host_header(),
+			body.len()
+		)?;
+		let mut response = String::new();
+		stream.read_to_string(&mut response)?;
+		let (head, body) = response
+			.split_once("\r\n\r\n")
+			.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed response"))?;
+		let status = head.split_whitespace().nth(1).unwrap_or_default();
+		if !status.starts_with('2') {
+			return Err(io::Error::other(format!("server answered {status}")));
+		}
+		Ok(body.to_string())
+	}
+
+	/// Browsers don't allow opening sockets.
+	#[cfg(target_arch = "wasm32")]
+	pub(crate) fn request(&self, _method: &str, _body: &str) -> io::Result<String> {
+		Err(io::Error::new(
+			io::ErrorKind::Unsupported,
+			"requests can't be made from the browser",
+		))
+	}
+
+	fn submit(&self, submission: &Submission) -> io::Result<()> {
+		let body = serde_json::to_string(submission)?;
+		self.request("POST", &body).map(drop)
+	}
+
+	fn top_scores(&self) -> io::Result<Vec<HighScore>> {
+		let mut scores: Vec<HighScore> = serde_json::from_str(&self.request("GET", "")?)?;
+		scores.truncate(TOP_SIZE);
+		Ok(scores)
+	}
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct Submission {
+	initials: String,
+	score: i64,
+	seed: u64,
+}
+
+/// Scores being sent, and the ones that failed to go out so far.
+#[derive(Resource, Default)]
+struct Submissions {
+	sending: Vec<Task<Result<(), Submission>>>,
+	unsent: Vec<Submission>,
+}
+
+/// What the leaderboard screen has to show.
+#[derive(Resource)]
+enum TopScores {
+	Loading(Task<io::Result<Vec<HighScore>>>),
+	Online(Vec<HighScore>),
+	/// Shows the local high scores instead, with the message saying why.
+	Offline(&'static str),
+}
+
+#[derive(Component)]
+struct LeaderboardUi;
+
+/// Live endless runs played by the [fair rules](crate::daily::fair_rules) go
+/// online, so every score on the board was played the same way.
+fn queue_score(
+	options: Res<LaunchOptions>,
+	mode: Res<GameMode>,
+	score: Res<GameScore>,
+	game_rng: Res<GameRng>,
+	save: Res<SaveData>,
+	mut submissions: ResMut<Submissions>,
+) {
+	if options.leaderboard.is_some() && *mode == GameMode::Endless && **score > 0 {
+		submissions.unsent.push(Submission {
+			initials: save.initials.clone().unwrap_or_else(|| ANONYMOUS.into()),
+			score: **score,
+			seed: game_rng.seed,
+		});
+	}
+}
+
+/// Sends the queued scores, along with anything that failed to go out before.
+fn submit_scores(options: Res<LaunchOptions>, mut submissions: ResMut<Submissions>) {
+	let Some(endpoint) = &options.leaderboard else {
+		return;
+	};
+	let pool = IoTaskPool::get();
+	for submission in std::mem::take(&mut submissions.unsent) {
+		let endpoint = endpoint.clone();
+		submissions.sending.push(pool.spawn(async move {
+			endpoint.submit(&submission).map_err(|err| {
+				warn!("Failed to submit score to the leaderboard: {err}");
+				submission
+			})
+		}));
+	}
+}
+
+fn finish_submissions(mut submissions: ResMut<Submissions>) {
+	let Submissions { sending, unsent } = &mut *submissions;
+	sending.retain_mut(|task| match check_ready(task) {
+		Some(result) => {
+			unsent.extend(result.err());
+			false
+		}
+		None => true,
+	});
+	let excess = unsent.len().saturating_sub(MAX_UNSENT);
+	unsent.drain(..excess);
+}
+
+fn fetch_top_scores(mut commands: Commands, options: Res<LaunchOptions>) {
+	let top_scores = match options.leaderboard.clone() {
+		Some(endpoint) => {
+			TopScores::Loading(IoTaskPool::get().spawn(async move { endpoint.top_scores() }))
+		}
+		None => TopScores::Offline("leaderboard-not-set-up"),
+	};
+	commands.insert_resource(top_scores);
+}
+
+fn finish_fetch(mut top_scores: ResMut<TopScores>) {
+	// Only the result coming in counts as a change, for the screen to show it.
+	let TopScores::Loading(task) = top_scores.bypass_change_detection() else {
+		return;
+	};
+	if let Some(result) = check_ready(task) {
+		*top_scores = match result {
+			Ok(scores) => TopScores::Online(scores),
+			Err(err) => {
+				warn!("Failed to fetch the leaderboard: {err}");
+				TopScores::Offline("leaderboard-unreachable")
+			}
+		};
+	}
+}
+
+/// Rebuilt whenever the scores come in.
+fn spawn_leaderboard(
+	mut commands: Commands,
+	top_scores: Res<TopScores>,
+	save: Res<SaveData>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+	ui: Query<Entity, With<LeaderboardUi>>,
+) {
+	for entity in &ui {
+		commands.entity(entity).despawn();
+	}
+	let (status, scores) = match &*top_scores {
+		TopScores::Loading(_) => (Some(locale.text("leaderboard-loading")), &[][..]),
+		TopScores::Online(scores) if scores.is_empty() => {
+			(Some(locale.text("leaderboard-empty")), &[][..])
+		}
+		TopScores::Online(scores) => (None, &scores[..]),
+		TopScores::Offline(reason) => (
+			Some(locale.format("leaderboard-offline", &[("reason", &locale.text(reason))])),
+			save.high_scores.entries(),
+		),
+	};
+	commands
+		.spawn((
+			LeaderboardUi,
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				row_gap: Val::Px(16.0),
+				..default()
+			},
+			BackgroundColor(theme.screen),
+		))
+		.with_children(|parent| {
+			parent.spawn((
+				Text::new(locale.text("leaderboard-title")),
+				theme.text(72.0),
+			));
+			if let Some(status) = status {
+				parent.spawn((Text::new(status), theme.text(32.0)));
+			}
+			spawn_score_table(parent, scores, &theme);
+			parent.spawn((Text::new(locale.text("back")), theme.text(24.0)));
+		});
+}
+
+fn despawn_leaderboard(mut commands: Commands, ui: Single<Entity, With<LeaderboardUi>>) {
+	commands.entity(*ui).despawn();
+	commands.remove_resource::<TopScores>();
+}
+
+fn open_leaderboard_on_o(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyO) {
+		next_screen.set(MenuScreen::Leaderboard);
+	}
+}
+
+fn close_leaderboard(
+	input: PlayerInput,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::Escape) || input.confirm_pressed() {
+		next_screen.set(MenuScreen::Main);
+	}
+}
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<Submissions>()
+			.add_systems(
+				OnEnter(GameStates::GameOver),
+				(
+					queue_score.run_if(crate::counts_for_progress.and(fair_rules)),
+					submit_scores,
+				)
+					.chain()
+					.run_if(not(is_headless)),
+			)
+			.add_systems(OnEnter(MenuScreen::Leaderboard), fetch_top_scores)
+			.add_systems(OnExit(MenuScreen::Leaderboard), despawn_leaderboard)
+			.add_systems(
+				Update,
+				(
+					finish_submissions,
+					open_leaderboard_on_o.run_if(in_state(MenuScreen::Main)),
+					(
+						finish_fetch,
+						spawn_leaderboard.run_if(resource_exists_and_changed::<TopScores>),
+						close_leaderboard,
+					)
+						.chain()
+						.run_if(in_state(MenuScreen::Leaderboard)),
+				),
+			);
+	}
+}
@@ -0,0 +1,1086 @@
+mod achievements;
+mod attract;
+mod autopilot;
+mod bench;
+mod biome;
+mod camera_shake;
+mod cleanup;
+mod cli;
+mod cloud_sync;
+mod coins;
+mod collision;
+mod combo;
+mod config;
+mod crash;
+mod credits;
+mod crt;
+mod daily;
+mod day_night;
+mod death_flash;
+mod debug_overlay;
+mod difficulty;
+mod editor;
+mod enemies;
+mod env;
+mod evolution;
+mod game_over;
+mod get_ready;
+mod ghost;
+mod gravity_flip;
+mod ground;
+mod hardcore;
+mod headless;
+mod high_contrast;
+mod high_scores;
+mod input;
+#[cfg(feature = "dev")]
+mod inspector;
+mod interpolation;
+mod layouts;
+mod leaderboard;
+mod lives;
+mod loading;
+mod locale;
+mod medals;
+mod menu;
+mod mirror;
+mod moving_gaps;
+mod music;
+mod mutators;
+mod near_miss;
+mod night;
+mod offscreen;
+mod palette;
+mod particles;
+mod pause;
+mod pipe_patterns;
+mod pipe_pool;
+mod play_area;
+mod power_ups;
+mod profiles;
+mod race;
+mod replay;
+mod replay_file;
+mod rotating_bars;
+mod rumble;
+mod sandbox;
+mod save;
+mod score_popup;
+mod seed;
+mod settings;
+mod shop;
+mod skins;
+#[cfg(feature = "tts")]
+mod speech;
+mod splash;
+mod split_screen;
+mod sprites;
+mod stages;
+mod stats;
+#[cfg(feature = "steam")]
+mod steam;
+mod tas;
+#[cfg(feature = "test-support")]
+mod testing;
+mod theme;
+mod time_attack;
+mod timed_transition;
+mod touch;
+mod trail;
+mod tutorial;
+mod tween;
+mod versus;
+mod weather;
+mod wind;
+mod zen;
+
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_6};
+
+pub use env::{FlappyEnv, Observation};
+#[cfg(feature = "test-support")]
+#[doc(hidden)]
+pub use testing::TestGame;
+
+use serde::{Deserialize, Serialize};
+
+use bevy::{
+	asset::AssetMetaCheck, ecs::system::SystemParam, prelude::*, ui::Node, window::WindowMode,
+};
+
+use cleanup::RunScene;
+use collision::{Collider, ColliderShape, CollisionLayer, OnCollision};
+use crash::Crash;
+use day_night::Tinted;
+use difficulty::{Difficulty, DifficultySettings};
+use get_ready::RunPhase;
+use ground::ground_top;
+use input::PlayerInput;
+use interpolation::Interpolated;
+use layouts::Layouts;
+use lives::{Lives, LIFE_LOST_GRACE};
+use locale::Locale;
+use menu::MenuScreen;
+use mirror::ScrollDirection;
+use offscreen::DespawnOffscreen;
+use pause::Pause;
+use pipe_patterns::{Gap, PipePattern};
+use pipe_pool::{PipePool, PipeSpawner};
+use play_area::{PlayArea, VisibleArea};
+use power_ups::{absorb_hit, timed_effect, HitProtection, Invulnerable};
+use seed::GameRng;
+use settings::motion_allowed;
+use sprites::{SpriteAnimation, Textures};
+use theme::UiTheme;
+use touch::IS_MOBILE;
+use zen::ZEN_MISS_GRACE;
+
+const WINDOW_SIZE: Vec2 = Vec2::new(1280.0, 720.0);
+
+const SCOREBOARD_TEXT_PADDING: Val = Val::Px(5.0);
+
+const PLAYER_MAX_TILT: f32 = FRAC_PI_6;
+const PLAYER_MIN_TILT: f32 = -FRAC_PI_2;
+const PLAYER_TILT_PER_VELOCITY: f32 = 0.002;
+const PLAYER_TILT_SMOOTHING: f32 = 12.0;
+
+/// Taps, clicks and buttons only restart the game after it has been over for
+/// this long.
+const RESTART_DELAY: f32 = 0.5;
+/// Seconds a flap waits for a simulated tick to take it before it's dropped,
+/// long enough to bridge frames without a tick and the start of the next run.
+const FLAP_BUFFER: f32 = 0.1;
+
+const PLAYER_SIZE: Vec2 = Vec2::new(32.0, 32.0);
+const PLAYER_START: Vec3 = Vec3::new(-320.0, 0.0, 0.0);
+const PIPE_WIDTH: f32 = 32.0;
+/// Pipes take on a little less of the sky's tint than the background.
+const PIPE_TINT: f32 = 0.7;
+
+#[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+enum GameStates {
+	/// Waiting for assets at startup, see [`loading`].
+	#[default]
+	Loading,
+	/// Branding shown once before the menu, see [`splash`].
+	Splash,
+	Menu,
+	InGame,
+	GameOver,
+	/// Re-simulating a recorded run, see [`replay`].
+	Replay,
+	/// Building obstacle patterns, see [`editor`].
+	Editor,
+	/// Watching birds learn to fly, see [`evolution`].
+	Evolution,
+	/// Two players side by side, see [`split_screen`].
+	SplitScreen,
+}
+
+/// Present while a run is being simulated, whether played live or replayed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct InRun;
+
+impl ComputedStates for InRun {
+	type SourceStates = GameStates;
+
+	fn compute(state: GameStates) -> Option<Self> {
+		matches!(state, GameStates::InGame | GameStates::Replay).then_some(InRun)
+	}
+}
+
+/// The steps of a simulated tick, run in order so that runs with the same seed
+/// and inputs play out identically.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+enum GameplaySet {
+	Input,
+	Physics,
+	Spawn,
+	Collision,
+	/// Acting on what the collisions found, like ending the run.
+	Outcome,
+	Scoring,
+	Progress,
+}
+
+/// What kind of run is started when leaving the menu.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum GameMode {
+	#[default]
+	Endless,
+	Daily,
+	/// Endless, but with a few hearts to lose before the run is over.
+	Lives,
+	/// Practice without crashing, see [`zen`].
+	Zen,
+	/// Harder than endless, see [`hardcore`].
+	Hardcore,
+	/// One of the handcrafted [`stages`], by index.
+	Stage(u8),
+	/// Trying out a pattern from the [`editor`].
+	Playtest,
+	/// Endless, played by the [`autopilot`].
+	Autopilot,
+	/// Against other players over the network, see [`race`].
+	Race,
+	/// Two players sharing the screen, see [`versus`].
+	Versus,
+	/// Against the clock to one of the [`time_attack::TARGETS`], by index.
+	TimeAttack(u8),
+}
+
+/// Something that happened in a run, for anything keeping track of the
+/// player's progress.
+#[derive(Event, Debug, Clone, Copy)]
+enum GameplayEvent {
+	/// This bird flapped its wings.
+	Flapped(Entity),
+	/// The bird passed a pipe, with the number passed so far this run.
+	PipePassed(u32),
+	/// The bird picked up a coin.
+	CoinCollected,
+	/// The bird made it through a gap with barely any room to spare, see
+	/// [`near_miss`].
+	CloseCall,
+	/// The bird hit a pipe and lost one of its hearts, see [`lives`].
+	LifeLost,
+	/// The bird flew into something it passes through in zen mode.
+	Missed,
+	/// A gust of wind is about to push the bird this hard, see [`wind`].
+	WindRising(Vec2),
+	/// A live run ended in a crash after lasting this many seconds.
+	RunEnded { seconds: f32 },
+}
+
+/// Points for the score, which only changes by way of these.
+#[derive(Event, Debug, Clone, Copy)]
+struct ScoredEvent {
+	points: i64,
+}
+
+/// A bird crashed with nothing left to save it, which ends the run once no
+/// other bird is left flying.
+#[derive(Event, Debug, Clone, Copy)]
+struct PlayerDiedEvent(Entity);
+
+/// A bird still in the run. There's only the one, except in [`versus`].
+#[derive(Component)]
+#[require(Seat)]
+struct Player;
+
+/// Which player a bird belongs to. The first player flaps through
+/// [`PendingFlap`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct Seat(usize);
+
+/// The camera everything is seen through, unless a mode brings its own.
+#[derive(Component)]
+struct MainCamera;
+
+#[derive(Component)]
+struct Scoretext;
+
+#[derive(Component)]
+struct Background;
+
+/// Something the bird crashes into.
+#[derive(Component, Default)]
+#[require(Collider = Collider::new(ColliderShape::Sprite, CollisionLayer::Obstacle))]
+struct Obstacle;
+
+#[derive(Component)]
+#[require(Obstacle, RunScene, DespawnOffscreen = DespawnOffscreen::new(PIPE_WIDTH))]
+struct Pipe {
+	give_score: bool,
+	/// Height of the gap right below this pipe, if there is one.
+	gap: f32,
+	/// Whether a coin floats in the gap right below this pipe.
+	coin: bool,
+	/// Closest the bird came to the edges of the gap below while flying
+	/// through it, see [`near_miss`] and [`combo`].
+	closest: f32,
+}
+
+impl Pipe {
+	/// Middle of the gap right below this pipe, if it's one to fly through and
+	/// the bird hasn't yet.
+	fn open_gap_center(&self, transform: &Transform, sprite: &Sprite) -> Option<Vec2> {
+		let height = sprite.custom_size.unwrap_or_default().y;
+		let bottom = transform.translation.y - height / 2.0;
+		self.give_score
+			.then(|| Vec2::new(transform.translation.x, bottom - self.gap / 2.0))
+	}
+}
+
+/// Middle of the gap the bird at `x` has to fly through next, if one has come
+/// in yet.
+fn next_gap<'a>(
+	x: f32,
+	pipes: impl IntoIterator<Item = (&'a Pipe, &'a Transform, &'a Sprite)>,
+) -> Option<Vec2> {
+	pipes
+		.into_iter()
+		.filter_map(|(pipe, transform, sprite)| pipe.open_gap_center(transform, sprite))
+		.min_by(|a, b| (a.x - x).abs().total_cmp(&(b.x - x).abs()))
+}
+
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+struct PipeSpawnTimer {
+	timer: Timer,
+	/// Seconds to wait before the timer runs again, leaving room for the last
+	/// columns of a long pattern.
+	delay: f32,
+}
+
+#[derive(Resource, Reflect, Default, Deref, DerefMut)]
+#[reflect(Resource)]
+struct GameScore(i64);
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct PipesPassed(u32);
+
+/// Number of fixed updates simulated since the run started.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct RunTick(u32);
+
+/// How the run that just ended went.
+#[derive(SystemParam)]
+struct RunResult<'w> {
+	game_rng: Res<'w, GameRng>,
+	score: Res<'w, GameScore>,
+	pipes_passed: Res<'w, PipesPassed>,
+	run_tick: Res<'w, RunTick>,
+	fixed_time: Res<'w, Time<Fixed>>,
+}
+
+impl RunResult<'_> {
+	fn seconds(&self) -> f32 {
+		**self.run_tick as f32 * self.fixed_time.timestep().as_secs_f32()
+	}
+}
+
+/// When the current game over began, in seconds since startup.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct GameOverAt(f32);
+
+/// When the player, or whatever plays in their place, last asked to flap, in
+/// real seconds since startup. The flap is applied on the next simulated tick, or dropped after
+/// [`FLAP_BUFFER`] if there isn't one.
+#[derive(Resource, Default)]
+struct PendingFlap(Option<f32>);
+
+impl PendingFlap {
+	fn press(&mut self, now: f32) {
+		self.0 = Some(now);
+	}
+
+	fn is_pending(&self) -> bool {
+		self.0.is_some()
+	}
+
+	fn take(&mut self) -> bool {
+		self.0.take().is_some()
+	}
+
+	fn expire(&mut self, now: f32) {
+		if self
+			.0
+			.is_some_and(|pressed_at| now - pressed_at > FLAP_BUFFER)
+		{
+			self.0 = None;
+		}
+	}
+}
+
+#[derive(Component, Default)]
+#[require(Transform, Interpolated)]
+struct Velocity {
+	x: f32,
+	y: f32,
+}
+
+/// Moves horizontally with the rest of the world at the current pipe speed.
+#[derive(Component, Default)]
+#[require(Velocity)]
+struct Scrolling;
+
+#[derive(Component, Default)]
+#[require(Velocity)]
+struct Acceleration {
+	x: f32,
+	y: f32,
+}
+impl Acceleration {
+	fn gravity(strength: f32) -> Self {
+		Acceleration {
+			x: 0.0,
+			y: -strength,
+		}
+	}
+}
+
+fn make_player(
+	textures: &Textures,
+	settings: &DifficultySettings,
+	direction: ScrollDirection,
+) -> impl Bundle {
+	let mut sprite = textures.bird_sprite(PLAYER_SIZE);
+	sprite.flip_x = direction.flip_x();
+	(
+		sprite,
+		SpriteAnimation::bird(),
+		Transform::from_translation(direction.player_start()),
+		Acceleration::gravity(settings.gravity),
+		Velocity::default(),
+		Player,
+		RunScene,
+	)
+}
+
+fn setup(
+	mut commands: Commands,
+	textures: Res<Textures>,
+	difficulty: Res<Difficulty>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	commands.insert_resource(PipeSpawnTimer {
+		timer: Timer::new(difficulty.pipe_spawn_interval, TimerMode::Repeating),
+		delay: 0.0,
+	});
+	commands.spawn((MainCamera, Camera2d, IsDefaultUiCamera));
+	commands.spawn((
+		Background,
+		Tinted(1.0),
+		Sprite {
+			image: textures.background.clone(),
+			custom_size: Some(WINDOW_SIZE),
+			..default()
+		},
+		Transform::from_xyz(0.0, 0.0, -10.0),
+	));
+	commands.spawn((
+		Scoretext,
+		Text::new(locale.format("score", &[("score", &0)])),
+		theme.text(64.0),
+		Node {
+			position_type: PositionType::Absolute,
+			top: SCOREBOARD_TEXT_PADDING,
+			left: SCOREBOARD_TEXT_PADDING,
+			..default()
+		},
+	));
+}
+
+fn fit_background(
+	visible_area: Res<VisibleArea>,
+	mut background: Single<&mut Sprite, With<Background>>,
+) {
+	background.custom_size = Some(visible_area.size());
+}
+
+fn on_enter_game(
+	mut commands: Commands,
+	textures: Res<Textures>,
+	settings: Res<DifficultySettings>,
+	direction: Res<ScrollDirection>,
+	mut run_tick: ResMut<RunTick>,
+	mut pending_flap: ResMut<PendingFlap>,
+	state: Res<State<GameStates>>,
+) {
+	commands.spawn(make_player(&textures, &settings, *direction));
+	**run_tick = 0;
+	// A flap pressed right before restarting carries over, replays only get
+	// their recorded flaps.
+	if *state.get() == GameStates::Replay {
+		pending_flap.0 = None;
+	}
+}
+
+/// The bird and the pipes are left in place after a crash, and cleaned up
+/// along with the rest of the [`RunScene`] once the game moves on.
+fn on_game_restart(
+	mut score: ResMut<GameScore>,
+	mut pipes_passed: ResMut<PipesPassed>,
+	mut pipe_spawn_timer: ResMut<PipeSpawnTimer>,
+) {
+	pipe_spawn_timer.timer.reset();
+	pipe_spawn_timer.delay = 0.0;
+	**score = 0;
+	**pipes_passed = 0;
+}
+
+fn handle_movement(
+	input: PlayerInput,
+	mut pending_flap: ResMut<PendingFlap>,
+	time: Res<Time<Real>>,
+) {
+	if input.flap_pressed() {
+		pending_flap.press(time.elapsed_secs());
+	}
+}
+
+/// Runs after the frame's fixed updates had their chance to take the flap, so
+/// only flaps the simulation couldn't take in time are dropped, like those
+/// pressed while paused or crashing.
+fn expire_pending_flap(mut pending_flap: ResMut<PendingFlap>, time: Res<Time<Real>>) {
+	pending_flap.expire(time.elapsed_secs());
+}
+
+/// Flaps push the bird against gravity, whichever way it currently pulls.
+fn flap(velocity: &mut Velocity, acceleration: &Acceleration, settings: &DifficultySettings) {
+	velocity.y = -acceleration.y.signum() * settings.jump_strength;
+}
+
+fn apply_flap(
+	settings: Res<DifficultySettings>,
+	mut pending_flap: ResMut<PendingFlap>,
+	players: Query<(Entity, &Seat, &mut Velocity, &Acceleration), With<Player>>,
+	mut events: EventWriter<GameplayEvent>,
+) {
+	if !pending_flap.take() {
+		return;
+	}
+	for (entity, seat, mut velocity, acceleration) in players {
+		if *seat == Seat(0) {
+			flap(&mut velocity, acceleration, &settings);
+			events.write(GameplayEvent::Flapped(entity));
+		}
+	}
+}
+
+/// How far the bird leans while moving vertically at the given speed, leaning
+/// the other way when it flies to the left.
+fn player_tilt(velocity_y: f32, direction: ScrollDirection) -> f32 {
+	let tilt = (velocity_y * PLAYER_TILT_PER_VELOCITY).clamp(PLAYER_MIN_TILT, PLAYER_MAX_TILT);
+	-direction.sign() * tilt
+}
+
+fn tilt_player(
+	players: Query<(&mut Transform, &Velocity), With<Player>>,
+	direction: Res<ScrollDirection>,
+	time: Res<Time>,
+) {
+	let smoothing = 1.0 - (-PLAYER_TILT_SMOOTHING * time.delta_secs()).exp();
+	for (mut transform, velocity) in players {
+		let target = player_tilt(velocity.y, *direction);
+		let (_, _, current) = transform.rotation.to_euler(EulerRot::XYZ);
+		transform.rotation = Quat::from_rotation_z(current.lerp(target, smoothing));
+	}
+}
+
+fn apply_velocity(mut query: Query<(&mut Transform, &Velocity)>, time: Res<Time>) {
+	let elapsed = time.delta_secs();
+	for (mut transform, velocity) in &mut query {
+		let moved = Vec2::new(velocity.x * elapsed, velocity.y * elapsed);
+		transform.translation += moved.extend(0.0);
+	}
+}
+
+fn apply_scrolling(
+	difficulty: Res<Difficulty>,
+	direction: Res<ScrollDirection>,
+	query: Query<&mut Velocity, With<Scrolling>>,
+) {
+	for mut velocity in query {
+		velocity.x = direction.sign() * difficulty.pipe_speed;
+	}
+}
+
+fn apply_acceleration(mut query: Query<(&mut Velocity, &Acceleration)>, time: Res<Time>) {
+	let elapsed = time.delta_secs();
+	for (mut velocity, acceleration) in &mut query {
+		velocity.x += acceleration.x * elapsed;
+		velocity.y += acceleration.y * elapsed;
+	}
+}
+
+#[derive(Bundle)]
+struct PipeBundle {
+	sprite: Sprite,
+	transform: Transform,
+	scrolling: Scrolling,
+	pipe: Pipe,
+	tinted: Tinted,
+}
+
+impl PipeBundle {
+	/// A pipe `height` tall hanging down from `y`, right above the gap `below`
+	/// if there is one.
+	fn new(textures: &Textures, x: f32, height: f32, y: f32, below: Option<&Gap>) -> Self {
+		PipeBundle {
+			sprite: textures.pipe_sprite(Vec2::new(PIPE_WIDTH, height)),
+			transform: Transform::from_xyz(x, y - height / 2.0, 0.0),
+			scrolling: Scrolling,
+			pipe: Pipe {
+				give_score: below.is_some_and(|gap| gap.safe),
+				gap: below.map_or(0.0, |gap| gap.height),
+				coin: below.is_some_and(|gap| gap.coin),
+				closest: f32::INFINITY,
+			},
+			tinted: Tinted(PIPE_TINT),
+		}
+	}
+}
+
+/// Everything deciding what the next obstacle looks like and where it goes.
+#[derive(SystemParam)]
+struct SpawnContext<'w> {
+	play_area: Res<'w, PlayArea>,
+	direction: Res<'w, ScrollDirection>,
+	difficulty: Res<'w, Difficulty>,
+	score: Res<'w, GameScore>,
+	layouts: Layouts<'w>,
+}
+
+fn handle_pipe_spawn(
+	mut pipes: PipeSpawner,
+	time: Res<Time>,
+	mut pipe_spawn_timer: ResMut<PipeSpawnTimer>,
+	context: SpawnContext,
+	mut game_rng: ResMut<GameRng>,
+) {
+	if pipe_spawn_timer.delay > 0.0 {
+		pipe_spawn_timer.delay -= time.delta_secs();
+		return;
+	}
+	pipe_spawn_timer.timer.tick(time.delta());
+	if !pipe_spawn_timer.timer.finished() {
+		return;
+	}
+	let play_area = &context.play_area;
+	let authored = context.layouts.patterns();
+	let pattern = PipePattern::choose(**context.score, authored, &mut game_rng);
+	let bounds = ground_top(play_area)..play_area.max.y;
+	let columns = pattern.columns(context.difficulty.pipe_gap, bounds, authored, &mut game_rng);
+	let entry_x = context.direction.entry_x(play_area);
+	// Authored patterns can be longer than the distance between two spawns.
+	if let (PipePattern::Authored(_), Some(last)) = (pattern, columns.last()) {
+		pipe_spawn_timer.delay = last.offset / context.difficulty.pipe_speed;
+	}
+	for column in columns {
+		// Later columns come in further out.
+		let x = entry_x - context.direction.sign() * column.offset;
+		pipes.spawn_column(x, play_area.height(), &column.gaps);
+	}
+}
+
+/// What a hit the bird doesn't survive unharmed costs it, depending on the
+/// mode.
+#[derive(SystemParam)]
+struct HitConsequences<'w> {
+	lives: ResMut<'w, Lives>,
+	mode: Res<'w, GameMode>,
+	events: EventWriter<'w, GameplayEvent>,
+	deaths: EventWriter<'w, PlayerDiedEvent>,
+}
+
+impl HitConsequences<'_> {
+	fn apply(&mut self, player: &mut EntityCommands) {
+		if *self.mode == GameMode::Zen {
+			player.insert(timed_effect(Invulnerable, ZEN_MISS_GRACE));
+			self.events.write(GameplayEvent::Missed);
+		} else if self.lives.lose_one() {
+			player.insert(timed_effect(Invulnerable, LIFE_LOST_GRACE));
+			self.events.write(GameplayEvent::LifeLost);
+		} else {
+			self.deaths.write(PlayerDiedEvent(player.id()));
+		}
+	}
+}
+
+/// Crashes into anything that's an [`Obstacle`], unless something saves the
+/// bird.
+fn hit_obstacle(
+	trigger: Trigger<OnCollision>,
+	mut commands: Commands,
+	players: Query<HitProtection, With<Player>>,
+	mut consequences: HitConsequences,
+) {
+	if trigger.layer != CollisionLayer::Obstacle {
+		return;
+	}
+	let Ok(protection) = players.get(trigger.target()) else {
+		return;
+	};
+	let mut player = commands.entity(trigger.target());
+	if !absorb_hit(&mut player, protection) {
+		consequences.apply(&mut player);
+	}
+}
+
+fn check_player_screen_bounds(
+	play_area: Res<PlayArea>,
+	players: Query<(&Transform, &mut Velocity), With<Player>>,
+) {
+	for (transform, mut velocity) in players {
+		if transform.translation.y - 100.0 > play_area.max.y {
+			velocity.y = 0.0;
+		}
+	}
+}
+
+/// Passed once the whole pipe is behind the whole bird.
+fn has_passed(direction: ScrollDirection, pipe_x: f32, player_x: f32) -> bool {
+	direction.distance_past(pipe_x, player_x) > (PIPE_WIDTH + PLAYER_SIZE.x) / 2.0
+}
+
+/// Birds all fly at the same x, so pipes are passed by all of them at once.
+fn give_score_when_over_player(
+	mut pipes_passed: ResMut<PipesPassed>,
+	mut events: EventWriter<GameplayEvent>,
+	mut scored: EventWriter<ScoredEvent>,
+	players: Query<&Transform, With<Player>>,
+	pipes_query: Query<(&Transform, &mut Pipe)>,
+	direction: Res<ScrollDirection>,
+) {
+	let Some(player_x) = players.iter().next().map(|player| player.translation.x) else {
+		return;
+	};
+	for (pipe_transform, mut pipe) in pipes_query {
+		if !pipe.give_score {
+			continue;
+		}
+		if has_passed(*direction, pipe_transform.translation.x, player_x) {
+			pipe.give_score = false;
+			scored.write(ScoredEvent { points: 1 });
+			**pipes_passed += 1;
+			events.write(GameplayEvent::PipePassed(**pipes_passed));
+		}
+	}
+}
+
+fn apply_scored(mut events: EventReader<ScoredEvent>, mut score: ResMut<GameScore>) {
+	for event in events.read() {
+		**score += event.points;
+	}
+}
+
+/// The one place a crash ends the run, whatever the bird hit. Birds crashing
+/// while others are still flying are left behind, out of the run.
+fn end_run_on_death(
+	mut commands: Commands,
+	mut events: EventReader<PlayerDiedEvent>,
+	players: Query<Entity, With<Player>>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	let mut died = events.read().map(|event| event.0).collect::<Vec<_>>();
+	if died.is_empty() {
+		return;
+	}
+	died.sort();
+	died.dedup();
+	if players.iter().all(|player| died.contains(&player)) {
+		next_state.set(GameStates::GameOver);
+		return;
+	}
+	for entity in died {
+		commands
+			.entity(entity)
+			.remove::<Player>()
+			.insert(versus::Downed);
+	}
+}
+
+fn update_score(
+	score: Res<GameScore>,
+	locale: Res<Locale>,
+	mut score_display: Single<&mut Text, With<Scoretext>>,
+) {
+	**score_display = locale.format("score", &[("score", &**score)]).into();
+}
+
+fn advance_run_tick(mut run_tick: ResMut<RunTick>) {
+	**run_tick += 1;
+}
+
+/// Playtests of a pattern from the editor, runs the autopilot or a TAS script
+/// plays and versus runs between two players don't count towards stats,
+/// achievements or coins.
+fn counts_for_progress(mode: Res<GameMode>, script: Option<Res<tas::TasScript>>) -> bool {
+	script.is_none()
+		&& !matches!(
+			*mode,
+			GameMode::Playtest | GameMode::Autopilot | GameMode::Versus
+		)
+}
+
+/// Stops the simulation as soon as a state change is queued, so a run ends on
+/// the same tick no matter how many fixed updates the current frame runs.
+fn no_pending_transition(next_state: Res<NextState<GameStates>>) -> bool {
+	matches!(*next_state, NextState::Unchanged)
+}
+
+/// The get ready phase isn't simulated, so runs are timed from the first flap.
+fn send_run_ended(
+	run_tick: Res<RunTick>,
+	fixed_time: Res<Time<Fixed>>,
+	mut events: EventWriter<GameplayEvent>,
+) {
+	events.write(GameplayEvent::RunEnded {
+		seconds: **run_tick as f32 * fixed_time.timestep().as_secs_f32(),
+	});
+}
+
+fn mark_game_over(mut game_over_at: ResMut<GameOverAt>, time: Res<Time>) {
+	**game_over_at = time.elapsed_secs();
+}
+
+fn restart_on_r(
+	input: PlayerInput,
+	buttons: Query<&Interaction, With<Button>>,
+	game_over_at: Res<GameOverAt>,
+	time: Res<Time>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	// Clicks on the game over buttons are handled by the buttons themselves.
+	let on_button = buttons
+		.iter()
+		.any(|interaction| *interaction != Interaction::None);
+	let pointer_allowed = !on_button && time.elapsed_secs() - **game_over_at > RESTART_DELAY;
+	if input.restart_pressed() || (pointer_allowed && input.pointer_pressed()) {
+		next_state.set(GameStates::InGame);
+	}
+}
+
+/// Builds the full game, ready to `run()`.
+pub fn app() -> App {
+	let options = cli::LaunchOptions::from_args(std::env::args().skip(1));
+	let headless = options.headless;
+	let bench = options.bench.is_some();
+	let mut app = game_app(options);
+	if bench {
+		app.add_plugins(bench::BenchPlugin);
+	} else if headless {
+		app.add_plugins(headless::RunReportPlugin);
+	} else {
+		app.add_plugins((crt::CrtPlugin, debug_overlay::DebugOverlayPlugin));
+	}
+	#[cfg(feature = "dev")]
+	if !headless {
+		app.add_plugins(inspector::InspectorPlugin);
+	}
+	#[cfg(feature = "steam")]
+	if !headless {
+		app.add_plugins(steam::SteamPlugin);
+	}
+	#[cfg(feature = "tts")]
+	if !headless {
+		app.add_plugins(speech::SpeechPlugin);
+	}
+	app
+}
+
+fn game_app(options: cli::LaunchOptions) -> App {
+	let window_size = options
+		.window_size
+		.map_or(WINDOW_SIZE, |size| size.as_vec2());
+	let headless = options.headless;
+	let mut app = App::new();
+	app.insert_resource(options);
+	if headless {
+		app.add_plugins(headless::HeadlessPlugin);
+	} else {
+		app.add_plugins(
+			DefaultPlugins
+				.set(WindowPlugin {
+					primary_window: Some(Window {
+						title: "Flappy game".into(),
+						resizable: true,
+						resolution: window_size.into(),
+						mode: if IS_MOBILE {
+							WindowMode::BorderlessFullscreen(MonitorSelection::Primary)
+						} else {
+							WindowMode::Windowed
+						},
+						// Only used in the browser, see web/index.html.
+						canvas: Some("#flappy".into()),
+						..default()
+					}),
+					..default()
+				})
+				.set(AssetPlugin {
+					// Web servers answer the lookups for missing .meta files with
+					// errors, and the game has none.
+					meta_check: AssetMetaCheck::Never,
+					..default()
+				})
+				.set(ImagePlugin::default_nearest()),
+		);
+	}
+	app.insert_resource(GameScore::default())
+		.init_resource::<PipesPassed>()
+		.init_resource::<GameMode>()
+		.init_resource::<RunTick>()
+		.init_resource::<PendingFlap>()
+		.init_resource::<PipePool>()
+		.init_resource::<GameOverAt>()
+		.add_event::<GameplayEvent>()
+		.add_event::<ScoredEvent>()
+		.add_event::<PlayerDiedEvent>()
+		.add_plugins((
+			(
+				music::MusicPlugin,
+				sprites::SpritesPlugin,
+				ground::GroundPlugin,
+				difficulty::DifficultyPlugin,
+				seed::SeedPlugin,
+				play_area::PlayAreaPlugin,
+				save::SavePlugin,
+				settings::SettingsPlugin,
+				touch::TouchPlugin,
+				tween::TweenPlugin,
+				stats::StatsPlugin,
+				coins::CoinsPlugin,
+				power_ups::PowerUpsPlugin,
+				lives::LivesPlugin,
+				zen::ZenPlugin,
+			),
+			(
+				menu::MenuPlugin,
+				get_ready::GetReadyPlugin,
+				pause::PausePlugin,
+				crash::CrashPlugin,
+				game_over::GameOverPlugin,
+				tutorial::TutorialPlugin,
+				daily::DailyPlugin,
+				replay::ReplayPlugin,
+				ghost::GhostPlugin,
+				particles::ParticlesPlugin,
+				score_popup::ScorePopupPlugin,
+				camera_shake::CameraShakePlugin,
+				medals::MedalsPlugin,
+				high_scores::HighScoresPlugin,
+				achievements::AchievementsPlugin,
+			),
+			(
+				hardcore::HardcorePlugin,
+				gravity_flip::GravityFlipPlugin,
+				mirror::MirrorPlugin,
+				moving_gaps::MovingGapsPlugin,
+				rotating_bars::RotatingBarsPlugin,
+				enemies::EnemiesPlugin,
+				wind::WindPlugin,
+				day_night::DayNightPlugin,
+				weather::WeatherPlugin,
+				night::NightPlugin,
+				biome::BiomePlugin,
+				stages::StagesPlugin,
+				layouts::LayoutsPlugin,
+				editor::EditorPlugin,
+				shop::ShopPlugin,
+			),
+			(
+				config::ConfigPlugin,
+				cli::CliPlugin,
+				collision::CollisionPlugin,
+				interpolation::InterpolationPlugin,
+				offscreen::OffscreenPlugin,
+				cleanup::CleanupPlugin,
+				loading::LoadingPlugin,
+				timed_transition::TimedTransitionPlugin,
+				splash::SplashPlugin,
+				credits::CreditsPlugin,
+				attract::AttractPlugin,
+				autopilot::AutopilotPlugin,
+				evolution::EvolutionPlugin,
+				leaderboard::LeaderboardPlugin,
+				race::RacePlugin,
+			),
+			(
+				sandbox::SandboxPlugin,
+				split_screen::SplitScreenPlugin,
+				versus::VersusPlugin,
+				rumble::RumblePlugin,
+				near_miss::NearMissPlugin,
+				combo::ComboPlugin,
+				time_attack::TimeAttackPlugin,
+				tas::TasPlugin,
+				death_flash::DeathFlashPlugin,
+				trail::TrailPlugin,
+				palette::PalettePlugin,
+				high_contrast::HighContrastPlugin,
+				locale::LocalePlugin,
+				theme::ThemePlugin,
+				skins::SkinsPlugin,
+			),
+			(profiles::ProfilesPlugin, cloud_sync::CloudSyncPlugin),
+		))
+		.add_systems(Startup, setup)
+		.add_observer(hit_obstacle)
+		.add_systems(OnEnter(InRun), on_enter_game)
+		.add_systems(
+			OnExit(GameStates::InGame),
+			send_run_ended.run_if(in_state(GameStates::GameOver)),
+		)
+		.add_systems(OnEnter(GameStates::GameOver), mark_game_over)
+		.add_systems(OnExit(GameStates::GameOver), on_game_restart)
+		.add_systems(OnEnter(GameStates::Menu), on_game_restart)
+		.configure_sets(
+			FixedUpdate,
+			(
+				GameplaySet::Input,
+				GameplaySet::Physics,
+				GameplaySet::Spawn,
+				GameplaySet::Collision,
+				GameplaySet::Outcome,
+				GameplaySet::Scoring,
+				GameplaySet::Progress,
+			)
+				.chain()
+				.run_if(
+					in_state(InRun)
+						.and(not(in_state(RunPhase::GetReady)))
+						.and(no_pending_transition),
+				),
+		)
+		.add_systems(
+			FixedUpdate,
+			(
+				apply_flap.in_set(GameplaySet::Input),
+				(apply_scrolling, apply_acceleration, apply_velocity)
+					.chain()
+					.in_set(GameplaySet::Physics),
+				(handle_pipe_spawn
+					.run_if(not(stages::in_stage).and(not(resource_equals(GameMode::Playtest)))),)
+					.in_set(GameplaySet::Spawn),
+				check_player_screen_bounds
+					.run_if(not(resource_equals(GameMode::Hardcore)))
+					.in_set(GameplaySet::Collision),
+				end_run_on_death.in_set(GameplaySet::Outcome),
+				(give_score_when_over_player, apply_scored, update_score)
+					.chain()
+					.in_set(GameplaySet::Scoring),
+				advance_run_tick.in_set(GameplaySet::Progress),
+			),
+		)
+		.add_systems(
+			Update,
+			(
+				(
+					expire_pending_flap,
+					handle_movement.run_if(
+						(in_state(Pause::Running).or(in_state(GameStates::GameOver)))
+							.and(not(resource_equals(GameMode::Autopilot)))
+							// Versus hands out flaps to each player instead.
+							.and(not(resource_equals(GameMode::Versus)))
+							.and(not(tas::scripted)),
+					),
+				)
+					.chain(),
+				tilt_player.run_if(in_state(InRun).and(motion_allowed)),
+				restart_on_r
+					.run_if(in_state(Crash::Over).and(not(resource_equals(GameMode::Race)))),
+				fit_background.run_if(resource_changed::<VisibleArea>),
+				update_score.run_if(resource_changed::<Locale>),
+			),
+		)
+		.init_state::<GameStates>()
+		.add_computed_state::<InRun>()
+		.add_sub_state::<MenuScreen>()
+		.add_sub_state::<Pause>()
+		.add_sub_state::<RunPhase>()
+		.add_sub_state::<Crash>()
+		.add_sub_state::<split_screen::SplitRound>();
+	app
+}
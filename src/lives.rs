@@ -0,0 +1,170 @@
+//! Lives mode, where the bird gets a few hearts to lose to pipes before the
+//! run is over.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit, pipe_pool::PipeSpawner, power_ups::Invulnerable, settings::Settings,
+	sprites::Textures, GameMode, GameplayEvent, GameplaySet, InRun, Obstacle, Pipe, Player,
+};
+
+const STARTING_LIVES: u32 = 3;
+/// Pipes closer to the bird than this along x are cleared when it loses a life.
+const CLEAR_DISTANCE: f32 = 200.0;
+/// How long the bird can fly through pipes after losing a life.
+pub const LIFE_LOST_GRACE: f32 = 2.0;
+/// Times per second an invulnerable bird blinks.
+const BLINK_RATE: f32 = 10.0;
+const HEART_SIZE: f32 = 40.0;
+/// Tint of the hearts already lost.
+const LOST_HEART_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.25);
+
+/// Hearts left in the current run. Runs outside of lives mode have none, so
+/// their first hit ends them.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct Lives(u32);
+
+impl Lives {
+	/// Takes away a heart, returning whether the run goes on.
+	pub fn lose_one(&mut self) -> bool {
+		self.0 = self.0.saturating_sub(1);
+		self.0 > 0
+	}
+}
+
+/// Hearts a run in the given mode starts with.
+pub fn starting_lives(mode: GameMode) -> u32 {
+	match mode {
+		GameMode::Lives => STARTING_LIVES,
+		GameMode::Endless
+		| GameMode::Daily
+		| GameMode::Zen
+		| GameMode::Hardcore
+		| GameMode::Stage(_)
+		| GameMode::Playtest
+		| GameMode::Autopilot
+		| GameMode::Race
+		| GameMode::Versus
+		| GameMode::TimeAttack(_) => 0,
+	}
+}
+
+#[derive(Component)]
+struct HeartsUi;
+
+/// The heart standing for the life at this index.
+#[derive(Component)]
+struct Heart(u32);
+
+fn reset_lives(mode: Res<GameMode>, mut lives: ResMut<Lives>) {
+	**lives = starting_lives(*mode);
+}
+
+fn spawn_hearts(mut commands: Commands, lives: Res<Lives>, textures: Res<Textures>) {
+	if **lives == 0 {
+		return;
+	}
+	commands
+		.spawn((
+			HeartsUi,
+			CleanupOnExit(InRun),
+			Node {
+				position_type: PositionType::Absolute,
+				width: Val::Percent(100.0),
+				top: Val::Px(48.0),
+				justify_content: JustifyContent::Center,
+				column_gap: Val::Px(8.0),
+				..default()
+			},
+		))
+		.with_children(|parent| {
+			for i in 0..**lives {
+				parent.spawn((
+					Heart(i),
+					ImageNode::new(textures.heart.clone()),
+					Node {
+						width: Val::Px(HEART_SIZE),
+						height: Val::Px(HEART_SIZE),
+						..default()
+					},
+				));
+			}
+		});
+}
+
+fn update_hearts(lives: Res<Lives>, hearts: Query<(&Heart, &mut ImageNode)>) {
+	for (heart, mut image) in hearts {
+		image.color = if heart.0 < **lives {
+			Color::WHITE
+		} else {
+			LOST_HEART_COLOR
+		};
+	}
+}
+
+/// Makes room for the bird to carry on after losing a life.
+fn clear_pipes_on_life_lost(
+	mut commands: Commands,
+	mut pipes: PipeSpawner,
+	mut events: EventReader<GameplayEvent>,
+	player: Single<&Transform, With<Player>>,
+	obstacles: Query<(Entity, &Transform, Has<Pipe>), With<Obstacle>>,
+) {
+	if !events
+		.read()
+		.any(|event| matches!(event, GameplayEvent::LifeLost))
+	{
+		return;
+	}
+	for (entity, transform, is_pipe) in obstacles {
+		if (transform.translation.x - player.translation.x).abs() >= CLEAR_DISTANCE {
+			continue;
+		}
+		if is_pipe {
+			pipes.recycle(entity);
+		} else {
+			commands.entity(entity).despawn();
+		}
+	}
+}
+
+/// Flickers the bird while pipes can't hurt it, or just fades it with reduced
+/// motion on.
+fn blink_invulnerable(
+	players: Query<(&mut Sprite, Has<Invulnerable>), With<Player>>,
+	settings: Res<Settings>,
+	time: Res<Time>,
+) {
+	for (mut sprite, invulnerable) in players {
+		let alpha = if !invulnerable {
+			1.0
+		} else if settings.reduced_motion {
+			0.5
+		} else if ((time.elapsed_secs() * BLINK_RATE) as u32).is_multiple_of(2) {
+			0.2
+		} else {
+			1.0
+		};
+		sprite.color.set_alpha(alpha);
+	}
+}
+
+pub struct LivesPlugin;
+
+impl Plugin for LivesPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<Lives>()
+			.add_systems(OnEnter(InRun), (reset_lives, spawn_hearts).chain())
+			.add_systems(
+				FixedUpdate,
+				clear_pipes_on_life_lost.in_set(GameplaySet::Outcome),
+			)
+			.add_systems(
+				Update,
+				(
+					update_hearts.run_if(resource_changed::<Lives>),
+					blink_invulnerable.run_if(in_state(InRun)),
+				),
+			);
+	}
+}
@@ -0,0 +1,99 @@
+//! The loading screen shown at startup, until every sprite, sound and data file
+//! the game uses is ready, so nothing pops in the first time it's needed. Text
+//! uses the font built into Bevy, which is always there.
+//!
+//! Whatever loads assets at startup hands their handles to [`Preload`].
+
+use bevy::prelude::*;
+
+use crate::{cleanup::CleanupOnExit, locale::Locale, theme::UiTheme, GameStates};
+
+const BAR_SIZE: Vec2 = Vec2::new(400.0, 16.0);
+const BAR_BACKGROUND: Color = Color::srgba(1.0, 1.0, 1.0, 0.2);
+
+/// Assets the loading screen waits for.
+#[derive(Resource, Default)]
+pub struct Preload(Vec<UntypedHandle>);
+
+impl Preload {
+	/// Meant for the `FromWorld` of resources holding handles, which may run
+	/// before the loading plugin is added.
+	pub fn add(world: &mut World, handles: impl IntoIterator<Item = UntypedHandle>) {
+		world.get_resource_or_init::<Preload>().0.extend(handles);
+	}
+}
+
+#[derive(Component)]
+struct ProgressBar;
+
+fn spawn_loading_screen(mut commands: Commands, locale: Res<Locale>, theme: Res<UiTheme>) {
+	commands
+		.spawn((
+			CleanupOnExit(GameStates::Loading),
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				row_gap: Val::Px(16.0),
+				..default()
+			},
+		))
+		.with_children(|parent| {
+			parent.spawn((Text::new(locale.text("loading")), theme.text(40.0)));
+			parent
+				.spawn((
+					Node {
+						width: Val::Px(BAR_SIZE.x),
+						height: Val::Px(BAR_SIZE.y),
+						..default()
+					},
+					BackgroundColor(BAR_BACKGROUND),
+				))
+				.with_child((
+					ProgressBar,
+					Node {
+						width: Val::Percent(0.0),
+						height: Val::Percent(100.0),
+						..default()
+					},
+					BackgroundColor(theme.text),
+				));
+		});
+}
+
+/// Assets that failed to load count as done, the game carries on without them
+/// like it did before there was a loading screen.
+fn track_progress(
+	preload: Res<Preload>,
+	asset_server: Res<AssetServer>,
+	mut bar: Single<&mut Node, With<ProgressBar>>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	let done = preload
+		.0
+		.iter()
+		.filter(|handle| {
+			asset_server.is_loaded_with_dependencies(handle.id())
+				|| asset_server
+					.get_load_state(handle.id())
+					.is_some_and(|state| state.is_failed())
+		})
+		.count();
+	let total = preload.0.len().max(1);
+	bar.width = Val::Percent(100.0 * done as f32 / total as f32);
+	if done == preload.0.len() {
+		next_state.set(GameStates::Splash);
+	}
+}
+
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<Preload>()
+			.add_systems(OnEnter(GameStates::Loading), spawn_loading_screen)
+			.add_systems(Update, track_progress.run_if(in_state(GameStates::Loading)));
+	}
+}
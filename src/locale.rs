@@ -0,0 +1,238 @@
+//! Translations of the interface, picked under Language in the settings. Each
+//! language is a file in `assets/locales`, named after its code, and
+//! `locales/languages.ftl` lists them with the name they go by in the picker,
+//! so adding one is a matter of adding its file and a line to the list.
+//!
+//! The files are written in the simple part of [Fluent](https://projectfluent.org):
+//! `key = text` messages, continued on indented lines, with `{ $name }` for
+//! what gets filled in and `#` for comments. Anything a translation leaves out
+//! is shown in English, which is built into the game.
+
+use bevy::{
+	asset::{io::Reader, AssetLoader, LoadContext},
+	prelude::*,
+};
+
+use crate::{loading::Preload, settings::Settings};
+
+const LANGUAGES_PATH: &str = "locales/languages.ftl";
+const ENGLISH: &str = include_str!("../assets/locales/en.ftl");
+
+/// Messages in the order they appear in the file.
+#[derive(Asset, TypePath, Debug, Default, Clone, PartialEq)]
+pub struct Translation(Vec<(String, String)>);
+
+impl Translation {
+	pub fn parse(source: &str) -> Self {
+		let mut messages: Vec<(String, String)> = Vec::new();
+		for line in source.lines() {
+			let trimmed = line.trim();
+			if trimmed.is_empty() || trimmed.starts_with('#') {
+				continue;
+			}
+			if line.starts_with(char::is_whitespace) {
+				if let Some((_, text)) = messages.last_mut() {
+					if !text.is_empty() {
+						text.push('\n');
+					}
+					text.push_str(trimmed);
+				}
+			} else if let Some((key, text)) = line.split_once('=') {
+				messages.push((key.trim().to_string(), text.trim().to_string()));
+			}
+		}
+		Translation(messages)
+	}
+
+	fn get(&self, key: &str) -> Option<&str> {
+		self.0
+			.iter()
+			.find(|(message, _)| message == key)
+			.map(|(_, text)| text.as_str())
+	}
+}
+
+#[derive(Default)]
+struct TranslationLoader;
+
+impl AssetLoader for TranslationLoader {
+	type Asset = Translation;
+	type Settings = ();
+	type Error = Box<dyn std::error::Error + Send + Sync>;
+
+	async fn load(
+		&self,
+		reader: &mut dyn Reader,
+		_settings: &(),
+		_load_context: &mut LoadContext<'_>,
+	) -> Result<Translation, Self::Error> {
+		let mut bytes = Vec::new();
+		reader.read_to_end(&mut bytes).await?;
+		Ok(Translation::parse(std::str::from_utf8(&bytes)?))
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["ftl"]
+	}
+}
+
+/// The interface text in the language picked, see [`Locale::text`].
+#[derive(Resource)]
+pub struct Locale {
+	english: Translation,
+	current: Translation,
+	/// Codes and names of the languages to pick from.
+	languages: Translation,
+}
+
+impl Default for Locale {
+	fn default() -> Self {
+		Locale {
+			english: Translation::parse(ENGLISH),
+			current: Translation::default(),
+			languages: Translation(vec![("en".into(), "English".into())]),
+		}
+	}
+}
+
+impl Locale {
+	/// The message `key`, with each `{ $name }` in it filled in from `args`.
+	/// Messages missing even in English come out as their key, to stand out.
+	pub fn format(&self, key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+		let message = self
+			.current
+			.get(key)
+			.or_else(|| self.english.get(key))
+			.unwrap_or(key);
+		let mut text = String::with_capacity(message.len());
+		let mut rest = message;
+		while let Some(start) = rest.find('{') {
+			let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+				break;
+			};
+			text.push_str(&rest[..start]);
+			let name = rest[start + 1..end].trim().trim_start_matches('$');
+			match args.iter().find(|(arg, _)| *arg == name) {
+				Some((_, value)) => text.push_str(&value.to_string()),
+				None => text.push_str(&rest[start..=end]),
+			}
+			rest = &rest[end + 1..];
+		}
+		text.push_str(rest);
+		text
+	}
+
+	pub fn text(&self, key: &str) -> String {
+		self.format(key, &[])
+	}
+
+	/// `"On"` or `"Off"`.
+	pub fn on_off(&self, on: bool) -> String {
+		self.text(if on { "on" } else { "off" })
+	}
+
+	/// Name of one of the options of a setting, by the message `<kind>-<name>`
+	/// with the name in lowercase, like `weather-rain`.
+	pub fn option(&self, kind: &str, name: &str) -> String {
+		self.text(&format!("{kind}-{}", name.to_lowercase()))
+	}
+
+	/// Codes of the languages to pick from, in the order of the list.
+	pub fn languages(&self) -> impl Iterator<Item = &str> {
+		self.languages.0.iter().map(|(code, _)| code.as_str())
+	}
+
+	/// Name of the language `code` in the list, or the code itself.
+	pub fn language_name<'a>(&'a self, code: &'a str) -> &'a str {
+		self.languages.get(code).unwrap_or(code)
+	}
+}
+
+#[derive(Resource)]
+struct LocaleFiles {
+	languages: Handle<Translation>,
+	/// Code of the language `current` is the file of.
+	code: String,
+	current: Handle<Translation>,
+}
+
+fn translation_path(code: &str) -> String {
+	format!("locales/{code}.ftl")
+}
+
+/// The language picked when the game starts is loaded with everything else,
+/// so the interface doesn't switch languages after showing up.
+impl FromWorld for LocaleFiles {
+	fn from_world(world: &mut World) -> Self {
+		let code = world
+			.get_resource::<Settings>()
+			.map_or_else(|| "en".to_string(), |settings| settings.language.clone());
+		let asset_server = world.resource::<AssetServer>();
+		let files = LocaleFiles {
+			languages: asset_server.load(LANGUAGES_PATH),
+			current: asset_server.load(translation_path(&code)),
+			code,
+		};
+		Preload::add(
+			world,
+			[
+				files.languages.clone().untyped(),
+				files.current.clone().untyped(),
+			],
+		);
+		files
+	}
+}
+
+fn switch_language(
+	settings: Res<Settings>,
+	asset_server: Res<AssetServer>,
+	mut files: ResMut<LocaleFiles>,
+) {
+	if files.code != settings.language {
+		files.code = settings.language.clone();
+		files.current = asset_server.load(translation_path(&settings.language));
+	}
+}
+
+/// English until the file of the language picked is loaded, which also
+/// covers languages without one.
+fn apply_translations(
+	files: Res<LocaleFiles>,
+	translations: Res<Assets<Translation>>,
+	mut locale: ResMut<Locale>,
+) {
+	let current = translations
+		.get(&files.current)
+		.cloned()
+		.unwrap_or_default();
+	if locale.current != current {
+		locale.current = current;
+	}
+	if let Some(languages) = translations.get(&files.languages) {
+		if locale.languages != *languages {
+			locale.languages = languages.clone();
+		}
+	}
+}
+
+pub struct LocalePlugin;
+
+impl Plugin for LocalePlugin {
+	fn build(&self, app: &mut App) {
+		app.init_asset::<Translation>()
+			.init_asset_loader::<TranslationLoader>()
+			.init_resource::<Locale>()
+			.init_resource::<LocaleFiles>()
+			.add_systems(
+				Update,
+				(
+					switch_language.run_if(resource_changed::<Settings>),
+					apply_translations.run_if(
+						resource_changed::<LocaleFiles>.or(on_event::<AssetEvent<Translation>>),
+					),
+				)
+					.chain(),
+			);
+	}
+}
@@ -0,0 +1,90 @@
+//! Medals awarded for the final score of a run.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{save::SaveData, GameMode, GameScore, GameStates};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Medal {
+	Bronze,
+	Silver,
+	Gold,
+	Platinum,
+}
+
+impl Medal {
+	/// From worst to best.
+	const ALL: [Medal; 4] = [Medal::Bronze, Medal::Silver, Medal::Gold, Medal::Platinum];
+
+	/// Lowest score that earns the medal.
+	fn threshold(self) -> i64 {
+		match self {
+			Medal::Bronze => 10,
+			Medal::Silver => 20,
+			Medal::Gold => 30,
+			Medal::Platinum => 40,
+		}
+	}
+
+	/// The best medal a score earns, if any.
+	pub fn for_score(score: i64) -> Option<Medal> {
+		Medal::ALL
+			.into_iter()
+			.rev()
+			.find(|medal| score >= medal.threshold())
+	}
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Medal::Bronze => "Bronze",
+			Medal::Silver => "Silver",
+			Medal::Gold => "Gold",
+			Medal::Platinum => "Platinum",
+		}
+	}
+
+	fn color(self) -> Color {
+		match self {
+			Medal::Bronze => Color::srgb(0.8, 0.5, 0.2),
+			Medal::Silver => Color::srgb(0.75, 0.75, 0.8),
+			Medal::Gold => Color::srgb(1.0, 0.8, 0.2),
+			Medal::Platinum => Color::srgb(0.85, 0.95, 1.0),
+		}
+	}
+
+	/// A round badge in the medal's color, for showing in the interface.
+	pub fn badge(self, size: f32) -> impl Bundle {
+		(
+			Node {
+				width: Val::Px(size),
+				height: Val::Px(size),
+				border: UiRect::all(Val::Px(size / 8.0)),
+				..default()
+			},
+			BackgroundColor(self.color()),
+			BorderColor(self.color().darker(0.25)),
+			BorderRadius::MAX,
+		)
+	}
+}
+
+/// Like the best score, only live endless runs count.
+fn record_best_medal(score: Res<GameScore>, mut save: ResMut<SaveData>) {
+	let medal = Medal::for_score(**score);
+	if medal > save.best_medal {
+		save.best_medal = medal;
+	}
+}
+
+pub struct MedalsPlugin;
+
+impl Plugin for MedalsPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			OnExit(GameStates::InGame),
+			record_best_medal
+				.run_if(resource_equals(GameMode::Endless).and(crate::counts_for_progress)),
+		);
+	}
+}
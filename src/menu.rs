@@ -0,0 +1,357 @@
+use bevy::prelude::*;
+
+use crate::{
+	crash::Crash,
+	difficulty::DifficultyPreset,
+	input::{key_name, InputAction, PlayerInput},
+	locale::Locale,
+	save::SaveData,
+	settings::Settings,
+	stages,
+	theme::UiTheme,
+	time_attack::{SelectedTarget, TARGETS},
+	GameMode, GameStates,
+};
+
+const PRESET_KEYS: [KeyCode; 3] = [KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3];
+
+/// The screens reachable from the main menu.
+#[derive(SubStates, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[source(GameStates = GameStates::Menu)]
+pub(crate) enum MenuScreen {
+	#[default]
+	Main,
+	Settings,
+	HighScores,
+	Stats,
+	Credits,
+	Leaderboard,
+	Skins,
+	Shop,
+	Profiles,
+	/// Waiting for a multiplayer race, see [`race`](crate::race).
+	Lobby,
+}
+
+#[derive(Component)]
+struct MenuUi;
+
+#[derive(Component)]
+struct DifficultyLabel;
+
+#[derive(Component)]
+struct MutatorsLabel;
+
+#[derive(Component)]
+struct TimeAttackLabel;
+
+pub fn preset_name(preset: DifficultyPreset, locale: &Locale) -> String {
+	locale.option("difficulty", preset.name())
+}
+
+fn difficulty_label(preset: DifficultyPreset, locale: &Locale) -> String {
+	locale.format(
+		"menu-difficulty",
+		&[("difficulty", &preset_name(preset, locale))],
+	)
+}
+
+fn spawn_menu(
+	mut commands: Commands,
+	settings: Res<Settings>,
+	save: Res<SaveData>,
+	target: Res<SelectedTarget>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+	mut mode: ResMut<GameMode>,
+) {
+	*mode = GameMode::Endless;
+	let presets = DifficultyPreset::ALL
+		.iter()
+		.enumerate()
+		.map(|(i, preset)| {
+			locale.format(
+				"menu-preset",
+				&[
+					("key", &(i + 1)),
+					("difficulty", &preset_name(*preset, &locale)),
+				],
+			)
+		})
+		.collect::<Vec<_>>()
+		.join("   ");
+	commands
+		.spawn((
+			MenuUi,
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				row_gap: Val::Px(16.0),
+				..default()
+			},
+			BackgroundColor(theme.screen),
+		))
+		.with_children(|parent| {
+			parent.spawn((Text::new("Flappy"), theme.text(96.0)));
+			parent.spawn((
+				DifficultyLabel,
+				Text::new(difficulty_label(settings.difficulty, &locale)),
+				theme.text(40.0),
+			));
+			parent.spawn((Text::new(presets), theme.text(28.0)));
+			parent.spawn((Text::new(save.daily.menu_entry(&locale)), theme.text(28.0)));
+			parent.spawn((
+				Text::new(locale.format("menu-modes", &[("best", &save.hardcore_best)])),
+				theme.text(28.0),
+			));
+			parent.spawn((
+				Text::new(locale.format(
+					"menu-more-modes",
+					&[
+						("stars", &stages::total_stars(&save)),
+						("max", &stages::max_stars()),
+					],
+				)),
+				theme.text(28.0),
+			));
+			parent.spawn((
+				TimeAttackLabel,
+				Text::new(save.time_attack.menu_entry(**target, &locale)),
+				theme.text(28.0),
+			));
+			parent.spawn((
+				MutatorsLabel,
+				Text::new(settings.mutators.describe(&locale)),
+				theme.text(28.0),
+			));
+			parent.spawn((
+				Text::new(locale.format(
+					"menu-controls",
+					&[("key", &key_name(settings.controls.key(InputAction::Flap)))],
+				)),
+				theme.text(28.0),
+			));
+		});
+}
+
+fn despawn_menu(mut commands: Commands, menu: Single<Entity, With<MenuUi>>) {
+	commands.entity(*menu).despawn();
+}
+
+fn select_difficulty(keyboard_input: Res<ButtonInput<KeyCode>>, mut settings: ResMut<Settings>) {
+	for (key, preset) in PRESET_KEYS.iter().zip(DifficultyPreset::ALL) {
+		if keyboard_input.just_pressed(*key) && settings.difficulty != preset {
+			settings.difficulty = preset;
+		}
+	}
+}
+
+fn update_difficulty_label(
+	settings: Res<Settings>,
+	locale: Res<Locale>,
+	mut label: Single<&mut Text, With<DifficultyLabel>>,
+) {
+	label.0 = difficulty_label(settings.difficulty, &locale);
+}
+
+fn toggle_mutators(keyboard_input: Res<ButtonInput<KeyCode>>, mut settings: ResMut<Settings>) {
+	if keyboard_input.just_pressed(KeyCode::KeyG) {
+		settings.mutators.gravity_flip = !settings.mutators.gravity_flip;
+	}
+	if keyboard_input.just_pressed(KeyCode::KeyM) {
+		settings.mutators.mirror = !settings.mutators.mirror;
+	}
+	if keyboard_input.just_pressed(KeyCode::KeyN) {
+		settings.mutators.night = !settings.mutators.night;
+	}
+}
+
+fn update_mutators_label(
+	settings: Res<Settings>,
+	locale: Res<Locale>,
+	mut label: Single<&mut Text, With<MutatorsLabel>>,
+) {
+	label.0 = settings.mutators.describe(&locale);
+}
+
+fn select_time_attack_target(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut target: ResMut<SelectedTarget>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyU) {
+		**target = (**target + 1) % TARGETS.len() as u8;
+	}
+}
+
+fn update_time_attack_label(
+	save: Res<SaveData>,
+	target: Res<SelectedTarget>,
+	locale: Res<Locale>,
+	mut label: Single<&mut Text, With<TimeAttackLabel>>,
+) {
+	label.0 = save.time_attack.menu_entry(**target, &locale);
+}
+
+fn start_on_space(input: PlayerInput, mut next_state: ResMut<NextState<GameStates>>) {
+	if input.start_pressed() {
+		next_state.set(GameStates::InGame);
+	}
+}
+
+fn start_daily_on_d(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut mode: ResMut<GameMode>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyD) {
+		*mode = GameMode::Daily;
+		next_state.set(GameStates::InGame);
+	}
+}
+
+fn start_lives_on_l(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut mode: ResMut<GameMode>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyL) {
+		*mode = GameMode::Lives;
+		next_state.set(GameStates::InGame);
+	}
+}
+
+fn start_zen_on_z(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut mode: ResMut<GameMode>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyZ) {
+		*mode = GameMode::Zen;
+		next_state.set(GameStates::InGame);
+	}
+}
+
+fn start_hardcore_on_x(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut mode: ResMut<GameMode>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyX) {
+		*mode = GameMode::Hardcore;
+		next_state.set(GameStates::InGame);
+	}
+}
+
+/// Picks up the campaign where it was left.
+fn start_stage_on_c(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	save: Res<SaveData>,
+	mut mode: ResMut<GameMode>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyC) {
+		*mode = GameMode::Stage(stages::next_stage(&save));
+		next_state.set(GameStates::InGame);
+	}
+}
+
+fn start_time_attack_on_i(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	target: Res<SelectedTarget>,
+	mut mode: ResMut<GameMode>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyI) {
+		*mode = GameMode::TimeAttack(**target);
+		next_state.set(GameStates::InGame);
+	}
+}
+
+fn start_autopilot_on_a(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut mode: ResMut<GameMode>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyA) {
+		*mode = GameMode::Autopilot;
+		next_state.set(GameStates::InGame);
+	}
+}
+
+fn start_versus_on_b(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut mode: ResMut<GameMode>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyB) {
+		*mode = GameMode::Versus;
+		next_state.set(GameStates::InGame);
+	}
+}
+
+fn open_editor_on_e(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyE) {
+		next_state.set(GameStates::Editor);
+	}
+}
+
+fn open_settings_on_s(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyS) {
+		next_screen.set(MenuScreen::Settings);
+	}
+}
+
+fn open_menu_on_escape(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::Escape) {
+		next_state.set(GameStates::Menu);
+	}
+}
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(OnEnter(MenuScreen::Main), spawn_menu)
+			.add_systems(OnExit(MenuScreen::Main), despawn_menu)
+			.add_systems(
+				Update,
+				(
+					(
+						select_difficulty,
+						update_difficulty_label.run_if(resource_changed::<Settings>),
+						toggle_mutators,
+						update_mutators_label.run_if(resource_changed::<Settings>),
+						select_time_attack_target,
+						update_time_attack_label.run_if(resource_changed::<SelectedTarget>),
+						start_on_space,
+						start_daily_on_d,
+						start_lives_on_l,
+						start_zen_on_z,
+						start_hardcore_on_x,
+						start_stage_on_c,
+						start_time_attack_on_i,
+						start_autopilot_on_a,
+						start_versus_on_b,
+						open_editor_on_e,
+						open_settings_on_s,
+					)
+						.chain()
+						.run_if(in_state(MenuScreen::Main)),
+					open_menu_on_escape
+						.run_if(in_state(Crash::Over).or(in_state(GameStates::Replay))),
+				),
+			);
+	}
+}
@@ -0,0 +1,75 @@
+//! The mirror mutator, where the world scrolls the other way and the bird flies
+//! to the left.
+
+use bevy::prelude::*;
+
+use crate::{mutators::Mutators, on_enter_game, play_area::PlayArea, InRun, PLAYER_START};
+
+/// Which way the world scrolls past the bird.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScrollDirection {
+	/// Things come in on the right, the usual way.
+	#[default]
+	Left,
+	/// Things come in on the left.
+	Right,
+}
+
+impl ScrollDirection {
+	/// Sign of the horizontal velocity of everything scrolling.
+	pub fn sign(self) -> f32 {
+		match self {
+			ScrollDirection::Left => -1.0,
+			ScrollDirection::Right => 1.0,
+		}
+	}
+
+	/// Where new things scroll into the play area.
+	pub fn entry_x(self, play_area: &PlayArea) -> f32 {
+		match self {
+			ScrollDirection::Left => play_area.max.x,
+			ScrollDirection::Right => play_area.min.x,
+		}
+	}
+
+	/// Whether something at `x` has scrolled more than `margin` past the far edge
+	/// of `area`.
+	pub fn has_left(self, area: &Rect, x: f32, margin: f32) -> bool {
+		match self {
+			ScrollDirection::Left => x < area.min.x - margin,
+			ScrollDirection::Right => x > area.max.x + margin,
+		}
+	}
+
+	/// How far `x` has scrolled past `from`, negative while still ahead of it.
+	pub fn distance_past(self, x: f32, from: f32) -> f32 {
+		self.sign() * (x - from)
+	}
+
+	/// The bird starts on the side things scroll towards.
+	pub fn player_start(self) -> Vec3 {
+		PLAYER_START.with_x(self.sign() * PLAYER_START.x.abs())
+	}
+
+	/// Whether sprites facing right should be flipped.
+	pub fn flip_x(self) -> bool {
+		self == ScrollDirection::Right
+	}
+}
+
+fn apply_scroll_direction(mutators: Res<Mutators>, mut direction: ResMut<ScrollDirection>) {
+	direction.set_if_neq(if mutators.mirror {
+		ScrollDirection::Right
+	} else {
+		ScrollDirection::Left
+	});
+}
+
+pub struct MirrorPlugin;
+
+impl Plugin for MirrorPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<ScrollDirection>()
+			.add_systems(OnEnter(InRun), apply_scroll_direction.before(on_enter_game));
+	}
+}
@@ -0,0 +1,121 @@
+//! Pipe pairs whose gap bobs up and down, showing up more often as the score
+//! goes up.
+
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+	ground::ground_top, handle_pipe_spawn, play_area::PlayArea, seed::GameRng, GameScore,
+	GameplaySet, Obstacle, Pipe, Velocity,
+};
+
+/// Score from which moving gaps can show up.
+const MOVING_GAP_START_SCORE: i64 = 15;
+const MOVING_GAP_CHANCE_PER_POINT: f64 = 0.02;
+const MAX_MOVING_GAP_CHANCE: f64 = 0.5;
+/// How far the gap moves up and down from where it spawned.
+const MAX_AMPLITUDE: f32 = 80.0;
+/// Full swings up and down per second.
+const FREQUENCY: f32 = 0.5;
+
+/// Moves something up and down along a sine wave around where it spawned. Both
+/// pipes of a pair and their coin get the same one, so they move in sync.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Oscillator {
+	amplitude: f32,
+	/// Full swings up and down per second.
+	frequency: f32,
+	/// Seconds since spawning, in game time.
+	elapsed: f32,
+}
+
+impl Oscillator {
+	pub fn new(amplitude: f32, frequency: f32) -> Self {
+		Oscillator {
+			amplitude,
+			frequency,
+			elapsed: 0.0,
+		}
+	}
+
+	fn offset(&self, elapsed: f32) -> f32 {
+		self.amplitude * (elapsed * self.frequency * TAU).sin()
+	}
+}
+
+fn moving_gap_chance(score: i64) -> f64 {
+	let progress = (score - MOVING_GAP_START_SCORE).max(0) as f64;
+	(progress * MOVING_GAP_CHANCE_PER_POINT).min(MAX_MOVING_GAP_CHANCE)
+}
+
+/// Picks whether the newest pair of pipes moves. Nothing is drawn from the
+/// game's randomness before the first moving gap can show up, so the start of a
+/// run plays out the same as it did before they existed.
+pub fn make_gaps_move(
+	mut commands: Commands,
+	score: Res<GameScore>,
+	play_area: Res<PlayArea>,
+	mut game_rng: ResMut<GameRng>,
+	pipes: Query<(Entity, &Transform, &Sprite, &Pipe), Added<Pipe>>,
+	obstacles: Query<(), Added<Obstacle>>,
+) {
+	// Only plain pairs move, the other patterns are hard enough as they are.
+	if obstacles.iter().count() != 2 {
+		return;
+	}
+	let chance = moving_gap_chance(**score);
+	let Some((_, transform, sprite, pipe)) = pipes.iter().find(|(.., pipe)| pipe.give_score) else {
+		return;
+	};
+	if chance <= 0.0 || !game_rng.random_bool(chance) {
+		return;
+	}
+	let height = sprite.custom_size.unwrap_or_default().y;
+	let gap_top = transform.translation.y - height / 2.0;
+	let gap_bottom = gap_top - pipe.gap;
+	// Keep the whole gap inside the play area.
+	let amplitude = MAX_AMPLITUDE
+		.min(play_area.max.y - gap_top)
+		.min(gap_bottom - ground_top(&play_area))
+		.max(0.0);
+	for (entity, ..) in &pipes {
+		commands
+			.entity(entity)
+			.insert(Oscillator::new(amplitude, FREQUENCY));
+	}
+}
+
+/// Sets the vertical velocity that lands exactly on the next point of the wave,
+/// so the gap doesn't drift away over time.
+fn oscillate(query: Query<(&mut Oscillator, &mut Velocity)>, time: Res<Time>) {
+	let elapsed = time.delta_secs();
+	if elapsed <= 0.0 {
+		return;
+	}
+	for (mut oscillator, mut velocity) in query {
+		let next = oscillator.elapsed + elapsed;
+		velocity.y = (oscillator.offset(next) - oscillator.offset(oscillator.elapsed)) / elapsed;
+		oscillator.elapsed = next;
+	}
+}
+
+pub struct MovingGapsPlugin;
+
+impl Plugin for MovingGapsPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			FixedUpdate,
+			(
+				oscillate
+					.after(crate::apply_scrolling)
+					.before(crate::apply_velocity)
+					.in_set(GameplaySet::Physics),
+				make_gaps_move
+					.after(handle_pipe_spawn)
+					.in_set(GameplaySet::Spawn),
+			),
+		);
+	}
+}
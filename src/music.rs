@@ -0,0 +1,135 @@
+use bevy::{audio::Volume, prelude::*};
+
+use crate::{
+	biome::{Biome, BiomeAssets},
+	cli::LaunchOptions,
+	loading::Preload,
+	settings::Settings,
+	GameStates, InRun,
+};
+
+const CROSSFADE_DURATION: f32 = 1.0;
+
+#[derive(Resource)]
+struct MusicTracks {
+	menu: Handle<AudioSource>,
+	game_over: Handle<AudioSource>,
+}
+
+impl FromWorld for MusicTracks {
+	fn from_world(world: &mut World) -> Self {
+		let asset_server = world.resource::<AssetServer>();
+		let tracks = MusicTracks {
+			menu: asset_server.load("music/menu.wav"),
+			game_over: asset_server.load("music/game_over.wav"),
+		};
+		Preload::add(
+			world,
+			[
+				tracks.menu.clone().untyped(),
+				tracks.game_over.clone().untyped(),
+			],
+		);
+		tracks
+	}
+}
+
+/// A looping music entity, fading its volume towards `target`. Both are
+/// fractions of the music volume picked in the settings.
+/// Entities fading out to silence are despawned once they get there.
+#[derive(Component)]
+struct MusicFade {
+	volume: f32,
+	target: f32,
+}
+
+/// Leaves a track that is already fading in alone, rather than starting it
+/// over.
+fn crossfade(
+	commands: &mut Commands,
+	track: &Handle<AudioSource>,
+	playing: &mut Query<(&mut MusicFade, &AudioPlayer)>,
+) {
+	if playing
+		.iter()
+		.any(|(fade, player)| fade.target == 1.0 && player.0 == *track)
+	{
+		return;
+	}
+	for (mut fade, _) in playing {
+		fade.target = 0.0;
+	}
+	commands.spawn((
+		AudioPlayer::new(track.clone()),
+		PlaybackSettings::LOOP.with_volume(Volume::Linear(0.0)),
+		MusicFade {
+			volume: 0.0,
+			target: 1.0,
+		},
+	));
+}
+
+fn crossfade_to(
+	track: fn(&MusicTracks) -> &Handle<AudioSource>,
+) -> impl Fn(Commands, Res<MusicTracks>, Query<(&mut MusicFade, &AudioPlayer)>) {
+	move |mut commands, tracks, mut playing| crossfade(&mut commands, track(&tracks), &mut playing)
+}
+
+/// Runs play the music of the biome they are in.
+fn crossfade_to_biome(
+	mut commands: Commands,
+	biome: Res<Biome>,
+	assets: Res<BiomeAssets>,
+	mut playing: Query<(&mut MusicFade, &AudioPlayer)>,
+) {
+	crossfade(&mut commands, &assets.get(*biome).music, &mut playing);
+}
+
+fn fade_music(
+	mut commands: Commands,
+	mut query: Query<(Entity, &mut MusicFade, Option<&mut AudioSink>)>,
+	settings: Res<Settings>,
+	options: Res<LaunchOptions>,
+	time: Res<Time>,
+) {
+	let level = if options.mute {
+		0.0
+	} else {
+		settings.music_level()
+	};
+	let step = time.delta_secs() / CROSSFADE_DURATION;
+	for (entity, mut fade, sink) in &mut query {
+		if fade.volume < fade.target {
+			fade.volume = (fade.volume + step).min(fade.target);
+		} else {
+			fade.volume = (fade.volume - step).max(fade.target);
+		}
+		if fade.target == 0.0 && fade.volume == 0.0 {
+			commands.entity(entity).despawn();
+		} else if let Some(mut sink) = sink {
+			sink.set_volume(Volume::Linear(fade.volume * level));
+		}
+	}
+}
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<MusicTracks>()
+			.add_systems(OnEnter(GameStates::Menu), crossfade_to(|t| &t.menu))
+			.add_systems(OnEnter(GameStates::Editor), crossfade_to(|t| &t.menu))
+			.add_systems(OnEnter(InRun), crossfade_to_biome)
+			.add_systems(
+				OnEnter(GameStates::GameOver),
+				crossfade_to(|t| &t.game_over),
+			)
+			.add_systems(
+				Update,
+				(
+					fade_music,
+					crossfade_to_biome.run_if(in_state(InRun).and(resource_changed::<Biome>)),
+				),
+			);
+	}
+}
@@ -0,0 +1,42 @@
+//! Optional rules that can be turned on for any run except the daily
+//! challenge, which everyone plays the same way.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::locale::Locale;
+
+/// The mutators active for the current run. The preferred ones are kept in
+/// [`Settings`](crate::settings::Settings), replays bring their own.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct Mutators {
+	/// Gravity turns around every so often, see [`gravity_flip`](crate::gravity_flip).
+	pub gravity_flip: bool,
+	/// The world scrolls the other way, see [`mirror`](crate::mirror).
+	pub mirror: bool,
+	/// Only a small circle around the bird is lit, see [`night`](crate::night).
+	pub night: bool,
+}
+
+impl Mutators {
+	/// Short list of the active mutators for the menu.
+	pub fn describe(&self, locale: &Locale) -> String {
+		locale.format(
+			"menu-mutators",
+			&[
+				("gravity_flip", &locale.on_off(self.gravity_flip)),
+				("mirror", &locale.on_off(self.mirror)),
+				("night", &locale.on_off(self.night)),
+			],
+		)
+	}
+}
+
+pub fn gravity_flip_enabled(mutators: Res<Mutators>) -> bool {
+	mutators.gravity_flip
+}
+
+pub fn night_enabled(mutators: Res<Mutators>) -> bool {
+	mutators.night
+}
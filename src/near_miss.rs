@@ -0,0 +1,136 @@
+//! Close calls: making it through a gap within a few pixels of its edges is
+//! worth a bonus point, with a chime and a flash of the screen.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit, collision::sprite_collider, loading::Preload, mirror::ScrollDirection,
+	play_area::VisibleArea, settings::Settings, GameplayEvent, GameplaySet, InRun, Pipe, Player,
+	ScoredEvent, PIPE_WIDTH, PLAYER_SIZE,
+};
+
+/// Room to spare between the bird and the edge of a gap that still counts as
+/// a close call.
+const NEAR_MISS_DISTANCE: f32 = 6.0;
+const FLASH_ALPHA: f32 = 0.25;
+const FLASH_DURATION: f32 = 0.2;
+
+#[derive(Resource)]
+struct CloseCallSound(Handle<AudioSource>);
+
+impl FromWorld for CloseCallSound {
+	fn from_world(world: &mut World) -> Self {
+		let handle = world
+			.resource::<AssetServer>()
+			.load("sounds/close_call.wav");
+		Preload::add(world, [handle.clone().untyped()]);
+		CloseCallSound(handle)
+	}
+}
+
+/// Fades out over the play area, starting at [`FLASH_ALPHA`].
+#[derive(Component)]
+struct Flash(Timer);
+
+/// Keeps track of how close the birds come to the edges of each gap while
+/// flying through it, and hands out the bonus as the gap is passed. Birds
+/// flying through pipes, like in zen mode, never get it.
+pub fn track_close_calls(
+	players: Query<(&Transform, &Sprite), With<Player>>,
+	pipes: Query<(&Transform, &Sprite, &mut Pipe), Without<Player>>,
+	direction: Res<ScrollDirection>,
+	mut scored: EventWriter<ScoredEvent>,
+	mut events: EventWriter<GameplayEvent>,
+) {
+	let Some(player_x) = players
+		.iter()
+		.next()
+		.map(|(player, _)| player.translation.x)
+	else {
+		return;
+	};
+	for (transform, sprite, mut pipe) in pipes {
+		if !pipe.give_score {
+			continue;
+		}
+		let gap_top = transform.translation.y - sprite.custom_size.unwrap_or_default().y / 2.0;
+		let gap_bottom = gap_top - pipe.gap;
+		for (player_transform, player_sprite) in &players {
+			let distance = (transform.translation.x - player_transform.translation.x).abs();
+			if distance >= (PIPE_WIDTH + PLAYER_SIZE.x) / 2.0 {
+				continue;
+			}
+			let bird = sprite_collider(player_transform, player_sprite);
+			let clearance = (gap_top - bird.max.y).min(bird.min.y - gap_bottom);
+			pipe.closest = pipe.closest.min(clearance);
+		}
+		if crate::has_passed(*direction, transform.translation.x, player_x)
+			&& (0.0..NEAR_MISS_DISTANCE).contains(&pipe.closest)
+		{
+			scored.write(ScoredEvent { points: 1 });
+			events.write(GameplayEvent::CloseCall);
+		}
+	}
+}
+
+fn celebrate_close_calls(
+	mut commands: Commands,
+	mut events: EventReader<GameplayEvent>,
+	sound: Res<CloseCallSound>,
+	visible_area: Res<VisibleArea>,
+	settings: Res<Settings>,
+) {
+	for event in events.read() {
+		if !matches!(event, GameplayEvent::CloseCall) {
+			continue;
+		}
+		commands.spawn((AudioPlayer::new(sound.0.clone()), PlaybackSettings::DESPAWN));
+		if settings.reduced_motion || settings.reduce_flashing {
+			continue;
+		}
+		commands.spawn((
+			Flash(Timer::from_seconds(FLASH_DURATION, TimerMode::Once)),
+			CleanupOnExit(InRun),
+			Sprite {
+				color: Color::WHITE.with_alpha(FLASH_ALPHA),
+				custom_size: Some(visible_area.size()),
+				..default()
+			},
+			Transform::from_translation(visible_area.center().extend(50.0)),
+		));
+	}
+}
+
+fn fade_flash(
+	mut commands: Commands,
+	flashes: Query<(Entity, &mut Flash, &mut Sprite)>,
+	time: Res<Time>,
+) {
+	for (entity, mut flash, mut sprite) in flashes {
+		if flash.0.tick(time.delta()).finished() {
+			commands.entity(entity).despawn();
+		} else {
+			sprite
+				.color
+				.set_alpha(FLASH_ALPHA * flash.0.fraction_remaining());
+		}
+	}
+}
+
+pub struct NearMissPlugin;
+
+impl Plugin for NearMissPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<CloseCallSound>()
+			.add_systems(
+				FixedUpdate,
+				track_close_calls
+					.in_set(GameplaySet::Scoring)
+					.before(crate::give_score_when_over_player),
+			)
+			.add_systems(
+				Update,
+				(celebrate_close_calls, fade_flash).run_if(in_state(InRun)),
+			);
+	}
+}
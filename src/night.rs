@@ -0,0 +1,98 @@
+//! The night mutator, where everything is dark except for a small circle of
+//! light around the bird, so pipes only show up shortly before they arrive.
+
+use bevy::{
+	asset::RenderAssetUsages,
+	image::ImageSampler,
+	prelude::*,
+	render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use crate::{cleanup::CleanupOnExit, mutators::night_enabled, InRun, Player};
+
+/// Size of the darkness around the light, big enough to cover any window from
+/// wherever the bird is.
+const DARKNESS_SIZE: f32 = 4096.0;
+const LIGHT_RADIUS: f32 = 180.0;
+/// Where the light starts fading out, relative to its radius.
+const LIGHT_FALLOFF_START: f32 = 0.55;
+const DARKNESS_ALPHA: f32 = 0.96;
+const TEXTURE_SIZE: u32 = 512;
+
+#[derive(Component)]
+struct Darkness;
+
+#[derive(Resource)]
+struct DarknessImage(Handle<Image>);
+
+impl FromWorld for DarknessImage {
+	fn from_world(world: &mut World) -> Self {
+		DarknessImage(world.resource_mut::<Assets<Image>>().add(darkness_image()))
+	}
+}
+
+/// A dark square with a soft round hole in the middle.
+fn darkness_image() -> Image {
+	let center = TEXTURE_SIZE as f32 / 2.0;
+	let radius = LIGHT_RADIUS / DARKNESS_SIZE * TEXTURE_SIZE as f32;
+	let mut data = Vec::with_capacity((TEXTURE_SIZE * TEXTURE_SIZE * 4) as usize);
+	for y in 0..TEXTURE_SIZE {
+		for x in 0..TEXTURE_SIZE {
+			let distance = Vec2::new(x as f32 + 0.5, y as f32 + 0.5).distance(Vec2::splat(center));
+			let t = ((distance / radius - LIGHT_FALLOFF_START) / (1.0 - LIGHT_FALLOFF_START))
+				.clamp(0.0, 1.0);
+			let alpha = t * t * (3.0 - 2.0 * t) * DARKNESS_ALPHA;
+			data.extend_from_slice(&[0, 0, 0, (alpha * 255.0) as u8]);
+		}
+	}
+	let mut image = Image::new(
+		Extent3d {
+			width: TEXTURE_SIZE,
+			height: TEXTURE_SIZE,
+			depth_or_array_layers: 1,
+		},
+		TextureDimension::D2,
+		data,
+		TextureFormat::Rgba8UnormSrgb,
+		RenderAssetUsages::RENDER_WORLD,
+	);
+	// Stretched a lot, the rest of the game's pixel look would make the edge
+	// of the light blocky.
+	image.sampler = ImageSampler::linear();
+	image
+}
+
+fn spawn_darkness(mut commands: Commands, image: Res<DarknessImage>) {
+	commands.spawn((
+		Darkness,
+		CleanupOnExit(InRun),
+		Sprite {
+			image: image.0.clone(),
+			custom_size: Some(Vec2::splat(DARKNESS_SIZE)),
+			..default()
+		},
+		Transform::from_xyz(0.0, 0.0, 20.0),
+	));
+}
+
+fn follow_player(
+	player: Single<&Transform, (With<Player>, Without<Darkness>)>,
+	mut darkness: Single<&mut Transform, With<Darkness>>,
+) {
+	darkness.translation = player.translation.truncate().extend(darkness.translation.z);
+}
+
+pub struct NightPlugin;
+
+impl Plugin for NightPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<DarknessImage>()
+			.add_systems(OnEnter(InRun), spawn_darkness.run_if(night_enabled))
+			.add_systems(
+				PostUpdate,
+				follow_player
+					.before(TransformSystem::TransformPropagate)
+					.run_if(in_state(InRun)),
+			);
+	}
+}
@@ -0,0 +1,52 @@
+//! Cleanup for everything that scrolls or flies off past the far edge of what
+//! can be seen.
+//!
+//! Only things already out of play are removed, so this runs every frame
+//! rather than as part of the simulation, and catches what's left moving
+//! after a crash too.
+
+use bevy::prelude::*;
+
+use crate::{mirror::ScrollDirection, pipe_pool::PipeSpawner, play_area::VisibleArea, Pipe};
+
+/// Despawned once it's more than `margin` past the edge of the visible area
+/// that things scroll towards, which should be enough for all of it to be out
+/// of sight. Pipes go back to the [`PipePool`](crate::pipe_pool::PipePool)
+/// instead.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DespawnOffscreen {
+	pub margin: f32,
+}
+
+impl DespawnOffscreen {
+	pub const fn new(margin: f32) -> Self {
+		DespawnOffscreen { margin }
+	}
+}
+
+fn despawn_offscreen(
+	mut commands: Commands,
+	mut pipes: PipeSpawner,
+	visible_area: Res<VisibleArea>,
+	direction: Res<ScrollDirection>,
+	query: Query<(Entity, &Transform, &DespawnOffscreen, Has<Pipe>)>,
+) {
+	for (entity, transform, offscreen, is_pipe) in query {
+		if !direction.has_left(&visible_area, transform.translation.x, offscreen.margin) {
+			continue;
+		}
+		if is_pipe {
+			pipes.recycle(entity);
+		} else {
+			commands.entity(entity).despawn();
+		}
+	}
+}
+
+pub struct OffscreenPlugin;
+
+impl Plugin for OffscreenPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(Update, despawn_offscreen);
+	}
+}
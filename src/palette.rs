@@ -0,0 +1,207 @@
+//! Color palettes for color blind players, picked under Colors in the
+//! settings. Each one turns the blue of the bird and the green of the pipes to
+//! colors that are easier to tell apart with that kind of color blindness,
+//! along with the colors the interface uses for good and bad news.
+//!
+//! The textures themselves are recolored, so everything drawn with them
+//! changes at once, including the pipes of every biome. The same goes for
+//! high contrast, see [`high_contrast`](crate::high_contrast), which takes
+//! over from the palette while on. Pipe themes from the [`shop`](crate::shop)
+//! are drawn the same way, but give way to the palettes.
+
+use std::collections::HashMap;
+
+use bevy::{color::Hsla, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	biome::BiomeAssets, high_contrast, save::SaveData, settings::Settings, shop::PipeTheme,
+	sprites::Textures,
+};
+
+/// Hue of the blue of the bird in its texture, in degrees.
+pub(crate) const BIRD_HUE: f32 = 223.0;
+/// Hue of the green of the pipe textures, in degrees.
+const PIPE_HUE: f32 = 100.0;
+/// Only colors within this many degrees of the hue being replaced are turned,
+/// so the beak and the eyes keep their colors.
+const HUE_RANGE: f32 = 45.0;
+/// Grays and near black outlines are left alone.
+const MIN_SATURATION: f32 = 0.2;
+const MIN_LIGHTNESS: f32 = 0.2;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Palette {
+	#[default]
+	Standard,
+	Deuteranopia,
+	Protanopia,
+	Tritanopia,
+}
+
+impl Palette {
+	pub const ALL: [Palette; 4] = [
+		Palette::Standard,
+		Palette::Deuteranopia,
+		Palette::Protanopia,
+		Palette::Tritanopia,
+	];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Palette::Standard => "Standard",
+			Palette::Deuteranopia => "Deuteranopia",
+			Palette::Protanopia => "Protanopia",
+			Palette::Tritanopia => "Tritanopia",
+		}
+	}
+
+	/// Hue the blue of the bird turns to.
+	fn bird_hue(self) -> f32 {
+		match self {
+			Palette::Tritanopia => 330.0,
+			_ => BIRD_HUE,
+		}
+	}
+
+	/// Hue the green of the pipes turns to, that of the pipe theme for the
+	/// palettes that leave the pipes alone.
+	fn pipe_hue(self, theme: PipeTheme) -> f32 {
+		match self {
+			Palette::Deuteranopia => 30.0,
+			Palette::Protanopia => 55.0,
+			_ => theme.hue().unwrap_or(PIPE_HUE),
+		}
+	}
+
+	/// For things going well, like being ahead of the best time.
+	pub fn good(self) -> Color {
+		match self {
+			Palette::Deuteranopia | Palette::Protanopia => Color::srgb(0.35, 0.7, 1.0),
+			_ => Color::srgb(0.4, 1.0, 0.4),
+		}
+	}
+
+	/// For things going badly, like falling behind the best time.
+	pub fn bad(self) -> Color {
+		match self {
+			Palette::Deuteranopia | Palette::Protanopia => Color::srgb(1.0, 0.65, 0.1),
+			_ => Color::srgb(1.0, 0.45, 0.45),
+		}
+	}
+}
+
+/// The textures that get redrawn.
+#[derive(Clone, Copy)]
+enum Part {
+	Bird,
+	Pipe,
+	Ground,
+}
+
+/// The textures as loaded, to recolor from each time the palette changes.
+#[derive(Resource, Default)]
+struct OriginalImages(HashMap<AssetId<Image>, Image>);
+
+/// The palette each texture is currently in, whether in high contrast and the
+/// pipe theme.
+#[derive(Resource, Default)]
+struct AppliedPalettes(HashMap<AssetId<Image>, (Palette, bool, PipeTheme)>);
+
+/// Gives colors close to `from` the hue `to` instead, keeping how far from
+/// `from` they were.
+fn turn_hue(color: Color, from: f32, to: f32) -> Color {
+	let hsla = Hsla::from(color);
+	let distance = (hsla.hue - from + 540.0).rem_euclid(360.0) - 180.0;
+	if distance.abs() > HUE_RANGE
+		|| hsla.saturation < MIN_SATURATION
+		|| hsla.lightness < MIN_LIGHTNESS
+	{
+		return color;
+	}
+	hsla.with_hue((to + distance).rem_euclid(360.0)).into()
+}
+
+pub(crate) fn recolor(original: &Image, from: f32, to: f32) -> Image {
+	let mut image = original.clone();
+	if from == to {
+		return image;
+	}
+	for y in 0..image.height() {
+		for x in 0..image.width() {
+			let Ok(color) = original.get_color_at(x, y) else {
+				return image;
+			};
+			if color.alpha() > 0.0 {
+				let _ = image.set_color_at(x, y, turn_hue(color, from, to));
+			}
+		}
+	}
+	image
+}
+
+fn restyle(original: &Image, part: Part, look: (Palette, bool, PipeTheme)) -> Image {
+	let (palette, high_contrast, pipes) = look;
+	match (part, high_contrast) {
+		(Part::Bird, true) => high_contrast::bird(original),
+		(Part::Pipe, true) => high_contrast::pipe(original),
+		(Part::Ground, true) => high_contrast::ground(original),
+		(Part::Bird, false) => recolor(original, BIRD_HUE, palette.bird_hue()),
+		(Part::Pipe, false) => recolor(original, PIPE_HUE, palette.pipe_hue(pipes)),
+		(Part::Ground, false) => original.clone(),
+	}
+}
+
+/// Also runs as textures finish loading, which puts them back to how they
+/// were loaded.
+pub(crate) fn recolor_textures(
+	mut events: EventReader<AssetEvent<Image>>,
+	mut images: ResMut<Assets<Image>>,
+	mut originals: ResMut<OriginalImages>,
+	mut applied: ResMut<AppliedPalettes>,
+	settings: Res<Settings>,
+	save: Res<SaveData>,
+	textures: Res<Textures>,
+	biomes: Res<BiomeAssets>,
+) {
+	for event in events.read() {
+		if let AssetEvent::LoadedWithDependencies { id } = event {
+			originals.0.remove(id);
+			applied.0.remove(id);
+		}
+	}
+	let look = (settings.palette, settings.high_contrast, save.shop.pipes);
+	let parts = [
+		(textures.bird_image().id(), Part::Bird),
+		(textures.ground_image().id(), Part::Ground),
+	];
+	let pipes = biomes.pipe_images().map(|pipe| (pipe.id(), Part::Pipe));
+	for (id, part) in parts.into_iter().chain(pipes) {
+		if applied.0.get(&id) == Some(&look) {
+			continue;
+		}
+		let Some(image) = images.get_mut(id) else {
+			continue;
+		};
+		let original = originals.0.entry(id).or_insert_with(|| image.clone());
+		*image = restyle(original, part, look);
+		applied.0.insert(id, look);
+	}
+}
+
+pub struct PalettePlugin;
+
+impl Plugin for PalettePlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<OriginalImages>()
+			.init_resource::<AppliedPalettes>()
+			.add_systems(
+				Update,
+				recolor_textures.run_if(
+					resource_changed::<Settings>
+						.or(resource_changed::<SaveData>)
+						.or(on_event::<AssetEvent<Image>>),
+				),
+			);
+	}
+}
@@ -0,0 +1,185 @@
+//! Short-lived bursts of particles for flapping, scoring and crashing.
+//!
+//! Purely cosmetic, so they use their own randomness and stay out of the
+//! deterministic simulation.
+
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+	offscreen::DespawnOffscreen, settings::motion_allowed, tween::FadeOut, GameScore, GameStates,
+	GameplayEvent, InRun, Player,
+};
+
+const PARTICLE_GRAVITY: f32 = 900.0;
+/// How fast fluttering particles sway from side to side, in radians per
+/// second.
+const FLUTTER_RATE: f32 = 9.0;
+/// How far fluttering particles lean into their sway.
+const FLUTTER_TILT: f32 = 0.6;
+
+/// Falls under its own gravity until its [`FadeOut`] is done.
+#[derive(Component)]
+struct Particle {
+	velocity: Vec2,
+	gravity: f32,
+	max_fall_speed: f32,
+	/// Speed of the sway from side to side, none for particles that don't
+	/// flutter.
+	flutter: f32,
+	/// Where in its sway the particle started, so they don't all sway alike.
+	phase: f32,
+}
+
+/// What a burst of particles looks like.
+struct Burst {
+	count: usize,
+	/// Cycled through from one particle to the next.
+	colors: &'static [Color],
+	size: Vec2,
+	/// Fastest initial speed, particles get between half of it and all of it.
+	speed: f32,
+	lifetime: f32,
+	gravity: f32,
+	max_fall_speed: f32,
+	flutter: f32,
+}
+
+const SPARKS: Burst = Burst {
+	count: 12,
+	colors: &[Color::srgb(1.0, 0.9, 0.3)],
+	size: Vec2::splat(4.0),
+	speed: 250.0,
+	lifetime: 0.4,
+	gravity: PARTICLE_GRAVITY,
+	max_fall_speed: f32::INFINITY,
+	flutter: 0.0,
+};
+
+const FEATHERS: Burst = Burst {
+	count: 16,
+	colors: &[Color::srgb(1.0, 0.85, 0.25), Color::WHITE],
+	size: Vec2::new(8.0, 4.0),
+	speed: 350.0,
+	lifetime: 0.9,
+	gravity: PARTICLE_GRAVITY,
+	max_fall_speed: f32::INFINITY,
+	flutter: 0.0,
+};
+
+/// A few loose feathers on every flap, drifting down slowly.
+const FLAP_FEATHERS: Burst = Burst {
+	count: 3,
+	colors: &[Color::srgb(1.0, 0.85, 0.25), Color::WHITE],
+	size: Vec2::new(6.0, 3.0),
+	speed: 80.0,
+	lifetime: 1.0,
+	gravity: 200.0,
+	max_fall_speed: 45.0,
+	flutter: 40.0,
+};
+
+impl Burst {
+	fn spawn(&self, commands: &mut Commands, position: Vec2) {
+		self.spawn_some(commands, position, self.count);
+	}
+
+	fn spawn_some(&self, commands: &mut Commands, position: Vec2, count: usize) {
+		let mut rng = rand::rng();
+		for i in 0..count {
+			let angle = rng.random_range(0.0..TAU);
+			let speed = rng.random_range(0.5..1.0) * self.speed;
+			commands.spawn((
+				Particle {
+					velocity: Vec2::from_angle(angle) * speed,
+					gravity: self.gravity,
+					max_fall_speed: self.max_fall_speed,
+					flutter: self.flutter,
+					phase: rng.random_range(0.0..TAU),
+				},
+				FadeOut::new(self.lifetime),
+				DespawnOffscreen::new(self.size.x),
+				Sprite::from_color(self.colors[i % self.colors.len()], self.size),
+				Transform::from_translation(position.extend(5.0))
+					.with_rotation(Quat::from_rotation_z(angle)),
+			));
+		}
+	}
+}
+
+fn sparks_on_score(
+	mut commands: Commands,
+	score: Res<GameScore>,
+	mut last_score: Local<i64>,
+	players: Query<&Transform, With<Player>>,
+) {
+	if **score > *last_score {
+		for transform in &players {
+			SPARKS.spawn(&mut commands, transform.translation.truncate());
+		}
+	}
+	*last_score = **score;
+}
+
+fn feathers_on_crash(mut commands: Commands, players: Query<&Transform, With<Player>>) {
+	for transform in &players {
+		FEATHERS.spawn(&mut commands, transform.translation.truncate());
+	}
+}
+
+/// Two or three, so the flaps don't all look the same.
+fn feathers_on_flap(
+	mut commands: Commands,
+	mut events: EventReader<GameplayEvent>,
+	players: Query<&Transform, With<Player>>,
+) {
+	let mut rng = rand::rng();
+	for event in events.read() {
+		let GameplayEvent::Flapped(bird) = event else {
+			continue;
+		};
+		if let Ok(transform) = players.get(*bird) {
+			FLAP_FEATHERS.spawn_some(
+				&mut commands,
+				transform.translation.truncate(),
+				rng.random_range(2..=FLAP_FEATHERS.count),
+			);
+		}
+	}
+}
+
+fn update_particles(particles: Query<(&mut Particle, &mut Transform)>, time: Res<Time>) {
+	for (mut particle, mut transform) in particles {
+		particle.velocity.y = (particle.velocity.y - particle.gravity * time.delta_secs())
+			.max(-particle.max_fall_speed);
+		// Fluttering particles lose their sideways speed to the air and drift
+		// with their sway instead.
+		if particle.flutter > 0.0 {
+			particle.velocity.x *= 1.0 - (2.0 * time.delta_secs()).min(1.0);
+			let sway = (time.elapsed_secs() * FLUTTER_RATE + particle.phase).sin();
+			transform.translation.x += sway * particle.flutter * time.delta_secs();
+			transform.rotation = Quat::from_rotation_z(sway * FLUTTER_TILT);
+		}
+		transform.translation += (particle.velocity * time.delta_secs()).extend(0.0);
+	}
+}
+
+pub struct ParticlesPlugin;
+
+impl Plugin for ParticlesPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			OnExit(InRun),
+			feathers_on_crash.run_if(in_state(GameStates::GameOver).and(motion_allowed)),
+		)
+		.add_systems(
+			Update,
+			(
+				(sparks_on_score, feathers_on_flap).run_if(in_state(InRun).and(motion_allowed)),
+				update_particles,
+			),
+		);
+	}
+}
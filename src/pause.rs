@@ -0,0 +1,118 @@
+//! Pausing live runs.
+//!
+//! Pausing stops virtual time, which halts the fixed-tick simulation along
+//! with everything else driven by [`Time`], so a paused run resumes exactly
+//! where it left off.
+//!
+//! Runs also pause on their own when the game goes to the background, and only
+//! resume once the player asks for it.
+
+use bevy::{
+	prelude::*,
+	window::{AppLifecycle, WindowFocused, WindowOccluded},
+};
+
+use crate::{
+	input::{key_name, InputAction, PlayerInput},
+	locale::Locale,
+	settings::Settings,
+	theme::UiTheme,
+	GameStates,
+};
+
+#[derive(SubStates, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[source(GameStates = GameStates::InGame)]
+pub(crate) enum Pause {
+	#[default]
+	Running,
+	Paused,
+}
+
+#[derive(Component)]
+struct PauseUi;
+
+fn pause_time(
+	mut commands: Commands,
+	mut time: ResMut<Time<Virtual>>,
+	settings: Res<Settings>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	time.pause();
+	commands.spawn((
+		PauseUi,
+		Node {
+			width: Val::Percent(100.0),
+			height: Val::Percent(100.0),
+			flex_direction: FlexDirection::Column,
+			justify_content: JustifyContent::Center,
+			align_items: AlignItems::Center,
+			row_gap: Val::Px(16.0),
+			..default()
+		},
+		BackgroundColor(Color::BLACK.with_alpha(0.4)),
+		children![
+			(Text::new(locale.text("paused")), theme.text(96.0)),
+			(
+				Text::new(locale.format(
+					"paused-resume",
+					&[("key", &key_name(settings.controls.key(InputAction::Pause)))],
+				)),
+				theme.text(32.0),
+			),
+		],
+	));
+}
+
+fn resume_time(
+	mut commands: Commands,
+	mut time: ResMut<Time<Virtual>>,
+	ui: Single<Entity, With<PauseUi>>,
+) {
+	time.unpause();
+	commands.entity(*ui).despawn();
+}
+
+fn toggle_pause(
+	input: PlayerInput,
+	pause: Res<State<Pause>>,
+	mut next_pause: ResMut<NextState<Pause>>,
+) {
+	if input.pause_pressed() {
+		next_pause.set(match pause.get() {
+			Pause::Running => Pause::Paused,
+			Pause::Paused => Pause::Running,
+		});
+	}
+}
+
+fn pause_in_background(
+	mut focus_events: EventReader<WindowFocused>,
+	mut occlusion_events: EventReader<WindowOccluded>,
+	mut lifecycle_events: EventReader<AppLifecycle>,
+	mut next_pause: ResMut<NextState<Pause>>,
+) {
+	let unfocused = focus_events.read().any(|event| !event.focused);
+	let occluded = occlusion_events.read().any(|event| event.occluded);
+	let suspended = lifecycle_events
+		.read()
+		.any(|event| matches!(event, AppLifecycle::WillSuspend | AppLifecycle::Suspended));
+	if unfocused || occluded || suspended {
+		next_pause.set(Pause::Paused);
+	}
+}
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(OnEnter(Pause::Paused), pause_time)
+			.add_systems(OnExit(Pause::Paused), resume_time)
+			.add_systems(
+				Update,
+				(toggle_pause, pause_in_background)
+					.chain()
+					.run_if(in_state(GameStates::InGame)),
+			);
+	}
+}
@@ -0,0 +1,197 @@
+//! The obstacle layouts the pipe spawner picks from, getting more varied as the
+//! score goes up. Besides the built-in patterns here, there are the ones
+//! authored in [`layouts`](crate::layouts).
+
+use std::ops::Range;
+
+use rand::{seq::IndexedRandom, Rng};
+
+use crate::{layouts::AuthoredPattern, seed::GameRng, PIPE_WIDTH};
+
+/// How likely each pattern is, once the score reaches the given value.
+const PATTERN_WEIGHTS: [(PipePattern, i64, u32); 5] = [
+	(PipePattern::Pair, 0, 10),
+	(PipePattern::NarrowPair, 5, 3),
+	(PipePattern::Staggered, 10, 2),
+	(PipePattern::SpinningBar, 15, 2),
+	(PipePattern::DoubleGap, 20, 2),
+];
+
+const NARROW_GAP_SCALE: f32 = 0.8;
+const STAGGER_COLUMNS: usize = 3;
+/// Distance between the columns of a staggered cluster.
+const STAGGER_SPACING: f32 = PIPE_WIDTH * 5.0;
+/// How much higher or lower each gap of a staggered cluster is than the last.
+const STAGGER_STEP: f32 = 60.0;
+/// Too narrow for the bird to fit through.
+const DECOY_GAP: f32 = 24.0;
+/// Height of the pipe between the two gaps of a double gap column.
+const DIVIDER_HEIGHT: f32 = 80.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipePattern {
+	/// The classic pair of pipes with one gap.
+	Pair,
+	/// A pair with a tighter gap.
+	NarrowPair,
+	/// A few pairs close together, each gap a step higher or lower.
+	Staggered,
+	/// A column with two gaps, one of them too narrow to fly through.
+	DoubleGap,
+	/// A pair with a bar spinning in its gap, see [`rotating_bars`](crate::rotating_bars).
+	SpinningBar,
+	/// One of the authored patterns, by index.
+	Authored(usize),
+}
+
+/// An opening in a column of pipes.
+#[derive(Debug, Clone, Copy)]
+pub struct Gap {
+	pub bottom: f32,
+	pub height: f32,
+	/// Whether the bird can fly through, the pipe above it gives score.
+	pub safe: bool,
+	/// Whether a coin floats in the middle of the gap, see [`coins`](crate::coins).
+	pub coin: bool,
+	pub spinning_bar: bool,
+}
+
+impl Gap {
+	pub fn top(&self) -> f32 {
+		self.bottom + self.height
+	}
+}
+
+/// A column of pipes, behind the first column of the pattern by `offset`.
+#[derive(Debug, Clone)]
+pub struct Column {
+	pub offset: f32,
+	/// Ordered from the bottom up.
+	pub gaps: Vec<Gap>,
+}
+
+/// Picks where a gap of the given height goes between `bounds`. The window can
+/// be too short to fit it, then it's squeezed in at the bottom.
+fn random_gap_bottom(rng: &mut GameRng, height: f32, bounds: &Range<f32>) -> f32 {
+	let highest = bounds.end - height;
+	if highest > bounds.start {
+		rng.random_range(bounds.start..highest)
+	} else {
+		bounds.start
+	}
+}
+
+fn single(bottom: f32, height: f32) -> Vec<Gap> {
+	vec![Gap {
+		bottom,
+		height,
+		safe: true,
+		coin: true,
+		spinning_bar: false,
+	}]
+}
+
+impl PipePattern {
+	/// Picks a pattern from the ones available at `score`, built-in or
+	/// authored. Nothing is drawn from the game's randomness while plain pairs
+	/// are the only option, so the start of a run plays out the same as it did
+	/// before there were patterns.
+	pub fn choose(score: i64, authored: &[AuthoredPattern], rng: &mut GameRng) -> Self {
+		let authored = authored
+			.iter()
+			.enumerate()
+			.map(|(i, pattern)| (PipePattern::Authored(i), pattern.from_score, pattern.weight));
+		let available = PATTERN_WEIGHTS
+			.into_iter()
+			.chain(authored)
+			.filter(|(_, from_score, _)| score >= *from_score)
+			.collect::<Vec<_>>();
+		if available.len() == 1 {
+			return available[0].0;
+		}
+		available
+			.choose_weighted(&mut **rng, |(.., weight)| *weight)
+			.map_or(PipePattern::Pair, |(pattern, ..)| *pattern)
+	}
+
+	/// Lays out the columns of the pattern for the current gap height, with
+	/// every gap between `bounds`.
+	pub fn columns(
+		self,
+		gap: f32,
+		bounds: Range<f32>,
+		authored: &[AuthoredPattern],
+		rng: &mut GameRng,
+	) -> Vec<Column> {
+		match self {
+			PipePattern::Pair => vec![Column {
+				offset: 0.0,
+				gaps: single(random_gap_bottom(rng, gap, &bounds), gap),
+			}],
+			PipePattern::NarrowPair => {
+				let gap = gap * NARROW_GAP_SCALE;
+				vec![Column {
+					offset: 0.0,
+					gaps: single(random_gap_bottom(rng, gap, &bounds), gap),
+				}]
+			}
+			PipePattern::SpinningBar => vec![Column {
+				offset: 0.0,
+				gaps: vec![Gap {
+					// The middle of the gap is never clear.
+					coin: false,
+					spinning_bar: true,
+					..single(random_gap_bottom(rng, gap, &bounds), gap)[0]
+				}],
+			}],
+			PipePattern::Staggered => {
+				let first = random_gap_bottom(rng, gap, &bounds);
+				let step = if rng.random_bool(0.5) {
+					STAGGER_STEP
+				} else {
+					-STAGGER_STEP
+				};
+				let highest = (bounds.end - gap).max(bounds.start);
+				(0..STAGGER_COLUMNS)
+					.map(|i| Column {
+						offset: i as f32 * STAGGER_SPACING,
+						gaps: single((first + i as f32 * step).clamp(bounds.start, highest), gap),
+					})
+					.collect()
+			}
+			PipePattern::DoubleGap => {
+				let bottom = random_gap_bottom(rng, gap + DIVIDER_HEIGHT + DECOY_GAP, &bounds);
+				let safe = Gap {
+					bottom,
+					height: gap,
+					safe: true,
+					coin: true,
+					spinning_bar: false,
+				};
+				let decoy = Gap {
+					bottom,
+					height: DECOY_GAP,
+					safe: false,
+					coin: false,
+					spinning_bar: false,
+				};
+				let (lower, upper) = if rng.random_bool(0.5) {
+					(safe, decoy)
+				} else {
+					(decoy, safe)
+				};
+				let upper = Gap {
+					bottom: lower.top() + DIVIDER_HEIGHT,
+					..upper
+				};
+				vec![Column {
+					offset: 0.0,
+					gaps: vec![lower, upper],
+				}]
+			}
+			PipePattern::Authored(index) => authored
+				.get(index)
+				.map_or_else(Vec::new, |pattern| pattern.columns(gap, &bounds)),
+		}
+	}
+}
@@ -0,0 +1,108 @@
+//! Pipes leaving play are kept around out of sight instead of being despawned,
+//! and new columns are built from them first, so long sessions don't keep
+//! creating and destroying entities.
+//!
+//! A pipe in the pool has lost everything that makes it a pipe in play, so
+//! putting it back works just like spawning a new one, down to `Added<Pipe>`.
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+	cleanup::{CleanupOnExit, RunScene},
+	collision::Collider,
+	day_night::Tinted,
+	interpolation::Interpolated,
+	moving_gaps::Oscillator,
+	offscreen::DespawnOffscreen,
+	pipe_patterns::Gap,
+	rotating_bars::spinning_bar,
+	sprites::Textures,
+	GameStates, InRun, Obstacle, Pipe, PipeBundle, Scrolling, Velocity,
+};
+
+/// Pipes out of play, waiting for the next column.
+#[derive(Resource, Default)]
+pub struct PipePool(Vec<Entity>);
+
+/// Lays out columns of pipes and takes them out of play again. Recycling has
+/// to come before spawning within a tick, so a pipe is put away before it's
+/// put back.
+#[derive(SystemParam)]
+pub struct PipeSpawner<'w, 's> {
+	commands: Commands<'w, 's>,
+	textures: Res<'w, Textures>,
+	pool: ResMut<'w, PipePool>,
+}
+
+impl PipeSpawner<'_, '_> {
+	/// Velocity and smoothing start over through the components the bundle
+	/// requires.
+	fn spawn(&mut self, pipe: PipeBundle) {
+		match self.pool.0.pop() {
+			Some(entity) => {
+				self.commands
+					.entity(entity)
+					.insert((pipe, Visibility::Inherited));
+			}
+			None => {
+				self.commands.spawn(pipe);
+			}
+		}
+	}
+
+	/// Spawns the pipes of a column around its gaps, the pipe right above the
+	/// safe gap giving score. The outer pipes are `height` tall to reach past
+	/// the play area from anywhere in it.
+	pub fn spawn_column(&mut self, x: f32, height: f32, gaps: &[Gap]) {
+		let Some(first) = gaps.first() else {
+			return;
+		};
+		let textures = &self.textures;
+		let mut pipes = vec![PipeBundle::new(textures, x, height, first.bottom, None)];
+		for (below, above) in gaps.iter().zip(&gaps[1..]) {
+			pipes.push(PipeBundle::new(
+				textures,
+				x,
+				above.bottom - below.top(),
+				above.bottom,
+				Some(below),
+			));
+		}
+		let last = gaps.last().unwrap_or(first);
+		pipes.push(PipeBundle::new(
+			textures,
+			x,
+			height,
+			last.top() + height,
+			Some(last),
+		));
+		for pipe in pipes {
+			self.spawn(pipe);
+		}
+		for gap in gaps.iter().filter(|gap| gap.spinning_bar) {
+			self.commands.spawn(spinning_bar(&self.textures, x, gap));
+		}
+	}
+
+	/// Takes a pipe out of play and keeps it for a later column.
+	pub fn recycle(&mut self, entity: Entity) {
+		self.commands
+			.entity(entity)
+			.remove::<(
+				Pipe,
+				Obstacle,
+				Collider,
+				Scrolling,
+				Velocity,
+				Interpolated,
+				Oscillator,
+				Tinted,
+				DespawnOffscreen,
+				RunScene,
+				CleanupOnExit<InRun>,
+				CleanupOnExit<GameStates>,
+			)>()
+			.insert(Visibility::Hidden);
+		self.pool.0.push(entity);
+	}
+}
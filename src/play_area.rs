@@ -0,0 +1,136 @@
+//! The part of the world runs are played in, the part visible through the
+//! camera, and how that is fit into the window.
+//!
+//! Runs are always played in [`WINDOW_SIZE`] worth of the world, whatever the
+//! window, so the same seed gives the same pipes on every screen, in replays
+//! and ghosts too. Filling the window only shows more of the world around it.
+
+use bevy::{
+	prelude::*,
+	render::camera::{ScalingMode, Viewport},
+	window::{PrimaryWindow, WindowResized},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{headless::is_headless, settings::Settings, touch::IS_MOBILE, MainCamera, WINDOW_SIZE};
+
+/// How the world is fit into windows that aren't [`WINDOW_SIZE`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenScaling {
+	/// Always shows exactly the play area, with black bars around it.
+	Letterbox,
+	/// Fills the window, showing more of the world around the play area along
+	/// its longer side.
+	Fill,
+}
+
+impl Default for ScreenScaling {
+	/// Letterboxing a phone held upright would leave little more than a strip.
+	fn default() -> Self {
+		if IS_MOBILE {
+			ScreenScaling::Fill
+		} else {
+			ScreenScaling::Letterbox
+		}
+	}
+}
+
+impl ScreenScaling {
+	pub fn name(self) -> &'static str {
+		match self {
+			ScreenScaling::Letterbox => "Letterbox",
+			ScreenScaling::Fill => "Fill",
+		}
+	}
+}
+
+/// World rectangle runs are played in, in world coordinates. Always the size
+/// of [`WINDOW_SIZE`], centered on the origin.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Deref)]
+pub struct PlayArea(Rect);
+
+impl Default for PlayArea {
+	fn default() -> Self {
+		PlayArea(Rect::from_center_size(Vec2::ZERO, WINDOW_SIZE))
+	}
+}
+
+/// Visible world rectangle, which holds the play area and, when filling a
+/// window of another shape, more of the world around it. For drawing only.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Deref)]
+pub struct VisibleArea(Rect);
+
+impl Default for VisibleArea {
+	fn default() -> Self {
+		VisibleArea(*PlayArea::default())
+	}
+}
+
+fn fit_camera(
+	settings: Res<Settings>,
+	window: Single<&Window, With<PrimaryWindow>>,
+	camera: Single<(&mut Camera, &mut Projection), With<MainCamera>>,
+	mut ui_scale: ResMut<UiScale>,
+) {
+	let (mut camera, mut projection) = camera.into_inner();
+	let Projection::Orthographic(projection) = &mut *projection else {
+		return;
+	};
+	let window_size = window.physical_size().as_vec2();
+	if window_size.min_element() < 1.0 {
+		// Minimized, nothing to fit.
+		return;
+	}
+	match settings.screen_scaling {
+		ScreenScaling::Letterbox => {
+			let size = (WINDOW_SIZE * (window_size / WINDOW_SIZE).min_element()).floor();
+			camera.viewport = Some(Viewport {
+				physical_position: ((window_size - size) / 2.0).as_uvec2(),
+				physical_size: size.as_uvec2(),
+				..default()
+			});
+			projection.scaling_mode = ScalingMode::Fixed {
+				width: WINDOW_SIZE.x,
+				height: WINDOW_SIZE.y,
+			};
+		}
+		ScreenScaling::Fill => {
+			camera.viewport = None;
+			projection.scaling_mode = ScalingMode::AutoMin {
+				min_width: WINDOW_SIZE.x,
+				min_height: WINDOW_SIZE.y,
+			};
+		}
+	}
+	// Scale the interface along with the world.
+	ui_scale.0 = (window.size() / WINDOW_SIZE).min_element();
+}
+
+/// The camera sits at the origin, so its projected area is the visible world.
+fn update_visible_area(
+	mut visible_area: ResMut<VisibleArea>,
+	projection: Query<&Projection, (With<MainCamera>, Changed<Projection>)>,
+) {
+	let Ok(Projection::Orthographic(projection)) = projection.single() else {
+		return;
+	};
+	visible_area.set_if_neq(VisibleArea(projection.area));
+}
+
+pub struct PlayAreaPlugin;
+
+impl Plugin for PlayAreaPlugin {
+	fn build(&self, app: &mut App) {
+		// Shows in the bars around a letterboxed view.
+		app.insert_resource(ClearColor(Color::BLACK))
+			.init_resource::<PlayArea>()
+			.init_resource::<VisibleArea>()
+			// Nothing works out what the camera sees without rendering, so the
+			// visible area stays the play area.
+			.add_systems(PreUpdate, update_visible_area.run_if(not(is_headless)))
+			.add_systems(
+				Update,
+				fit_camera.run_if(resource_changed::<Settings>.or(on_event::<WindowResized>)),
+			);
+	}
+}
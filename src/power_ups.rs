@@ -0,0 +1,364 @@
+//! Power-ups picked up mid-run, each giving the bird a timed effect.
+//!
+//! They change how a run plays out, so they are spawned on a fixed schedule
+//! inside the simulation rather than at random, keeping runs with the same seed
+//! and flaps identical.
+
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::RunScene,
+	coins::Coin,
+	collision::{Collider, ColliderShape, CollisionLayer, OnCollision},
+	difficulty::{Difficulty, PaceModifier},
+	locale::Locale,
+	mirror::ScrollDirection,
+	moving_gaps::Oscillator,
+	offscreen::DespawnOffscreen,
+	theme::UiTheme,
+	GameplaySet, InRun, Pipe, Player, Scrolling, Velocity, SCOREBOARD_TEXT_PADDING,
+};
+
+const PICKUP_SIZE: Vec2 = Vec2::splat(28.0);
+/// A power-up shows up once every this many pairs of pipes.
+const PIPES_PER_POWER_UP: u32 = 8;
+
+const SHIELD_DURATION: f32 = 10.0;
+const SLOW_TIME_DURATION: f32 = 5.0;
+/// Pace of the pipes while time is slowed down.
+const SLOW_TIME_PACE: f32 = 0.5;
+const MAGNET_DURATION: f32 = 8.0;
+/// Coins closer to the bird than this are pulled in.
+const MAGNET_RANGE: f32 = 250.0;
+/// How quickly pulled coins turn towards the bird, in units per second squared.
+const MAGNET_PULL: f32 = 6000.0;
+/// Top speed of pulled coins.
+const MAGNET_SPEED: f32 = 1200.0;
+/// How long the bird can fly through pipes after its shield breaks, so it
+/// doesn't crash into the pipe that broke it.
+const SHIELD_BREAK_GRACE: f32 = 1.0;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[require(
+	Collider = Collider::new(ColliderShape::Box(PICKUP_SIZE), CollisionLayer::Pickup),
+	DespawnOffscreen = DespawnOffscreen::new(PICKUP_SIZE.x),
+	RunScene
+)]
+pub enum PowerUp {
+	Shield,
+	SlowTime,
+	Magnet,
+}
+
+impl PowerUp {
+	/// Handed out in turn.
+	const ALL: [PowerUp; 3] = [PowerUp::Shield, PowerUp::SlowTime, PowerUp::Magnet];
+
+	/// Message of the name.
+	fn key(self) -> &'static str {
+		match self {
+			PowerUp::Shield => "power-up-shield",
+			PowerUp::SlowTime => "power-up-slow-time",
+			PowerUp::Magnet => "power-up-magnet",
+		}
+	}
+
+	fn color(self) -> Color {
+		match self {
+			PowerUp::Shield => Color::srgb(0.3, 0.7, 1.0),
+			PowerUp::SlowTime => Color::srgb(0.7, 0.4, 1.0),
+			PowerUp::Magnet => Color::srgb(1.0, 0.3, 0.3),
+		}
+	}
+
+	/// Gives the effect of the power-up to the bird.
+	fn apply(self, player: &mut EntityCommands) {
+		match self {
+			PowerUp::Shield => player.insert(timed_effect(Shield, SHIELD_DURATION)),
+			PowerUp::SlowTime => player.insert(timed_effect(SlowTime, SLOW_TIME_DURATION)),
+			PowerUp::Magnet => player.insert(timed_effect(Magnet, MAGNET_DURATION)),
+		};
+	}
+}
+
+/// A lasting effect of a [`PowerUp`], on the player.
+pub trait Effect: Component {
+	const POWER_UP: PowerUp;
+}
+
+/// Removes the effect `T` from its entity once it runs out.
+#[derive(Component)]
+pub struct EffectTimer<T: Component> {
+	timer: Timer,
+	effect: PhantomData<T>,
+}
+
+impl<T: Component> EffectTimer<T> {
+	fn new(seconds: f32) -> Self {
+		EffectTimer {
+			timer: Timer::from_seconds(seconds, TimerMode::Once),
+			effect: PhantomData,
+		}
+	}
+}
+
+pub fn timed_effect<T: Component>(effect: T, seconds: f32) -> impl Bundle {
+	(effect, EffectTimer::<T>::new(seconds))
+}
+
+/// Survives one pipe collision.
+#[derive(Component)]
+pub struct Shield;
+
+impl Effect for Shield {
+	const POWER_UP: PowerUp = PowerUp::Shield;
+}
+
+/// Slows down the pipes, but not the bird.
+#[derive(Component)]
+pub struct SlowTime;
+
+impl Effect for SlowTime {
+	const POWER_UP: PowerUp = PowerUp::SlowTime;
+}
+
+/// Pulls nearby coins in.
+#[derive(Component)]
+pub struct Magnet;
+
+impl Effect for Magnet {
+	const POWER_UP: PowerUp = PowerUp::Magnet;
+}
+
+/// Passes through pipes without crashing, left behind by a broken [`Shield`].
+#[derive(Component)]
+pub struct Invulnerable;
+
+/// Whatever can keep the bird from crashing into a pipe, see [`absorb_hit`].
+pub type HitProtection = (Has<Shield>, Has<Invulnerable>);
+
+/// Takes the hit of a pipe collision on the bird, if anything can. Returns
+/// whether the bird survives it.
+pub fn absorb_hit(player: &mut EntityCommands, (has_shield, invulnerable): (bool, bool)) -> bool {
+	if invulnerable {
+		return true;
+	}
+	if has_shield {
+		player
+			.remove::<(Shield, EffectTimer<Shield>)>()
+			.insert(timed_effect(Invulnerable, SHIELD_BREAK_GRACE));
+		return true;
+	}
+	false
+}
+
+/// Pairs of pipes spawned so far in the run, and how many power-ups were
+/// handed out for them.
+#[derive(Resource, Default)]
+struct PowerUpSchedule {
+	pipes_seen: u32,
+	handed_out: usize,
+}
+
+#[derive(Component)]
+struct PowerUpHud;
+
+/// Row of the HUD showing one active effect.
+#[derive(Component)]
+struct EffectIndicator(PowerUp);
+
+fn reset_schedule(mut schedule: ResMut<PowerUpSchedule>) {
+	*schedule = PowerUpSchedule::default();
+}
+
+/// Places each power-up halfway between the pipes before it and the gap of
+/// the pipes that triggered it, at the height of that gap.
+fn spawn_power_ups(
+	mut commands: Commands,
+	mut schedule: ResMut<PowerUpSchedule>,
+	difficulty: Res<Difficulty>,
+	direction: Res<ScrollDirection>,
+	pipes: Query<(&Transform, &Sprite, &Pipe), Added<Pipe>>,
+) {
+	for (transform, sprite, pipe) in pipes {
+		if !pipe.give_score {
+			continue;
+		}
+		schedule.pipes_seen += 1;
+		if !schedule.pipes_seen.is_multiple_of(PIPES_PER_POWER_UP) {
+			continue;
+		}
+		let power_up = PowerUp::ALL[schedule.handed_out % PowerUp::ALL.len()];
+		schedule.handed_out += 1;
+		let height = sprite.custom_size.unwrap_or_default().y;
+		let gap_top = transform.translation.y - height / 2.0;
+		let spacing = difficulty.pipe_speed * difficulty.pipe_spawn_interval.as_secs_f32();
+		commands.spawn((
+			power_up,
+			Sprite::from_color(power_up.color(), PICKUP_SIZE),
+			Transform::from_xyz(
+				transform.translation.x + direction.sign() * spacing / 2.0,
+				gap_top - pipe.gap / 2.0,
+				1.0,
+			),
+			Scrolling,
+		));
+	}
+}
+
+fn pick_up_power_up(
+	trigger: Trigger<OnCollision>,
+	mut commands: Commands,
+	pickups: Query<&PowerUp>,
+) {
+	if let Ok(power_up) = pickups.get(trigger.target()) {
+		commands.entity(trigger.target()).despawn();
+		power_up.apply(&mut commands.entity(trigger.other));
+	}
+}
+
+pub fn expire_effects<T: Component>(
+	mut commands: Commands,
+	effects: Query<(Entity, &mut EffectTimer<T>)>,
+	time: Res<Time>,
+) {
+	for (entity, mut effect) in effects {
+		if effect.timer.tick(time.delta()).finished() {
+			commands.entity(entity).remove::<(T, EffectTimer<T>)>();
+		}
+	}
+}
+
+/// Runs in a fixed order with the difficulty being updated at the end of the
+/// tick, so the pace changes on the same tick on every replay of the run.
+fn slow_time(slowed: Query<(), (With<Player>, With<SlowTime>)>, mut pace: ResMut<PaceModifier>) {
+	let next = if slowed.is_empty() {
+		1.0
+	} else {
+		SLOW_TIME_PACE
+	};
+	pace.set_if_neq(PaceModifier(next));
+}
+
+/// Coins that come in range stop scrolling with the world and steer towards
+/// the bird until it picks them up.
+fn pull_coins(
+	mut commands: Commands,
+	player: Single<&Transform, (With<Player>, With<Magnet>)>,
+	coins: Query<(Entity, &Transform, &mut Velocity, Has<Scrolling>), With<Coin>>,
+	time: Res<Time>,
+) {
+	let target = player.translation.truncate();
+	for (entity, transform, mut velocity, scrolling) in coins {
+		let offset = target - transform.translation.truncate();
+		if scrolling {
+			if offset.length() > MAGNET_RANGE {
+				continue;
+			}
+			commands.entity(entity).remove::<(Scrolling, Oscillator)>();
+		}
+		let desired = offset.normalize_or_zero() * MAGNET_SPEED;
+		let pulled = Vec2::new(velocity.x, velocity.y)
+			.move_towards(desired, MAGNET_PULL * time.delta_secs());
+		velocity.x = pulled.x;
+		velocity.y = pulled.y;
+	}
+}
+
+fn spawn_hud(mut commands: Commands, theme: Res<UiTheme>) {
+	commands
+		.spawn((
+			PowerUpHud,
+			Node {
+				position_type: PositionType::Absolute,
+				top: Val::Px(80.0),
+				left: SCOREBOARD_TEXT_PADDING,
+				flex_direction: FlexDirection::Column,
+				row_gap: Val::Px(4.0),
+				..default()
+			},
+		))
+		.with_children(|hud| {
+			for power_up in PowerUp::ALL {
+				hud.spawn((
+					EffectIndicator(power_up),
+					Text::default(),
+					theme.font(28.0),
+					TextColor(power_up.color()),
+					Node {
+						display: Display::None,
+						..default()
+					},
+				));
+			}
+		});
+}
+
+/// Shows each active effect with the time it has left.
+fn update_indicators<T: Effect>(
+	effect: Query<&EffectTimer<T>, With<Player>>,
+	locale: Res<Locale>,
+	indicators: Query<(&EffectIndicator, &mut Text, &mut Node)>,
+) {
+	let remaining = effect
+		.single()
+		.ok()
+		.map(|effect| effect.timer.remaining_secs());
+	for (indicator, mut text, mut node) in indicators {
+		if indicator.0 != T::POWER_UP {
+			continue;
+		}
+		match remaining {
+			Some(remaining) => {
+				text.0 = locale.format(
+					"power-up-remaining",
+					&[
+						("power_up", &locale.text(T::POWER_UP.key())),
+						("seconds", &remaining.ceil()),
+					],
+				);
+				node.display = Display::Flex;
+			}
+			None => node.display = Display::None,
+		}
+	}
+}
+
+/// Registers the systems every [`Effect`] needs.
+fn add_effect<T: Effect>(app: &mut App) {
+	app.add_systems(
+		FixedUpdate,
+		expire_effects::<T>.in_set(GameplaySet::Progress),
+	)
+	.add_systems(Update, update_indicators::<T>);
+}
+
+pub struct PowerUpsPlugin;
+
+impl Plugin for PowerUpsPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<PowerUpSchedule>()
+			.add_observer(pick_up_power_up)
+			.add_systems(Startup, spawn_hud)
+			.add_systems(OnEnter(InRun), reset_schedule)
+			.add_systems(
+				FixedUpdate,
+				(
+					spawn_power_ups
+						.after(crate::handle_pipe_spawn)
+						.in_set(GameplaySet::Spawn),
+					slow_time.in_set(GameplaySet::Scoring),
+					pull_coins
+						.after(crate::apply_scrolling)
+						.before(crate::apply_velocity)
+						.in_set(GameplaySet::Physics),
+					expire_effects::<Invulnerable>.in_set(GameplaySet::Progress),
+				),
+			);
+		add_effect::<Shield>(app);
+		add_effect::<SlowTime>(app);
+		add_effect::<Magnet>(app);
+	}
+}
@@ -0,0 +1,399 @@
+//! Player profiles, each with its own save file, settings, stats, ghost and
+//! last replay, so several people can share the game. The profile screen shows
+//! up when the game starts, and with Tab from the main menu, to pick, create
+//! and delete profiles.
+//!
+//! Each profile keeps its files under `profiles/<name>/`. Files from before
+//! there were profiles are taken over by the first one. Names that only differ
+//! in case are taken as the same, since they'd share a directory on some
+//! systems.
+
+use std::sync::RwLock;
+
+use bevy::{
+	input::{
+		keyboard::{Key, KeyboardInput},
+		ButtonState,
+	},
+	prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	cleanup::CleanupOnExit,
+	cli::LaunchOptions,
+	ghost::{BestGhost, GHOST_FILE_NAME},
+	locale::Locale,
+	menu::MenuScreen,
+	replay::{LastReplay, REPLAY_FILE_NAME},
+	save::{copy_file, read_ron, remove_file, write_ron, SaveData, SAVE_FILE_NAME},
+	settings::{Settings, SETTINGS_FILE_NAME},
+	stats::{Stats, STATS_FILE_NAME},
+	theme::UiTheme,
+};
+
+const PROFILES_FILE_NAME: &str = "profiles.ron";
+/// The files kept apart for each profile.
+const PROFILE_FILES: [&str; 5] = [
+	SAVE_FILE_NAME,
+	SETTINGS_FILE_NAME,
+	STATS_FILE_NAME,
+	GHOST_FILE_NAME,
+	REPLAY_FILE_NAME,
+];
+const MAX_NAME_LENGTH: usize = 12;
+
+/// Name of the profile being played, for [`profile_file`]. Read from the list
+/// of profiles the first time it's needed, which is before the plugins have
+/// all been built.
+static CURRENT: RwLock<Option<String>> = RwLock::new(None);
+
+#[derive(Resource, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Profiles {
+	pub names: Vec<String>,
+	/// The profile played last, picked again at the next start.
+	pub current: String,
+}
+
+impl Default for Profiles {
+	fn default() -> Self {
+		Profiles {
+			names: vec!["Player".into()],
+			current: "Player".into(),
+		}
+	}
+}
+
+fn profile_path(name: &str, file_name: &str) -> String {
+	format!("profiles/{name}/{file_name}")
+}
+
+/// The list of profiles. The first time the game runs with profiles, the
+/// files from before go to the first one.
+fn read_profiles() -> Profiles {
+	read_ron(PROFILES_FILE_NAME).unwrap_or_else(|| {
+		let profiles = Profiles::default();
+		for file_name in PROFILE_FILES {
+			copy_file(file_name, &profile_path(&profiles.current, file_name));
+		}
+		write_ron(PROFILES_FILE_NAME, &profiles);
+		profiles
+	})
+}
+
+fn set_current(name: &str) {
+	if let Ok(mut current) = CURRENT.write() {
+		*current = Some(name.to_string());
+	}
+}
+
+/// Where the file `file_name` of the profile being played is kept.
+pub fn profile_file(file_name: &str) -> String {
+	let current = CURRENT.read().ok().and_then(|current| current.clone());
+	let name = current.unwrap_or_else(|| {
+		let name = read_profiles().current;
+		set_current(&name);
+		name
+	});
+	profile_path(&name, file_name)
+}
+
+/// Letters, digits and spaces, so names work as directory names everywhere.
+fn valid_name_char(c: char) -> bool {
+	c.is_ascii_alphanumeric() || c == ' '
+}
+
+/// Names that only differ in case share a directory on Windows and macOS.
+fn same_name(a: &str, b: &str) -> bool {
+	a.eq_ignore_ascii_case(b)
+}
+
+/// Loads everything kept for the profile `name`, which takes over from the
+/// profile played so far. The resources are removed before being inserted
+/// again so they count as added, and aren't written straight back.
+fn switch_profile(commands: &mut Commands, profiles: &mut Profiles, name: &str) {
+	profiles.current = name.to_string();
+	set_current(name);
+	commands.remove_resource::<SaveData>();
+	commands.remove_resource::<Settings>();
+	commands.remove_resource::<Stats>();
+	commands
+		.insert_resource(read_ron::<SaveData>(&profile_file(SAVE_FILE_NAME)).unwrap_or_default());
+	commands.insert_resource(
+		read_ron::<Settings>(&profile_file(SETTINGS_FILE_NAME)).unwrap_or_default(),
+	);
+	commands.insert_resource(read_ron::<Stats>(&profile_file(STATS_FILE_NAME)).unwrap_or_default());
+	commands.insert_resource(BestGhost::load());
+	commands.remove_resource::<LastReplay>();
+	if let Some(replay) = LastReplay::load() {
+		commands.insert_resource(replay);
+	}
+}
+
+#[derive(Resource, Default)]
+struct ProfileScreen {
+	/// Row highlighted, with the one after the profiles for creating one.
+	selected: usize,
+	/// Name being typed for a new profile.
+	naming: Option<String>,
+	/// Set after pressing Delete once, until the deletion is confirmed.
+	confirm_delete: bool,
+}
+
+#[derive(Component)]
+struct ProfileRows;
+
+#[derive(Component)]
+struct ProfileHelp;
+
+fn spawn_profile_screen(
+	mut commands: Commands,
+	mut screen: ResMut<ProfileScreen>,
+	profiles: Res<Profiles>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	*screen = ProfileScreen {
+		selected: profiles
+			.names
+			.iter()
+			.position(|name| *name == profiles.current)
+			.unwrap_or(0),
+		..default()
+	};
+	commands
+		.spawn((
+			CleanupOnExit(MenuScreen::Profiles),
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				row_gap: Val::Px(16.0),
+				..default()
+			},
+			BackgroundColor(theme.screen),
+		))
+		.with_children(|parent| {
+			parent.spawn((Text::new(locale.text("profiles-title")), theme.text(72.0)));
+			parent.spawn((
+				ProfileRows,
+				Node {
+					flex_direction: FlexDirection::Column,
+					align_items: AlignItems::Center,
+					row_gap: Val::Px(8.0),
+					..default()
+				},
+			));
+			parent.spawn((ProfileHelp, Text::default(), theme.text(24.0)));
+		});
+}
+
+/// Redraws the rows, whose number changes as profiles come and go.
+fn refresh_profile_screen(
+	mut commands: Commands,
+	screen: Res<ProfileScreen>,
+	profiles: Res<Profiles>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+	rows: Single<Entity, With<ProfileRows>>,
+	mut help: Single<&mut Text, With<ProfileHelp>>,
+) {
+	commands
+		.entity(*rows)
+		.despawn_related::<Children>()
+		.with_children(|parent| {
+			for (index, name) in profiles.names.iter().enumerate() {
+				let label = if *name == profiles.current {
+					locale.format("profiles-playing", &[("name", name)])
+				} else {
+					name.clone()
+				};
+				parent.spawn((
+					Text::new(label),
+					theme.font(36.0),
+					row_color(&theme, index == screen.selected),
+				));
+			}
+			let new = match &screen.naming {
+				Some(name) => locale.format("profiles-naming", &[("name", name)]),
+				None => locale.text("profiles-new"),
+			};
+			parent.spawn((
+				Text::new(new),
+				theme.font(36.0),
+				row_color(&theme, screen.selected == profiles.names.len()),
+			));
+		});
+	help.0 = match (&screen.naming, screen.confirm_delete) {
+		(Some(_), _) => locale.text("profiles-naming-help"),
+		(None, true) => locale.format(
+			"profiles-delete",
+			&[("name", &profiles.names[screen.selected])],
+		),
+		(None, false) => locale.text("profiles-help"),
+	};
+}
+
+fn row_color(theme: &UiTheme, selected: bool) -> TextColor {
+	TextColor(if selected { theme.accent } else { theme.text })
+}
+
+fn type_name(
+	mut typed: EventReader<KeyboardInput>,
+	mut screen: ResMut<ProfileScreen>,
+	mut profiles: ResMut<Profiles>,
+	mut commands: Commands,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if screen.naming.is_none() {
+		typed.clear();
+		return;
+	}
+	for event in typed.read() {
+		if event.state != ButtonState::Pressed {
+			continue;
+		}
+		let Some(name) = &mut screen.naming else {
+			return;
+		};
+		match &event.logical_key {
+			Key::Character(text) => {
+				for c in text.chars().filter(|c| valid_name_char(*c)) {
+					if name.chars().count() < MAX_NAME_LENGTH {
+						name.push(c);
+					}
+				}
+			}
+			Key::Space if name.chars().count() < MAX_NAME_LENGTH => name.push(' '),
+			Key::Backspace => {
+				name.pop();
+			}
+			Key::Escape => screen.naming = None,
+			Key::Enter => {
+				let name = name.trim().to_string();
+				if name.is_empty() || profiles.names.iter().any(|other| same_name(other, &name)) {
+					continue;
+				}
+				profiles.names.push(name.clone());
+				switch_profile(&mut commands, &mut profiles, &name);
+				next_screen.set(MenuScreen::Main);
+				screen.naming = None;
+			}
+			_ => {}
+		}
+	}
+}
+
+/// Leaves the typing to [`type_name`] while naming a new profile, which runs
+/// right after, so the key that started naming doesn't also end it.
+fn navigate_profiles(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut screen: ResMut<ProfileScreen>,
+	mut profiles: ResMut<Profiles>,
+	mut commands: Commands,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if screen.naming.is_some() {
+		return;
+	}
+	let rows = profiles.names.len() + 1;
+	let step = i32::from(keyboard_input.just_pressed(KeyCode::ArrowDown))
+		- i32::from(keyboard_input.just_pressed(KeyCode::ArrowUp));
+	if step != 0 {
+		screen.selected = (screen.selected as i32 + step).rem_euclid(rows as i32) as usize;
+		screen.confirm_delete = false;
+	}
+	let picked = profiles.names.get(screen.selected).cloned();
+	if keyboard_input.just_pressed(KeyCode::Enter) {
+		match picked {
+			Some(name) => {
+				if name != profiles.current {
+					switch_profile(&mut commands, &mut profiles, &name);
+				}
+				next_screen.set(MenuScreen::Main);
+			}
+			None => screen.naming = Some(String::new()),
+		}
+	}
+	// The profile being played stays, so there's always one.
+	if keyboard_input.just_pressed(KeyCode::Delete) {
+		if let Some(name) = picked.filter(|name| !same_name(name, &profiles.current)) {
+			if screen.confirm_delete {
+				profiles.names.retain(|other| *other != name);
+				// Left alone while another profile still shares them.
+				if !profiles.names.iter().any(|other| same_name(other, &name)) {
+					for file_name in PROFILE_FILES {
+						remove_file(&profile_path(&name, file_name));
+					}
+				}
+				screen.selected = screen.selected.min(profiles.names.len());
+			}
+			screen.confirm_delete = !screen.confirm_delete;
+		}
+	}
+	if keyboard_input.just_pressed(KeyCode::Escape) {
+		next_screen.set(MenuScreen::Main);
+	}
+}
+
+/// Once, the first time the menu shows up, unless the game was started
+/// straight into a run.
+fn pick_profile_at_start(
+	options: Res<LaunchOptions>,
+	mut shown: Local<bool>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if !*shown && options.mode.is_none() {
+		next_screen.set(MenuScreen::Profiles);
+	}
+	*shown = true;
+}
+
+fn open_profiles_on_tab(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::Tab) {
+		next_screen.set(MenuScreen::Profiles);
+	}
+}
+
+fn write_profiles(profiles: Res<Profiles>) {
+	write_ron(PROFILES_FILE_NAME, &*profiles);
+}
+
+pub struct ProfilesPlugin;
+
+impl Plugin for ProfilesPlugin {
+	fn build(&self, app: &mut App) {
+		let profiles = read_profiles();
+		set_current(&profiles.current);
+		app.insert_resource(profiles)
+			.init_resource::<ProfileScreen>()
+			.add_systems(OnEnter(MenuScreen::Main), pick_profile_at_start)
+			.add_systems(OnEnter(MenuScreen::Profiles), spawn_profile_screen)
+			.add_systems(
+				Update,
+				(
+					open_profiles_on_tab.run_if(in_state(MenuScreen::Main)),
+					(
+						navigate_profiles,
+						type_name,
+						refresh_profile_screen.run_if(
+							resource_changed::<ProfileScreen>.or(resource_changed::<Profiles>),
+						),
+					)
+						.chain()
+						.run_if(in_state(MenuScreen::Profiles)),
+				),
+			)
+			.add_systems(
+				Last,
+				write_profiles
+					.run_if(resource_changed::<Profiles>.and(not(resource_added::<Profiles>))),
+			);
+	}
+}
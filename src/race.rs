@@ -0,0 +1,906 @@
+//! Multiplayer races over the network, for 2 to 8 players. One player hosts
+//! with `--host <port>` and the others join with `--join <address:port>`,
+//! which opens the race lobby instead of the main menu.
+//!
+//! Once the host starts a race, everyone flies through the same pipes, seeded
+//! by the host, after a countdown so all birds take off together. The other
+//! birds show up as translucent remotes. The last bird still flying wins the
+//! race, and wins are tallied in the lobby.
+//!
+//! Players send how their bird is doing a few times per second over UDP, and
+//! the host passes it on to everyone else. Each message is a small JSON
+//! datagram, and nothing relies on a single one arriving: the lobby, the race
+//! start and every bird's status are simply sent again and again.
+
+use std::{
+	collections::HashMap,
+	io,
+	net::{SocketAddr, ToSocketAddrs, UdpSocket},
+	time::Duration,
+};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	cleanup::{CleanupOnExit, RunScene},
+	cli::LaunchOptions,
+	daily::apply_fair_rules,
+	get_ready::RunPhase,
+	input::PlayerInput,
+	locale::Locale,
+	menu::MenuScreen,
+	mirror::ScrollDirection,
+	save::SaveData,
+	sprites::{SpriteAnimation, Textures},
+	theme::UiTheme,
+	GameMode, GameScore, GameStates, PendingFlap, Player, PLAYER_SIZE,
+};
+
+const MAX_RACERS: usize = 8;
+const MIN_RACERS: usize = 2;
+/// How often every player sends the status of their bird.
+const SEND_INTERVAL: Duration = Duration::from_millis(50);
+/// How often the host sends the lobby, which also keeps the connection alive.
+const LOBBY_INTERVAL: Duration = Duration::from_secs(1);
+/// Players that haven't been heard from for this long have left.
+const TIMEOUT: f32 = 5.0;
+const COUNTDOWN: f32 = 3.0;
+/// Big enough for any message.
+const MAX_DATAGRAM: usize = 2048;
+const REMOTE_ALPHA: f32 = 0.45;
+/// Remote birds catch up with where they were last seen at this rate, so they
+/// move smoothly between updates.
+const REMOTE_SMOOTHING: f32 = 20.0;
+
+/// Where to race, from the command line, see [`cli`](crate::cli).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaceLink {
+	/// Hosting a race on this UDP port.
+	Host(u16),
+	/// Joining the race hosted at this address.
+	Join(SocketAddr),
+}
+
+impl RaceLink {
+	pub fn parse_join(address: &str) -> Option<Self> {
+		address.to_socket_addrs().ok()?.next().map(RaceLink::Join)
+	}
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Racer {
+	id: u8,
+	name: String,
+	wins: u32,
+}
+
+/// How a player's bird is doing in a race.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+struct Status {
+	id: u8,
+	race: u32,
+	y: f32,
+	score: i64,
+	flying: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+enum Message {
+	/// Sent to the host until it answers with a [`Message::Welcome`].
+	Join {
+		name: String,
+	},
+	Welcome {
+		id: u8,
+	},
+	/// The race already has [`MAX_RACERS`] players.
+	Full,
+	/// Everyone in the lobby, and who won the last race.
+	Lobby {
+		racers: Vec<Racer>,
+		last_win: Option<(u32, u8)>,
+	},
+	/// A race with these players is on.
+	Start {
+		race: u32,
+		seed: u64,
+		racers: Vec<u8>,
+	},
+	Status(Status),
+	Leave {
+		id: u8,
+	},
+}
+
+struct Peer {
+	id: u8,
+	address: SocketAddr,
+	last_heard: f32,
+}
+
+enum Role {
+	Host { peers: Vec<Peer> },
+	Client { host: SocketAddr, last_heard: f32 },
+}
+
+/// The connection to the other players.
+#[derive(Resource)]
+struct Link {
+	socket: UdpSocket,
+	role: Role,
+	send_timer: Timer,
+	lobby_timer: Timer,
+}
+
+impl Link {
+	fn open(link: RaceLink, now: f32) -> io::Result<Self> {
+		let (socket, role) = match link {
+			RaceLink::Host(port) => (
+				UdpSocket::bind(("0.0.0.0", port))?,
+				Role::Host { peers: Vec::new() },
+			),
+			RaceLink::Join(host) => (
+				UdpSocket::bind(("0.0.0.0", 0))?,
+				Role::Client {
+					host,
+					last_heard: now,
+				},
+			),
+		};
+		socket.set_nonblocking(true)?;
+		Ok(Link {
+			socket,
+			role,
+			send_timer: Timer::new(SEND_INTERVAL, TimerMode::Repeating),
+			lobby_timer: Timer::new(LOBBY_INTERVAL, TimerMode::Repeating),
+		})
+	}
+
+	fn is_host(&self) -> bool {
+		matches!(self.role, Role::Host { .. })
+	}
+
+	fn send_to(&self, message: &Message, address: SocketAddr) {
+		let Ok(datagram) = serde_json::to_vec(message) else {
+			return;
+		};
+		// Lost messages are sent again anyway.
+		let _ = self.socket.send_to(&datagram, address);
+	}
+
+	/// From the host to everyone, from anyone else to the host.
+	fn send(&self, message: &Message) {
+		match &self.role {
+			Role::Host { peers } => {
+				for peer in peers {
+					self.send_to(message, peer.address);
+				}
+			}
+			Role::Client { host, .. } => self.send_to(message, *host),
+		}
+	}
+
+	fn receive(&self) -> Option<(Message, SocketAddr)> {
+		let mut buffer = [0; MAX_DATAGRAM];
+		loop {
+			let (length, address) = self.socket.recv_from(&mut buffer).ok()?;
+			if let Ok(message) = serde_json::from_slice(&buffer[..length]) {
+				return Some((message, address));
+			}
+		}
+	}
+}
+
+/// The players and what has happened so far, shown in the lobby.
+#[derive(Resource, Default, Clone, PartialEq)]
+struct RaceLobby {
+	/// Assigned by the host once joined.
+	my_id: Option<u8>,
+	my_name: String,
+	racers: Vec<Racer>,
+	last_win: Option<(u32, u8)>,
+	/// Message of why the connection was lost, if it was.
+	notice: Option<&'static str>,
+}
+
+impl RaceLobby {
+	fn name(&self, id: u8) -> &str {
+		self.racers
+			.iter()
+			.find(|racer| racer.id == id)
+			.map_or("?", |racer| &racer.name)
+	}
+
+	fn is_in(&self, id: u8) -> bool {
+		self.racers.iter().any(|racer| racer.id == id)
+	}
+
+	fn message(&self) -> Message {
+		Message::Lobby {
+			racers: self.racers.clone(),
+			last_win: self.last_win,
+		}
+	}
+}
+
+/// The race being flown, or the last one.
+#[derive(Resource, Default)]
+pub(crate) struct Race {
+	number: u32,
+	pub seed: u64,
+	racers: Vec<u8>,
+	/// Latest status of every bird in the race, this player's included.
+	statuses: HashMap<u8, Status>,
+	/// Started, but this player hasn't taken off yet.
+	pending_start: bool,
+	/// Birds that stopped flying, in order. Only kept by the host.
+	crashed: Vec<u8>,
+}
+
+impl Race {
+	fn is_on(&self) -> bool {
+		self.number > 0
+	}
+
+	fn start_message(&self) -> Message {
+		Message::Start {
+			race: self.number,
+			seed: self.seed,
+			racers: self.racers.clone(),
+		}
+	}
+
+	/// Birds not heard from yet are still getting ready.
+	fn flying(&self) -> Vec<u8> {
+		self.racers
+			.iter()
+			.copied()
+			.filter(|id| {
+				self.statuses
+					.get(id)
+					.is_none_or(|status| status.race != self.number || status.flying)
+			})
+			.collect()
+	}
+}
+
+#[derive(Resource, Deref, DerefMut)]
+struct Countdown(Timer);
+
+#[derive(Component)]
+struct CountdownText;
+
+/// Another player's bird, by id.
+#[derive(Component)]
+struct RemoteBird(u8);
+
+#[derive(Component)]
+struct StandingsText;
+
+#[derive(Component)]
+struct LobbyUi;
+
+fn open_link(
+	mut commands: Commands,
+	options: Res<LaunchOptions>,
+	save: Res<SaveData>,
+	time: Res<Time<Real>>,
+) {
+	let Some(race_link) = options.race else {
+		return;
+	};
+	let link = match Link::open(race_link, time.elapsed_secs()) {
+		Ok(link) => link,
+		Err(err) => {
+			error!("Failed to open a connection for the race: {err}");
+			return;
+		}
+	};
+	let mut lobby = RaceLobby {
+		my_name: save.initials.clone().unwrap_or_default(),
+		..default()
+	};
+	if link.is_host() {
+		lobby.my_id = Some(0);
+		lobby.racers.push(Racer {
+			id: 0,
+			name: racer_name(lobby.my_name.clone(), 0),
+			wins: 0,
+		});
+	}
+	commands.insert_resource(link);
+	commands.insert_resource(lobby);
+	commands.init_resource::<Race>();
+}
+
+/// Players go by the initials they last entered for a high score, or by
+/// number if they never did.
+fn racer_name(name: String, id: u8) -> String {
+	if name.is_empty() {
+		format!("P{}", id + 1)
+	} else {
+		name
+	}
+}
+
+fn open_lobby(lobby: Option<Res<RaceLobby>>, mut next_screen: ResMut<NextState<MenuScreen>>) {
+	if lobby.is_some() {
+		next_screen.set(MenuScreen::Lobby);
+	}
+}
+
+fn receive_messages(
+	mut link: ResMut<Link>,
+	mut lobby: ResMut<RaceLobby>,
+	mut race: ResMut<Race>,
+	time: Res<Time<Real>>,
+) {
+	let now = time.elapsed_secs();
+	// Statuses come in many times a second, the lobby only counts as changed
+	// when something it shows does.
+	let before = lobby.clone();
+	let updated = lobby.bypass_change_detection();
+	while let Some((message, address)) = link.receive() {
+		if link.is_host() {
+			host_message(&mut link, updated, &mut race, message, address, now);
+		} else {
+			client_message(&mut link, updated, &mut race, message, now);
+		}
+	}
+	if *updated != before {
+		lobby.set_changed();
+	}
+}
+
+fn host_message(
+	link: &mut Link,
+	lobby: &mut RaceLobby,
+	race: &mut Race,
+	message: Message,
+	address: SocketAddr,
+	now: f32,
+) {
+	let Role::Host { peers } = &mut link.role else {
+		return;
+	};
+	let sender = peers
+		.iter_mut()
+		.find(|peer| peer.address == address)
+		.map(|peer| {
+			peer.last_heard = now;
+			peer.id
+		});
+	match (message, sender) {
+		(Message::Join { .. }, Some(id)) => link.send_to(&Message::Welcome { id }, address),
+		(Message::Join { name }, None) => {
+			let Some(id) = (1..MAX_RACERS as u8).find(|id| peers.iter().all(|peer| peer.id != *id))
+			else {
+				link.send_to(&Message::Full, address);
+				return;
+			};
+			peers.push(Peer {
+				id,
+				address,
+				last_heard: now,
+			});
+			lobby.racers.push(Racer {
+				id,
+				name: racer_name(name, id),
+				wins: 0,
+			});
+			link.send_to(&Message::Welcome { id }, address);
+			link.send(&lobby.message());
+		}
+		(Message::Status(status), Some(id)) if status.id == id => {
+			if status.race == race.number {
+				race.statuses.insert(status.id, status);
+			}
+			let others = peers
+				.iter()
+				.map(|peer| peer.address)
+				.filter(|other| *other != address)
+				.collect::<Vec<_>>();
+			for other in others {
+				link.send_to(&Message::Status(status), other);
+			}
+		}
+		(Message::Leave { id }, Some(sender)) if id == sender => drop_peer(link, lobby, id),
+		_ => {}
+	}
+}
+
+fn drop_peer(link: &mut Link, lobby: &mut RaceLobby, id: u8) {
+	if let Role::Host { peers } = &mut link.role {
+		peers.retain(|peer| peer.id != id);
+	}
+	lobby.racers.retain(|racer| racer.id != id);
+	link.send(&lobby.message());
+}
+
+fn client_message(
+	link: &mut Link,
+	lobby: &mut RaceLobby,
+	race: &mut Race,
+	message: Message,
+	now: f32,
+) {
+	let Role::Client { last_heard, .. } = &mut link.role else {
+		return;
+	};
+	*last_heard = now;
+	match message {
+		Message::Welcome { id } => lobby.my_id = Some(id),
+		Message::Full => lobby.notice = Some("race-full"),
+		Message::Lobby { racers, last_win } => {
+			lobby.racers = racers;
+			lobby.last_win = last_win;
+		}
+		Message::Start {
+			race: number,
+			seed,
+			racers,
+		} => {
+			let invited = lobby.my_id.is_some_and(|id| racers.contains(&id));
+			if number > race.number && invited {
+				*race = Race {
+					number,
+					seed,
+					racers,
+					pending_start: true,
+					..default()
+				};
+			}
+		}
+		Message::Status(status) if status.race == race.number => {
+			race.statuses.insert(status.id, status);
+		}
+		Message::Leave { id: 0 } => lobby.notice = Some("race-host-left"),
+		_ => {}
+	}
+}
+
+/// How this player's own bird is doing.
+#[derive(SystemParam)]
+struct OwnBird<'w> {
+	mode: Res<'w, GameMode>,
+	state: Res<'w, State<GameStates>>,
+	player: Option<Single<'w, &'static Transform, With<Player>>>,
+	score: Res<'w, GameScore>,
+}
+
+impl OwnBird<'_> {
+	/// Birds are flying while the race runs, from the countdown until they
+	/// crash.
+	fn status(&self, id: u8, race: &Race) -> Status {
+		let in_this_race = race.racers.contains(&id) && *self.mode == GameMode::Race;
+		Status {
+			id,
+			race: race.number,
+			y: self
+				.player
+				.as_ref()
+				.map_or(0.0, |player| player.translation.y),
+			score: **self.score,
+			flying: race.pending_start || (in_this_race && *self.state.get() == GameStates::InGame),
+		}
+	}
+}
+
+fn send_status(
+	mut link: ResMut<Link>,
+	lobby: Res<RaceLobby>,
+	mut race: ResMut<Race>,
+	own_bird: OwnBird,
+	time: Res<Time<Real>>,
+) {
+	let send = link.send_timer.tick(time.delta()).just_finished();
+	let send_lobby = link.lobby_timer.tick(time.delta()).just_finished();
+	let Some(id) = lobby.my_id else {
+		if send {
+			link.send(&Message::Join {
+				name: lobby.my_name.clone(),
+			});
+		}
+		return;
+	};
+	if !send {
+		return;
+	}
+	let status = own_bird.status(id, &race);
+	if race.racers.contains(&id) {
+		race.statuses.insert(id, status);
+	}
+	link.send(&Message::Status(status));
+	if link.is_host() {
+		// Players that missed the start still get to fly, and everyone gets
+		// to know who won.
+		if race.is_on() {
+			link.send(&race.start_message());
+		}
+		if send_lobby {
+			link.send(&lobby.message());
+		}
+	}
+}
+
+fn check_timeouts(
+	mut commands: Commands,
+	mut link: ResMut<Link>,
+	mut lobby: ResMut<RaceLobby>,
+	time: Res<Time<Real>>,
+) {
+	let now = time.elapsed_secs();
+	match &link.role {
+		Role::Host { peers } => {
+			let silent = peers
+				.iter()
+				.filter(|peer| now - peer.last_heard > TIMEOUT)
+				.map(|peer| peer.id)
+				.collect::<Vec<_>>();
+			for id in silent {
+				drop_peer(&mut link, &mut lobby, id);
+			}
+		}
+		Role::Client { last_heard, .. } => {
+			if lobby.notice.is_none() && now - last_heard > TIMEOUT {
+				lobby.notice = Some(if lobby.my_id.is_some() {
+					"race-connection-lost"
+				} else {
+					"race-unreachable"
+				});
+			}
+		}
+	}
+	if lobby.notice.is_some() {
+		commands.remove_resource::<Link>();
+	}
+}
+
+/// The host decides who wins, once at most one bird is left flying. If the
+/// last ones crash together, the last to be heard from wins.
+fn decide_winner(link: Res<Link>, mut lobby: ResMut<RaceLobby>, mut race: ResMut<Race>) {
+	let decided = lobby
+		.last_win
+		.is_some_and(|(number, _)| number == race.number);
+	if !link.is_host() || !race.is_on() || decided {
+		return;
+	}
+	let mut flying = race.flying();
+	flying.retain(|id| lobby.is_in(*id));
+	let left = race
+		.racers
+		.iter()
+		.copied()
+		.filter(|id| !flying.contains(id) && !race.crashed.contains(id))
+		.collect::<Vec<_>>();
+	race.crashed.extend(left);
+	let winner = match flying[..] {
+		[] => race.crashed.last().copied(),
+		[last] => Some(last),
+		_ => return,
+	};
+	let Some(winner) = winner else {
+		return;
+	};
+	lobby.last_win = Some((race.number, winner));
+	if let Some(racer) = lobby.racers.iter_mut().find(|racer| racer.id == winner) {
+		racer.wins += 1;
+	}
+	link.send(&lobby.message());
+}
+
+fn start_race_on_confirm(
+	input: PlayerInput,
+	link: Option<Res<Link>>,
+	lobby: Res<RaceLobby>,
+	mut race: ResMut<Race>,
+) {
+	let is_host = link.is_some_and(|link| link.is_host());
+	if !is_host || lobby.racers.len() < MIN_RACERS || !input.confirm_pressed() {
+		return;
+	}
+	*race = Race {
+		number: race.number + 1,
+		seed: rand::rng().random(),
+		racers: lobby.racers.iter().map(|racer| racer.id).collect(),
+		pending_start: true,
+		..default()
+	};
+}
+
+fn take_off_when_started(
+	mut race: ResMut<Race>,
+	mut mode: ResMut<GameMode>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if race.pending_start {
+		race.pending_start = false;
+		*mode = GameMode::Race;
+		next_state.set(GameStates::InGame);
+	}
+}
+
+/// The next race only starts from the lobby.
+fn back_to_lobby_when_started(race: Res<Race>, mut next_state: ResMut<NextState<GameStates>>) {
+	if race.pending_start {
+		next_state.set(GameStates::Menu);
+	}
+}
+
+fn leave_lobby(
+	mut commands: Commands,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	link: Option<Res<Link>>,
+	lobby: Res<RaceLobby>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if !keyboard_input.just_pressed(KeyCode::Escape) {
+		return;
+	}
+	if let (Some(link), Some(id)) = (link, lobby.my_id) {
+		link.send(&Message::Leave { id });
+	}
+	commands.remove_resource::<Link>();
+	commands.remove_resource::<RaceLobby>();
+	commands.remove_resource::<Race>();
+	next_screen.set(MenuScreen::Main);
+}
+
+fn spawn_lobby(
+	mut commands: Commands,
+	link: Option<Res<Link>>,
+	lobby: Res<RaceLobby>,
+	options: Res<LaunchOptions>,
+	locale: Res<Locale>,
+	ui: Query<Entity, With<LobbyUi>>,
+	theme: Res<UiTheme>,
+) {
+	for entity in &ui {
+		commands.entity(entity).despawn();
+	}
+	let is_host = link.as_ref().is_some_and(|link| link.is_host());
+	let status = match (lobby.notice, options.race, lobby.my_id) {
+		(Some(notice), ..) => locale.text(notice),
+		(None, Some(RaceLink::Host(port)), _) => locale.format("race-hosting", &[("port", &port)]),
+		(None, Some(RaceLink::Join(address)), None) => {
+			locale.format("race-joining", &[("address", &address)])
+		}
+		(None, Some(RaceLink::Join(address)), Some(_)) => {
+			locale.format("race-joined", &[("address", &address)])
+		}
+		(None, None, _) => String::new(),
+	};
+	let mut lines = vec![status];
+	if let Some((number, winner)) = lobby.last_win {
+		lines.push(locale.format(
+			"race-last-win",
+			&[("race", &number), ("player", &lobby.name(winner))],
+		));
+	}
+	let hint = locale.text(if lobby.notice.is_some() {
+		"back"
+	} else if !is_host {
+		"race-waiting-for-host"
+	} else if lobby.racers.len() < MIN_RACERS {
+		"race-waiting-for-players"
+	} else {
+		"race-start"
+	});
+	commands
+		.spawn((
+			LobbyUi,
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				row_gap: Val::Px(16.0),
+				..default()
+			},
+		))
+		.with_children(|parent| {
+			parent.spawn((Text::new(locale.text("race-lobby")), theme.text(72.0)));
+			for line in lines {
+				parent.spawn((Text::new(line), theme.text(32.0)));
+			}
+			for racer in &lobby.racers {
+				let key = if lobby.my_id == Some(racer.id) {
+					"race-racer-you"
+				} else {
+					"race-racer"
+				};
+				parent.spawn((
+					Text::new(
+						locale.format(key, &[("player", &racer.name), ("wins", &racer.wins)]),
+					),
+					theme.font(32.0),
+					TextColor(racer_color(racer.id)),
+				));
+			}
+			parent.spawn((Text::new(hint), theme.text(24.0)));
+		});
+}
+
+fn despawn_lobby(mut commands: Commands, ui: Query<Entity, With<LobbyUi>>) {
+	for entity in &ui {
+		commands.entity(entity).despawn();
+	}
+}
+
+fn racer_color(id: u8) -> Color {
+	Color::hsl(f32::from(id) * 360.0 / MAX_RACERS as f32, 0.8, 0.65)
+}
+
+/// Everyone races by the same rules, whatever their settings.
+fn spawn_race(
+	mut commands: Commands,
+	textures: Res<Textures>,
+	lobby: Res<RaceLobby>,
+	race: Res<Race>,
+	direction: Res<ScrollDirection>,
+	theme: Res<UiTheme>,
+) {
+	for &id in race.racers.iter().filter(|id| Some(**id) != lobby.my_id) {
+		let mut sprite = textures.bird_sprite(PLAYER_SIZE);
+		sprite.flip_x = direction.flip_x();
+		sprite.color = racer_color(id).with_alpha(REMOTE_ALPHA);
+		commands.spawn((
+			RemoteBird(id),
+			RunScene,
+			sprite,
+			SpriteAnimation::bird(),
+			Transform::from_translation(direction.player_start().with_z(0.5)),
+		));
+	}
+	commands.spawn((
+		StandingsText,
+		RunScene,
+		Text::default(),
+		theme.text(24.0),
+		Node {
+			position_type: PositionType::Absolute,
+			top: Val::Px(40.0),
+			left: Val::Px(5.0),
+			..default()
+		},
+	));
+	commands.insert_resource(Countdown(Timer::from_seconds(COUNTDOWN, TimerMode::Once)));
+	commands.spawn((
+		CountdownText,
+		CleanupOnExit(GameStates::InGame),
+		Text::default(),
+		theme.text(120.0),
+		Node {
+			position_type: PositionType::Absolute,
+			top: Val::Percent(15.0),
+			width: Val::Percent(100.0),
+			justify_content: JustifyContent::Center,
+			..default()
+		},
+		TextLayout::new_with_justify(JustifyText::Center),
+	));
+}
+
+/// Everyone takes off together, with a flap on the first tick like any run.
+fn count_down(
+	mut commands: Commands,
+	mut countdown: ResMut<Countdown>,
+	text: Single<(Entity, &mut Text), With<CountdownText>>,
+	mut pending_flap: ResMut<PendingFlap>,
+	mut next_phase: ResMut<NextState<RunPhase>>,
+	time: Res<Time<Real>>,
+) {
+	let (entity, mut text) = text.into_inner();
+	countdown.tick(time.delta());
+	text.0 = (countdown.remaining_secs().ceil() as u32).to_string();
+	if countdown.finished() {
+		commands.entity(entity).despawn();
+		pending_flap.press(time.elapsed_secs());
+		next_phase.set(RunPhase::Flying);
+	}
+}
+
+fn move_remote_birds(
+	mut commands: Commands,
+	race: Res<Race>,
+	birds: Query<(Entity, &RemoteBird, &mut Transform)>,
+	time: Res<Time>,
+) {
+	let smoothing = 1.0 - (-REMOTE_SMOOTHING * time.delta_secs()).exp();
+	for (entity, bird, mut transform) in birds {
+		let Some(status) = race
+			.statuses
+			.get(&bird.0)
+			.filter(|status| status.race == race.number)
+		else {
+			continue;
+		};
+		if status.flying {
+			transform.translation.y = transform.translation.y.lerp(status.y, smoothing);
+		} else {
+			commands.entity(entity).despawn();
+		}
+	}
+}
+
+fn update_standings(
+	lobby: Res<RaceLobby>,
+	race: Res<Race>,
+	locale: Res<Locale>,
+	mut text: Single<&mut Text, With<StandingsText>>,
+) {
+	let flying = race.flying();
+	let mut lines = vec![locale.format(
+		"race-standings",
+		&[
+			("race", &race.number),
+			("flying", &flying.len()),
+			("racers", &race.racers.len()),
+		],
+	)];
+	for &id in &race.racers {
+		let score = race.statuses.get(&id).map_or(0, |status| status.score);
+		let key = if flying.contains(&id) {
+			"race-flying"
+		} else {
+			"race-out"
+		};
+		lines.push(locale.format(key, &[("player", &lobby.name(id)), ("score", &score)]));
+	}
+	if let Some((number, winner)) = lobby.last_win.filter(|(number, _)| *number == race.number) {
+		lines.push(if Some(winner) == lobby.my_id {
+			locale.text("race-you-win")
+		} else {
+			locale.format(
+				"race-win",
+				&[("race", &number), ("player", &lobby.name(winner))],
+			)
+		});
+	}
+	text.0 = lines.join("\n");
+}
+
+pub struct RacePlugin;
+
+impl Plugin for RacePlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(OnExit(GameStates::Loading), open_link)
+			.add_systems(OnEnter(MenuScreen::Main), open_lobby)
+			.add_systems(OnEnter(MenuScreen::Lobby), spawn_lobby)
+			.add_systems(OnExit(MenuScreen::Lobby), despawn_lobby)
+			.add_systems(
+				OnEnter(GameStates::InGame),
+				(apply_fair_rules, spawn_race).run_if(resource_equals(GameMode::Race)),
+			)
+			.add_systems(
+				Update,
+				(
+					(receive_messages, send_status, decide_winner, check_timeouts)
+						.chain()
+						.run_if(resource_exists::<Link>),
+					(
+						spawn_lobby.run_if(resource_exists_and_changed::<RaceLobby>),
+						start_race_on_confirm,
+						take_off_when_started,
+						leave_lobby,
+					)
+						.chain()
+						.run_if(in_state(MenuScreen::Lobby).and(resource_exists::<RaceLobby>)),
+					back_to_lobby_when_started.run_if(
+						resource_exists::<Race>
+							.and(in_state(GameStates::InGame).or(in_state(GameStates::GameOver))),
+					),
+					count_down.run_if(
+						in_state(RunPhase::GetReady)
+							.and(resource_equals(GameMode::Race))
+							.and(resource_exists::<Countdown>),
+					),
+					(move_remote_birds, update_standings)
+						.run_if(resource_equals(GameMode::Race).and(resource_exists::<RaceLobby>)),
+				),
+			);
+	}
+}
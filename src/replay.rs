@@ -0,0 +1,311 @@
+//! Recording of live runs and playback of the last one.
+//!
+//! Runs are deterministic given their seed, rules such as the difficulty, and
+//! the ticks on which the player flapped, so that is all a replay needs to
+//! store. Playback runs
+//! the regular simulation in [`GameStates::Replay`], feeding the recorded
+//! flaps in place of player input.
+//!
+//! The last replay can be exported with F, in the format of
+//! [`replay_file`](crate::replay_file), and replay files dropped onto the
+//! window are played back right away.
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	cleanup::CleanupOnExit,
+	config::GameConfig,
+	crash::Crash,
+	difficulty::{DifficultyPreset, DifficultySettings},
+	locale::Locale,
+	menu::MenuScreen,
+	mutators::Mutators,
+	profiles::profile_file,
+	replay_file,
+	save::{read_ron, storage, write_ron, Storage},
+	seed::GameRng,
+	theme::UiTheme,
+	tween::FadeOut,
+	weather::Weather,
+	GameMode, GameScore, GameStates, GameplaySet, PendingFlap, RunTick,
+};
+
+pub(crate) const REPLAY_FILE_NAME: &str = "last_replay.ron";
+const NOTICE_DURATION: f32 = 4.0;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Replay {
+	pub seed: u64,
+	pub difficulty: DifficultyPreset,
+	/// Run ticks on which the player flapped, in increasing order.
+	pub flaps: Vec<u32>,
+	/// Final score of the recorded run.
+	pub score: i64,
+	/// Kind of run that was recorded, which can change how it plays out.
+	#[serde(default)]
+	pub mode: GameMode,
+	#[serde(default)]
+	pub mutators: Mutators,
+	#[serde(default)]
+	pub weather: Weather,
+}
+
+/// Flaps recorded so far in the current live run.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct Recording(Vec<u32>);
+
+/// The most recently finished live run, also kept on disk between sessions.
+#[derive(Resource, Deref)]
+pub(crate) struct LastReplay(Replay);
+
+impl LastReplay {
+	/// The last replay of the profile being played, if it has one.
+	pub(crate) fn load() -> Option<Self> {
+		read_ron(&profile_file(REPLAY_FILE_NAME)).map(LastReplay)
+	}
+}
+
+/// A replay from a file dropped onto the window, watched instead of the
+/// [`LastReplay`] next time playback starts.
+#[derive(Resource, Deref)]
+struct ImportedReplay(Replay);
+
+/// The replay being played back and the index of the next flap to feed in.
+#[derive(Resource)]
+struct Playback {
+	replay: Replay,
+	next_flap: usize,
+}
+
+#[derive(Component)]
+struct ReplayText;
+
+/// Tells how an export or import went, fading out after a while.
+fn show_notice(commands: &mut Commands, message: String, theme: &UiTheme) {
+	commands.spawn((
+		Text::new(message),
+		theme.text(24.0),
+		Node {
+			position_type: PositionType::Absolute,
+			bottom: Val::Px(5.0),
+			right: Val::Px(5.0),
+			..default()
+		},
+		FadeOut::new(NOTICE_DURATION),
+		GlobalZIndex(1),
+	));
+}
+
+fn start_recording(mut recording: ResMut<Recording>) {
+	recording.clear();
+}
+
+fn record_flap(
+	mut recording: ResMut<Recording>,
+	pending_flap: Res<PendingFlap>,
+	run_tick: Res<RunTick>,
+) {
+	if pending_flap.is_pending() {
+		recording.push(**run_tick);
+	}
+}
+
+/// Everything besides the seed that decided how the run played out.
+#[derive(SystemParam)]
+struct RunRules<'w> {
+	settings: ResMut<'w, DifficultySettings>,
+	mode: ResMut<'w, GameMode>,
+	mutators: ResMut<'w, Mutators>,
+	weather: ResMut<'w, Weather>,
+}
+
+impl RunRules<'_> {
+	/// Sets the rules `replay` was played by.
+	fn apply(&mut self, replay: &Replay, config: &GameConfig) {
+		*self.settings = DifficultySettings::new(replay.difficulty, config);
+		*self.mode = replay.mode;
+		*self.mutators = replay.mutators;
+		*self.weather = replay.weather;
+	}
+}
+
+fn finish_recording(
+	mut commands: Commands,
+	mut recording: ResMut<Recording>,
+	game_rng: Res<GameRng>,
+	score: Res<GameScore>,
+	rules: RunRules,
+) {
+	let replay = Replay {
+		seed: game_rng.seed,
+		difficulty: rules.settings.preset,
+		flaps: std::mem::take(&mut **recording),
+		score: **score,
+		mode: *rules.mode,
+		mutators: *rules.mutators,
+		weather: *rules.weather,
+	};
+	write_ron(&profile_file(REPLAY_FILE_NAME), &replay);
+	commands.insert_resource(LastReplay(replay));
+}
+
+fn start_playback(
+	mut commands: Commands,
+	last_replay: Option<Res<LastReplay>>,
+	imported: Option<Res<ImportedReplay>>,
+	config: Res<GameConfig>,
+	mut rules: RunRules,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	let (replay, message) = match (imported, last_replay) {
+		(Some(imported), _) => {
+			commands.remove_resource::<ImportedReplay>();
+			(imported.0.clone(), "replay-imported")
+		}
+		(None, Some(last_replay)) => (last_replay.0.clone(), "replay-playing"),
+		(None, None) => return,
+	};
+	// Set right away rather than through commands, the player is spawned with
+	// these settings when entering `InRun` right after this.
+	rules.apply(&replay, &config);
+	commands.insert_resource(GameRng::new(replay.seed));
+	commands.spawn((
+		ReplayText,
+		CleanupOnExit(GameStates::Replay),
+		Text::new(locale.format(message, &[("score", &replay.score)])),
+		theme.text(28.0),
+		Node {
+			position_type: PositionType::Absolute,
+			bottom: Val::Px(5.0),
+			left: Val::Px(5.0),
+			..default()
+		},
+	));
+	commands.insert_resource(Playback {
+		replay,
+		next_flap: 0,
+	});
+}
+
+fn play_back_flaps(
+	mut playback: ResMut<Playback>,
+	mut pending_flap: ResMut<PendingFlap>,
+	run_tick: Res<RunTick>,
+	time: Res<Time<Real>>,
+) {
+	let Playback { replay, next_flap } = &mut *playback;
+	while replay.flaps.get(*next_flap) == Some(&**run_tick) {
+		pending_flap.press(time.elapsed_secs());
+		*next_flap += 1;
+	}
+}
+
+fn watch_replay_on_p(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyP) {
+		next_state.set(GameStates::Replay);
+	}
+}
+
+fn export_on_f(
+	mut commands: Commands,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	last_replay: Res<LastReplay>,
+	config: Res<GameConfig>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	if !keyboard_input.just_pressed(KeyCode::KeyF) {
+		return;
+	}
+	let file_name = replay_file::file_name(&last_replay);
+	let storage = storage();
+	let message = match replay_file::encode(&last_replay, &config)
+		.and_then(|contents| storage.write(&file_name, &contents))
+	{
+		Ok(()) => locale.format(
+			"replay-exported",
+			&[("location", &storage.location(&file_name))],
+		),
+		Err(err) => {
+			error!("Failed to export {file_name}: {err}");
+			locale.text("replay-export-failed")
+		}
+	};
+	show_notice(&mut commands, message, &theme);
+}
+
+/// Plays back a replay file dropped onto the window.
+fn import_dropped_replay(
+	mut commands: Commands,
+	mut drops: EventReader<FileDragAndDrop>,
+	mut next_state: ResMut<NextState<GameStates>>,
+	config: Res<GameConfig>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	for dropped in drops.read() {
+		let FileDragAndDrop::DroppedFile { path_buf, .. } = dropped else {
+			continue;
+		};
+		let imported = std::fs::read_to_string(path_buf)
+			.map_err(|err| err.to_string())
+			.and_then(|contents| replay_file::decode(&contents, &config));
+		match imported {
+			Ok(replay) => {
+				commands.insert_resource(ImportedReplay(replay));
+				next_state.set(GameStates::Replay);
+			}
+			Err(err) => {
+				warn!("Ignoring dropped file {}: {err}", path_buf.display());
+				show_notice(
+					&mut commands,
+					locale.format("replay-unreadable", &[("error", &err)]),
+					&theme,
+				);
+			}
+		}
+	}
+}
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+	fn build(&self, app: &mut App) {
+		if let Some(replay) = LastReplay::load() {
+			app.insert_resource(replay);
+		}
+		app.init_resource::<Recording>()
+			.add_systems(
+				OnEnter(GameStates::InGame),
+				start_recording.run_if(crate::counts_for_progress),
+			)
+			.add_systems(
+				OnExit(GameStates::InGame),
+				finish_recording.run_if(crate::counts_for_progress),
+			)
+			.add_systems(OnEnter(GameStates::Replay), start_playback)
+			.add_systems(
+				FixedUpdate,
+				(
+					record_flap
+						.run_if(in_state(GameStates::InGame).and(crate::counts_for_progress)),
+					play_back_flaps.run_if(in_state(GameStates::Replay)),
+				)
+					.in_set(GameplaySet::Input)
+					.before(crate::apply_flap),
+			)
+			.add_systems(
+				Update,
+				(
+					(watch_replay_on_p, export_on_f).run_if(resource_exists::<LastReplay>),
+					import_dropped_replay,
+				)
+					.run_if(in_state(MenuScreen::Main).or(in_state(Crash::Over))),
+			);
+	}
+}
@@ -0,0 +1,178 @@
+//! The format replays are shared in, as files anyone can drop onto the game
+//! window to watch.
+//!
+//! A file starts with a line naming the format and its version, followed by
+//! the replay as compact RON. Flaps are stored as the ticks between them, which
+//! keeps the numbers small, and a checksum catches files that were damaged or
+//! edited along the way. A hash of the [`GameConfig`] the run was played with
+//! keeps out replays from installs tuned differently, which would play out
+//! differently here.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	config::GameConfig, difficulty::DifficultyPreset, mutators::Mutators, replay::Replay,
+	weather::Weather, GameMode,
+};
+
+const HEADER: &str = "flappy-replay";
+const VERSION: u32 = 2;
+pub const EXTENSION: &str = "flappy";
+
+#[derive(Serialize, Deserialize)]
+struct ReplayFile {
+	seed: u64,
+	difficulty: DifficultyPreset,
+	mode: GameMode,
+	mutators: Mutators,
+	weather: Weather,
+	score: i64,
+	/// Hash of the game config the run was played with.
+	config: u64,
+	/// Ticks since the previous flap, or since the start of the run for the
+	/// first one.
+	flaps: Vec<u32>,
+	checksum: u64,
+}
+
+/// FNV-1a of a value written as compact RON.
+fn hash<T: Serialize>(value: &T) -> Result<u64, String> {
+	let contents = ron::to_string(value).map_err(|err| err.to_string())?;
+	Ok(contents.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+		(hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+	}))
+}
+
+impl ReplayFile {
+	/// Hash of the file with the checksum left at zero.
+	fn compute_checksum(&self) -> Result<u64, String> {
+		hash(&ReplayFile {
+			checksum: 0,
+			flaps: self.flaps.clone(),
+			..*self
+		})
+	}
+}
+
+/// File name a replay is exported under, unique enough not to overwrite other
+/// exported runs.
+pub fn file_name(replay: &Replay) -> String {
+	format!("replay-{}-{:016x}.{EXTENSION}", replay.score, replay.seed)
+}
+
+pub fn encode(replay: &Replay, config: &GameConfig) -> Result<String, String> {
+	let mut previous = 0;
+	let flaps = replay
+		.flaps
+		.iter()
+		.map(|&tick| {
+			let gap = tick - previous;
+			previous = tick;
+			gap
+		})
+		.collect();
+	let mut file = ReplayFile {
+		seed: replay.seed,
+		difficulty: replay.difficulty,
+		mode: replay.mode,
+		mutators: replay.mutators,
+		weather: replay.weather,
+		score: replay.score,
+		config: hash(config)?,
+		flaps,
+		checksum: 0,
+	};
+	file.checksum = file.compute_checksum()?;
+	let body = ron::to_string(&file).map_err(|err| err.to_string())?;
+	Ok(format!("{HEADER} {VERSION}\n{body}\n"))
+}
+
+/// Refuses replays played with another config than `config`.
+pub fn decode(contents: &str, config: &GameConfig) -> Result<Replay, String> {
+	let (header, body) = contents.split_once('\n').ok_or("not a replay file")?;
+	let version = header
+		.strip_prefix(HEADER)
+		.and_then(|version| version.trim().parse::<u32>().ok())
+		.ok_or("not a replay file")?;
+	if version != VERSION {
+		return Err(format!("unsupported replay version {version}"));
+	}
+	let file: ReplayFile = ron::from_str(body).map_err(|err| err.to_string())?;
+	if file.compute_checksum()? != file.checksum {
+		return Err("the replay is damaged, its checksum doesn't match".to_string());
+	}
+	if file.config != hash(config)? {
+		return Err("the replay was played with a different game config".to_string());
+	}
+	let mut tick = 0u32;
+	let flaps = file
+		.flaps
+		.iter()
+		.map(|&gap| {
+			tick = tick.checked_add(gap)?;
+			Some(tick)
+		})
+		.collect::<Option<_>>()
+		.ok_or("the replay flaps later than a run can last")?;
+	Ok(Replay {
+		seed: file.seed,
+		difficulty: file.difficulty,
+		flaps,
+		score: file.score,
+		mode: file.mode,
+		mutators: file.mutators,
+		weather: file.weather,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn replay() -> Replay {
+		Replay {
+			seed: 1234,
+			difficulty: DifficultyPreset::Hard,
+			flaps: vec![0, 24, 60, 61],
+			score: 42,
+			mode: GameMode::Endless,
+			mutators: Mutators {
+				mirror: true,
+				..Mutators::default()
+			},
+			weather: Weather::default(),
+		}
+	}
+
+	#[test]
+	fn round_trip() {
+		let config = GameConfig::default();
+		let contents = encode(&replay(), &config).unwrap();
+		assert!(contents.starts_with(&format!("{HEADER} {VERSION}\n")));
+		assert_eq!(decode(&contents, &config).unwrap(), replay());
+	}
+
+	#[test]
+	fn tampered_checksum() {
+		let config = GameConfig::default();
+		let contents = encode(&replay(), &config).unwrap();
+		let tampered = contents.replace("score:42,", "score:43,");
+		assert_ne!(tampered, contents);
+		assert!(decode(&tampered, &config).unwrap_err().contains("checksum"));
+	}
+
+	#[test]
+	fn other_config() {
+		let contents = encode(&replay(), &GameConfig::default()).unwrap();
+		let mut config = GameConfig::default();
+		config.normal.gravity += 1.0;
+		assert!(decode(&contents, &config).is_err());
+	}
+
+	#[test]
+	fn other_version() {
+		let contents = encode(&replay(), &GameConfig::default()).unwrap();
+		let older = contents.replacen(&format!("{HEADER} {VERSION}"), &format!("{HEADER} 1"), 1);
+		assert!(decode(&older, &GameConfig::default()).is_err());
+	}
+}
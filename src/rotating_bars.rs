@@ -0,0 +1,49 @@
+//! Bars spinning in the middle of some gaps, to be timed rather than just flown
+//! through.
+
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::RunScene, day_night::Tinted, offscreen::DespawnOffscreen, pipe_patterns::Gap,
+	sprites::Textures, GameplaySet, Obstacle, Scrolling, PIPE_TINT,
+};
+
+const BAR_THICKNESS: f32 = 16.0;
+/// Length of a bar compared to the gap it spins in. Standing upright it leaves
+/// too little room on either side for the bird.
+const BAR_LENGTH_SCALE: f32 = 0.75;
+/// Turns per second.
+const BAR_SPIN: f32 = 0.25;
+
+#[derive(Component)]
+#[require(Obstacle, Scrolling, RunScene)]
+pub struct SpinningBar;
+
+/// A bar spinning around the middle of `gap`, starting out level.
+pub fn spinning_bar(textures: &Textures, x: f32, gap: &Gap) -> impl Bundle {
+	let length = gap.height * BAR_LENGTH_SCALE;
+	(
+		SpinningBar,
+		Tinted(PIPE_TINT),
+		textures.pipe_sprite(Vec2::new(BAR_THICKNESS, length)),
+		DespawnOffscreen::new(length),
+		Transform::from_xyz(x, gap.bottom + gap.height / 2.0, 0.0)
+			.with_rotation(Quat::from_rotation_z(TAU / 4.0)),
+	)
+}
+
+fn spin_bars(query: Query<&mut Transform, With<SpinningBar>>, time: Res<Time>) {
+	for mut transform in query {
+		transform.rotate_z(BAR_SPIN * TAU * time.delta_secs());
+	}
+}
+
+pub struct RotatingBarsPlugin;
+
+impl Plugin for RotatingBarsPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(FixedUpdate, spin_bars.in_set(GameplaySet::Physics));
+	}
+}
@@ -0,0 +1,97 @@
+//! Rumbles connected gamepads when the bird flaps, scores or crashes, scaled by
+//! the strength picked in the settings.
+
+use std::time::Duration;
+
+use bevy::{
+	input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest},
+	prelude::*,
+};
+
+use crate::{settings::Settings, GameStates, GameplayEvent, PlayerDiedEvent};
+
+/// A single buzz, at full strength before the setting is applied.
+struct Pulse {
+	strong: f32,
+	weak: f32,
+	seconds: f32,
+}
+
+const FLAP: Pulse = Pulse {
+	strong: 0.0,
+	weak: 0.25,
+	seconds: 0.05,
+};
+const SCORE: Pulse = Pulse {
+	strong: 0.2,
+	weak: 0.5,
+	seconds: 0.1,
+};
+const LIFE_LOST: Pulse = Pulse {
+	strong: 0.6,
+	weak: 0.6,
+	seconds: 0.25,
+};
+const CRASH: Pulse = Pulse {
+	strong: 1.0,
+	weak: 1.0,
+	seconds: 0.4,
+};
+
+fn rumble_enabled(settings: Res<Settings>) -> bool {
+	settings.rumble > 0.0
+}
+
+/// Only the strongest pulse of a frame is sent, so a crash isn't cut short by
+/// the flap right before it.
+fn rumble_gamepads(
+	mut events: EventReader<GameplayEvent>,
+	mut deaths: EventReader<PlayerDiedEvent>,
+	gamepads: Query<Entity, With<Gamepad>>,
+	settings: Res<Settings>,
+	mut requests: EventWriter<GamepadRumbleRequest>,
+) {
+	let mut pulse = None;
+	for event in events.read() {
+		let next = match event {
+			GameplayEvent::Flapped(_) => &FLAP,
+			GameplayEvent::PipePassed(_) => &SCORE,
+			GameplayEvent::LifeLost => &LIFE_LOST,
+			_ => continue,
+		};
+		if pulse.is_none_or(|pulse: &Pulse| next.strong + next.weak > pulse.strong + pulse.weak) {
+			pulse = Some(next);
+		}
+	}
+	if deaths.read().count() > 0 {
+		pulse = Some(&CRASH);
+	}
+	let Some(pulse) = pulse else {
+		return;
+	};
+	for gamepad in &gamepads {
+		requests.write(GamepadRumbleRequest::Add {
+			gamepad,
+			intensity: GamepadRumbleIntensity {
+				strong_motor: pulse.strong * settings.rumble,
+				weak_motor: pulse.weak * settings.rumble,
+			},
+			duration: Duration::from_secs_f32(pulse.seconds),
+		});
+	}
+}
+
+pub struct RumblePlugin;
+
+impl Plugin for RumblePlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			Update,
+			rumble_gamepads.run_if(
+				in_state(GameStates::InGame)
+					.and(rumble_enabled)
+					.and(any_with_component::<Gamepad>),
+			),
+		);
+	}
+}
@@ -0,0 +1,143 @@
+//! A plain simulation of pipes with a single gap, for birds flown outside of a
+//! regular run, like the [`evolution`](crate::evolution) flock and the
+//! [`split_screen`](crate::split_screen) players. None of the extras of a run
+//! come along, and the birds are left to the modes using it.
+//!
+//! The pipes move while a [`Sandbox`] exists, in [`SandboxSet`].
+
+use bevy::{
+	math::bounding::{Aabb2d, IntersectsVolume},
+	prelude::*,
+};
+use rand::Rng;
+
+use crate::{
+	collision::sprite_collider, difficulty::Difficulty, ground::ground_top,
+	mirror::ScrollDirection, pipe_patterns::Gap, pipe_pool::PipeSpawner, play_area::PlayArea,
+	seed::GameRng, Pipe, Velocity, PIPE_WIDTH, PLAYER_SIZE,
+};
+
+#[derive(Resource)]
+pub(crate) struct Sandbox {
+	/// Simulated since the start.
+	pub ticks: u32,
+	/// By the birds, which all fly at the same x.
+	pub pipes_passed: u32,
+	spawn_timer: Timer,
+}
+
+impl Sandbox {
+	pub fn new(difficulty: &Difficulty) -> Self {
+		let mut spawn_timer = Timer::new(difficulty.pipe_spawn_interval, TimerMode::Repeating);
+		// The first column comes in right away.
+		spawn_timer.set_elapsed(difficulty.pipe_spawn_interval);
+		Sandbox {
+			ticks: 0,
+			pipes_passed: 0,
+			spawn_timer,
+		}
+	}
+}
+
+/// Spawns and moves the pipes, in `FixedUpdate`. Birds should fly after it.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct SandboxSet;
+
+/// Whether a bird hit a pipe, the ground or the top of the screen, so none can
+/// get away with flying over the pipes.
+pub(crate) fn hits_anything<'a>(
+	bird: Aabb2d,
+	play_area: &PlayArea,
+	pipes: impl IntoIterator<Item = (&'a Transform, &'a Sprite)>,
+) -> bool {
+	bird.min.y < ground_top(play_area)
+		|| bird.max.y > play_area.max.y
+		|| pipes
+			.into_iter()
+			.any(|(transform, sprite)| bird.intersects(&sprite_collider(transform, sprite)))
+}
+
+/// Plain gravity, and flaps straight up.
+pub(crate) fn fly(
+	transform: &mut Transform,
+	velocity: &mut Velocity,
+	flap: bool,
+	jump: f32,
+	gravity: f32,
+	delta: f32,
+) {
+	if flap {
+		velocity.y = jump;
+	}
+	velocity.y -= gravity * delta;
+	transform.translation.y += velocity.y * delta;
+}
+
+/// Takes all the pipes out and stops the simulation.
+pub(crate) fn clear_sandbox(
+	mut commands: Commands,
+	mut pipes: PipeSpawner,
+	query: Query<Entity, With<Pipe>>,
+) {
+	for entity in &query {
+		pipes.recycle(entity);
+	}
+	commands.remove_resource::<Sandbox>();
+}
+
+fn spawn_columns(
+	mut pipes: PipeSpawner,
+	mut sandbox: ResMut<Sandbox>,
+	mut rng: ResMut<GameRng>,
+	difficulty: Res<Difficulty>,
+	play_area: Res<PlayArea>,
+	direction: Res<ScrollDirection>,
+	time: Res<Time>,
+) {
+	sandbox.ticks += 1;
+	if !sandbox.spawn_timer.tick(time.delta()).just_finished() {
+		return;
+	}
+	let lowest = ground_top(&play_area) + PLAYER_SIZE.y;
+	let highest = (play_area.max.y - PLAYER_SIZE.y - difficulty.pipe_gap).max(lowest);
+	let gap = Gap {
+		bottom: rng.random_range(lowest..=highest),
+		height: difficulty.pipe_gap,
+		safe: true,
+		coin: false,
+		spinning_bar: false,
+	};
+	pipes.spawn_column(direction.entry_x(&play_area), play_area.height(), &[gap]);
+}
+
+fn scroll_pipes(
+	mut query: Query<(&mut Transform, &mut Pipe)>,
+	mut sandbox: ResMut<Sandbox>,
+	difficulty: Res<Difficulty>,
+	direction: Res<ScrollDirection>,
+	time: Res<Time>,
+) {
+	let bird_x = direction.player_start().x;
+	for (mut transform, mut pipe) in &mut query {
+		transform.translation.x += direction.sign() * difficulty.pipe_speed * time.delta_secs();
+		let passed = direction.distance_past(transform.translation.x, bird_x);
+		if pipe.give_score && passed > (PIPE_WIDTH + PLAYER_SIZE.x) / 2.0 {
+			pipe.give_score = false;
+			sandbox.pipes_passed += 1;
+		}
+	}
+}
+
+pub struct SandboxPlugin;
+
+impl Plugin for SandboxPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			FixedUpdate,
+			(spawn_columns, scroll_pipes)
+				.chain()
+				.in_set(SandboxSet)
+				.run_if(resource_exists::<Sandbox>),
+		);
+	}
+}
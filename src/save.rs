@@ -0,0 +1,214 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+	achievements::AchievementRecord, daily::DailyRecord, high_scores::HighScores, medals::Medal,
+	profiles::profile_file, shop::ShopRecord, skins::Skin, time_attack::TimeAttackRecord,
+};
+
+pub(crate) const SAVE_FILE_NAME: &str = "save.ron";
+
+/// Set for simulated runs, which neither read nor touch the player's files.
+static DETACHED: AtomicBool = AtomicBool::new(false);
+
+/// Everything that should survive a restart of the game. Written back to disk
+/// whenever it changes, kept apart for each [profile](crate::profiles).
+#[derive(Resource, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct SaveData {
+	pub high_scores: HighScores,
+	/// Best score of a live hardcore run, kept apart from the endless ones.
+	pub hardcore_best: i64,
+	/// Best medal of a live endless run.
+	pub best_medal: Option<Medal>,
+	pub daily: DailyRecord,
+	pub achievements: AchievementRecord,
+	/// Coins collected over every live run, to spend on cosmetics.
+	pub coins: u64,
+	/// Initials last entered for a high score, which online scores are
+	/// submitted under.
+	pub initials: Option<String>,
+	/// Best star rating of each campaign stage, by index. Zero for stages
+	/// never cleared.
+	pub stage_stars: Vec<u8>,
+	pub time_attack: TimeAttackRecord,
+	/// Skin the bird wears, see [`skins`](crate::skins).
+	pub skin: Skin,
+	pub shop: ShopRecord,
+}
+
+/// A place to keep save files between sessions, keyed by file name.
+pub trait Storage {
+	fn read(&self, file_name: &str) -> Option<String>;
+	fn write(&self, file_name: &str, contents: &str) -> Result<(), String>;
+	fn remove(&self, file_name: &str) -> Result<(), String>;
+	/// Where a file is kept, to tell the player.
+	fn location(&self, file_name: &str) -> String;
+}
+
+/// Files in the platform's data directory.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FileStorage;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStorage {
+	fn path(file_name: &str) -> Option<std::path::PathBuf> {
+		Some(dirs::data_dir()?.join("flappy").join(file_name))
+	}
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Storage for FileStorage {
+	fn read(&self, file_name: &str) -> Option<String> {
+		std::fs::read_to_string(Self::path(file_name)?).ok()
+	}
+
+	fn write(&self, file_name: &str, contents: &str) -> Result<(), String> {
+		let path = Self::path(file_name).ok_or("no data directory available")?;
+		path.parent()
+			.map_or(Ok(()), std::fs::create_dir_all)
+			.and_then(|()| std::fs::write(&path, contents))
+			.map_err(|err| format!("{}: {err}", path.display()))
+	}
+
+	fn remove(&self, file_name: &str) -> Result<(), String> {
+		let path = Self::path(file_name).ok_or("no data directory available")?;
+		match std::fs::remove_file(&path) {
+			Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+				Err(format!("{}: {err}", path.display()))
+			}
+			_ => Ok(()),
+		}
+	}
+
+	fn location(&self, file_name: &str) -> String {
+		Self::path(file_name)
+			.map_or_else(|| file_name.to_string(), |path| path.display().to_string())
+	}
+}
+
+/// The browser's `localStorage`, with keys prefixed to stay out of the way of
+/// anything else hosted on the same origin.
+#[cfg(target_arch = "wasm32")]
+pub struct LocalStorage;
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+	fn storage() -> Option<web_sys::Storage> {
+		web_sys::window()?.local_storage().ok()?
+	}
+
+	fn key(file_name: &str) -> String {
+		format!("flappy/{file_name}")
+	}
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Storage for LocalStorage {
+	fn read(&self, file_name: &str) -> Option<String> {
+		Self::storage()?.get_item(&Self::key(file_name)).ok()?
+	}
+
+	fn write(&self, file_name: &str, contents: &str) -> Result<(), String> {
+		Self::storage()
+			.ok_or("localStorage is not available")?
+			.set_item(&Self::key(file_name), contents)
+			.map_err(|err| format!("{err:?}"))
+	}
+
+	fn remove(&self, file_name: &str) -> Result<(), String> {
+		Self::storage()
+			.ok_or("localStorage is not available")?
+			.remove_item(&Self::key(file_name))
+			.map_err(|err| format!("{err:?}"))
+	}
+
+	fn location(&self, file_name: &str) -> String {
+		format!("localStorage key {}", Self::key(file_name))
+	}
+}
+
+/// The storage for the platform the game was built for.
+pub fn storage() -> impl Storage {
+	#[cfg(not(target_arch = "wasm32"))]
+	return FileStorage;
+	#[cfg(target_arch = "wasm32")]
+	return LocalStorage;
+}
+
+/// Stops [`read_ron`] from reading and [`write_ron`] from writing anything for
+/// the rest of the session, so everything starts from its defaults.
+pub fn detach_from_disk() {
+	DETACHED.store(true, Ordering::Relaxed);
+}
+
+/// Reads a RON save file, if it exists and is valid.
+pub fn read_ron<T: DeserializeOwned>(file_name: &str) -> Option<T> {
+	if DETACHED.load(Ordering::Relaxed) {
+		return None;
+	}
+	let contents = storage().read(file_name)?;
+	ron::from_str(&contents)
+		.inspect_err(|err| warn!("Ignoring unreadable file {file_name}: {err}"))
+		.ok()
+}
+
+/// Writes a value as a RON save file, logging any failure.
+pub fn write_ron<T: Serialize>(file_name: &str, value: &T) {
+	if DETACHED.load(Ordering::Relaxed) {
+		return;
+	}
+	let contents = match ron::ser::to_string_pretty(value, default()) {
+		Ok(contents) => contents,
+		Err(err) => {
+			error!("Failed to serialize {file_name}: {err}");
+			return;
+		}
+	};
+	if let Err(err) = storage().write(file_name, &contents) {
+		error!("Failed to write {file_name}: {err}");
+	}
+}
+
+/// Copies a save file over, if it exists, logging any failure.
+pub fn copy_file(from: &str, to: &str) {
+	if DETACHED.load(Ordering::Relaxed) {
+		return;
+	}
+	let storage = storage();
+	if let Some(contents) = storage.read(from) {
+		if let Err(err) = storage.write(to, &contents) {
+			error!("Failed to write {to}: {err}");
+		}
+	}
+}
+
+/// Removes a save file, logging any failure.
+pub fn remove_file(file_name: &str) {
+	if DETACHED.load(Ordering::Relaxed) {
+		return;
+	}
+	if let Err(err) = storage().remove(file_name) {
+		error!("Failed to remove {file_name}: {err}");
+	}
+}
+
+fn write_save(save: Res<SaveData>) {
+	write_ron(&profile_file(SAVE_FILE_NAME), &*save);
+}
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+	fn build(&self, app: &mut App) {
+		app.insert_resource(
+			read_ron::<SaveData>(&profile_file(SAVE_FILE_NAME)).unwrap_or_default(),
+		)
+		.add_systems(
+			Last,
+			write_save.run_if(resource_changed::<SaveData>.and(not(resource_added::<SaveData>))),
+		);
+	}
+}
@@ -0,0 +1,54 @@
+//! "+1" popups rising from the bird whenever it scores.
+
+use bevy::prelude::*;
+
+use crate::{
+	offscreen::DespawnOffscreen,
+	settings::Settings,
+	theme::UiTheme,
+	tween::{Drift, FadeOut},
+	GameScore, InRun, Player,
+};
+
+const POPUP_OFFSET: Vec2 = Vec2::new(0.0, 32.0);
+const POPUP_RISE_SPEED: f32 = 80.0;
+const POPUP_DURATION: f32 = 0.5;
+const POPUP_FONT_SIZE: f32 = 32.0;
+
+fn popup_on_score(
+	mut commands: Commands,
+	score: Res<GameScore>,
+	mut last_score: Local<i64>,
+	settings: Res<Settings>,
+	players: Query<&Transform, With<Player>>,
+	theme: Res<UiTheme>,
+) {
+	let gained = **score - *last_score;
+	*last_score = **score;
+	if gained <= 0 {
+		return;
+	}
+	for transform in &players {
+		let mut popup = commands.spawn((
+			Text2d::new(format!("+{gained}")),
+			theme.text(POPUP_FONT_SIZE),
+			Transform::from_translation(
+				(transform.translation.truncate() + POPUP_OFFSET).extend(6.0),
+			),
+			FadeOut::new(POPUP_DURATION),
+			// About as wide as it is tall.
+			DespawnOffscreen::new(POPUP_FONT_SIZE),
+		));
+		if !settings.reduced_motion {
+			popup.insert(Drift(Vec2::Y * POPUP_RISE_SPEED));
+		}
+	}
+}
+
+pub struct ScorePopupPlugin;
+
+impl Plugin for ScorePopupPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(Update, popup_on_score.run_if(in_state(InRun)));
+	}
+}
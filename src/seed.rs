@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+	cli::LaunchOptions, daily, locale::Locale, race::Race, theme::UiTheme, GameMode, GameStates,
+};
+
+const SEED_TEXT_PADDING: Val = Val::Px(5.0);
+
+/// Seed configuration for new runs. Without a fixed seed every run picks a
+/// fresh random one.
+#[derive(Resource)]
+pub struct SeedSettings {
+	pub fixed_seed: Option<u64>,
+}
+
+/// Fixed with `--seed <n>` on the command line, see [`cli`](crate::cli).
+impl FromWorld for SeedSettings {
+	fn from_world(world: &mut World) -> Self {
+		SeedSettings {
+			fixed_seed: world
+				.get_resource::<LaunchOptions>()
+				.and_then(|options| options.seed),
+		}
+	}
+}
+
+/// Random number generator for everything that should be identical between
+/// two runs with the same seed, such as the pipe layout.
+#[derive(Resource, Deref, DerefMut)]
+pub struct GameRng {
+	pub seed: u64,
+	#[deref]
+	rng: ChaCha8Rng,
+}
+
+impl GameRng {
+	pub fn new(seed: u64) -> Self {
+		GameRng {
+			seed,
+			rng: ChaCha8Rng::seed_from_u64(seed),
+		}
+	}
+}
+
+#[derive(Component)]
+struct SeedText;
+
+fn reseed(
+	mut commands: Commands,
+	settings: Res<SeedSettings>,
+	mode: Res<GameMode>,
+	race: Option<Res<Race>>,
+) {
+	let seed = match *mode {
+		GameMode::Daily => u64::from(daily::today()),
+		// Picked by the host, for everyone to race through the same pipes.
+		GameMode::Race => race.map_or(0, |race| race.seed),
+		// Every attempt at a stage plays out the same.
+		GameMode::Stage(index) => u64::from(index),
+		GameMode::Endless
+		| GameMode::Lives
+		| GameMode::Zen
+		| GameMode::Hardcore
+		| GameMode::Playtest
+		| GameMode::Autopilot
+		| GameMode::Versus
+		| GameMode::TimeAttack(_) => settings.fixed_seed.unwrap_or_else(|| rand::rng().random()),
+	};
+	commands.insert_resource(GameRng::new(seed));
+}
+
+fn spawn_seed_text(mut commands: Commands, theme: Res<UiTheme>) {
+	commands.spawn((
+		SeedText,
+		Text::default(),
+		theme.text(24.0),
+		Node {
+			position_type: PositionType::Absolute,
+			top: SEED_TEXT_PADDING,
+			right: SEED_TEXT_PADDING,
+			..default()
+		},
+	));
+}
+
+fn update_seed_text(
+	game_rng: Res<GameRng>,
+	locale: Res<Locale>,
+	mut seed_text: Single<&mut Text, With<SeedText>>,
+) {
+	seed_text.0 = locale.format("seed", &[("seed", &game_rng.seed)]);
+}
+
+pub struct SeedPlugin;
+
+impl Plugin for SeedPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<SeedSettings>()
+			.add_systems(Startup, spawn_seed_text)
+			.add_systems(OnEnter(GameStates::InGame), reseed)
+			.add_systems(
+				Update,
+				update_seed_text.run_if(resource_exists_and_changed::<GameRng>),
+			);
+	}
+}
@@ -0,0 +1,569 @@
+//! Player preferences, kept between sessions, and the screen to change them.
+
+use bevy::{
+	audio::Volume,
+	prelude::*,
+	window::{PrimaryWindow, WindowMode},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	cli::LaunchOptions,
+	config::GameConfig,
+	difficulty::{DifficultyPreset, DifficultySettings},
+	input::{key_name, reserved_key, InputAction, KeyBindings},
+	locale::Locale,
+	menu::{preset_name, MenuScreen},
+	mutators::Mutators,
+	palette::Palette,
+	play_area::ScreenScaling,
+	profiles::profile_file,
+	save::{read_ron, write_ron},
+	theme::{ThemeName, UiTheme},
+	touch::IS_MOBILE,
+	weather::Weather,
+	GameStates,
+};
+
+pub(crate) const SETTINGS_FILE_NAME: &str = "settings.ron";
+/// Step for volumes and other settings between 0 and 1.
+const FRACTION_STEP: f32 = 0.1;
+
+#[derive(Resource, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+	/// Code of the language of the interface, see [`locale`](crate::locale).
+	pub language: String,
+	pub master_volume: f32,
+	pub music_volume: f32,
+	/// Difficulty of endless runs, also picked with the number keys in the menu.
+	pub difficulty: DifficultyPreset,
+	/// Mutators for runs started from the menu, toggled there.
+	pub mutators: Mutators,
+	pub weather: Weather,
+	pub fullscreen: bool,
+	pub screen_scaling: ScreenScaling,
+	/// Look of the interface, see [`theme`](crate::theme).
+	pub theme: ThemeName,
+	/// Strength of the camera shake on crashing, between 0 and 1.
+	pub screen_shake: f32,
+	/// Strength of gamepad rumble, between 0 and 1.
+	pub rumble: f32,
+	/// Keeps the bird level and its wings still, and turns off particles and
+	/// screen shake.
+	pub reduced_motion: bool,
+	/// Leaves out white flashes, like the one on crashing.
+	pub reduce_flashing: bool,
+	/// Leaves a trail of afterimages behind the bird.
+	pub motion_trail: bool,
+	/// Draws the game like on an old CRT screen, see [`crt`](crate::crt).
+	pub crt_filter: bool,
+	/// Colors for color blind players, see [`palette`](crate::palette).
+	pub palette: Palette,
+	/// Plain, outlined bird and pipes over a black background, see
+	/// [`high_contrast`](crate::high_contrast).
+	pub high_contrast: bool,
+	/// Reads the score out loud, see [`speech`](crate::speech). Needs the
+	/// `tts` feature.
+	pub announce_score: bool,
+	/// Shows hints during the next run, turned off once the player passes a pipe.
+	pub show_tutorial: bool,
+	pub controls: KeyBindings,
+}
+
+impl Default for Settings {
+	fn default() -> Self {
+		Settings {
+			language: "en".into(),
+			master_volume: 1.0,
+			music_volume: 0.5,
+			difficulty: DifficultyPreset::default(),
+			mutators: Mutators::default(),
+			weather: Weather::default(),
+			fullscreen: IS_MOBILE,
+			screen_scaling: ScreenScaling::default(),
+			theme: ThemeName::default(),
+			screen_shake: 1.0,
+			rumble: 1.0,
+			reduced_motion: false,
+			reduce_flashing: false,
+			motion_trail: true,
+			crt_filter: false,
+			palette: Palette::default(),
+			high_contrast: false,
+			announce_score: false,
+			show_tutorial: true,
+			controls: KeyBindings::default(),
+		}
+	}
+}
+
+impl Settings {
+	/// Volume the music should play at, with the master volume applied.
+	pub fn music_level(&self) -> f32 {
+		self.master_volume * self.music_volume
+	}
+}
+
+/// Run condition for purely cosmetic movement.
+pub fn motion_allowed(settings: Res<Settings>) -> bool {
+	!settings.reduced_motion
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SettingsRow {
+	Language,
+	MasterVolume,
+	MusicVolume,
+	Difficulty,
+	Weather,
+	WindowMode,
+	ScreenScaling,
+	Theme,
+	ScreenShake,
+	Rumble,
+	ReducedMotion,
+	ReduceFlashing,
+	MotionTrail,
+	CrtFilter,
+	Colors,
+	HighContrast,
+	AnnounceScore,
+	Tutorial,
+	Binding(InputAction),
+}
+
+impl SettingsRow {
+	const ALL: [SettingsRow; 21] = [
+		SettingsRow::Language,
+		SettingsRow::MasterVolume,
+		SettingsRow::MusicVolume,
+		SettingsRow::Difficulty,
+		SettingsRow::Weather,
+		SettingsRow::WindowMode,
+		SettingsRow::ScreenScaling,
+		SettingsRow::Theme,
+		SettingsRow::ScreenShake,
+		SettingsRow::Rumble,
+		SettingsRow::ReducedMotion,
+		SettingsRow::ReduceFlashing,
+		SettingsRow::MotionTrail,
+		SettingsRow::CrtFilter,
+		SettingsRow::Colors,
+		SettingsRow::HighContrast,
+		SettingsRow::AnnounceScore,
+		SettingsRow::Tutorial,
+		SettingsRow::Binding(InputAction::Flap),
+		SettingsRow::Binding(InputAction::Restart),
+		SettingsRow::Binding(InputAction::Pause),
+	];
+
+	fn label(self, settings: &Settings, locale: &Locale) -> String {
+		let value = |key: &str, value: String| locale.format(key, &[("value", &value)]);
+		let percent = |key: &str, fraction: f32| {
+			locale.format(key, &[("percent", &format!("{:.0}", fraction * 100.0))])
+		};
+		match self {
+			SettingsRow::Language => locale.format(
+				"settings-language",
+				&[("language", &locale.language_name(&settings.language))],
+			),
+			SettingsRow::MasterVolume => percent("settings-master-volume", settings.master_volume),
+			SettingsRow::MusicVolume => percent("settings-music-volume", settings.music_volume),
+			SettingsRow::Difficulty => value(
+				"settings-difficulty",
+				preset_name(settings.difficulty, locale),
+			),
+			SettingsRow::Weather => value(
+				"settings-weather",
+				locale.option("weather", settings.weather.name()),
+			),
+			SettingsRow::WindowMode => value(
+				"settings-window-mode",
+				locale.text(if settings.fullscreen {
+					"window-fullscreen"
+				} else {
+					"window-windowed"
+				}),
+			),
+			SettingsRow::ScreenScaling => value(
+				"settings-screen-scaling",
+				locale.option("scaling", settings.screen_scaling.name()),
+			),
+			SettingsRow::ScreenShake => percent("settings-screen-shake", settings.screen_shake),
+			SettingsRow::Rumble => percent("settings-rumble", settings.rumble),
+			SettingsRow::ReducedMotion => value(
+				"settings-reduced-motion",
+				locale.on_off(settings.reduced_motion),
+			),
+			SettingsRow::ReduceFlashing => value(
+				"settings-reduce-flashing",
+				locale.on_off(settings.reduce_flashing),
+			),
+			SettingsRow::MotionTrail => value(
+				"settings-motion-trail",
+				locale.on_off(settings.motion_trail),
+			),
+			SettingsRow::CrtFilter => {
+				value("settings-crt-filter", locale.on_off(settings.crt_filter))
+			}
+			SettingsRow::Theme => value(
+				"settings-theme",
+				locale.option("theme", settings.theme.name()),
+			),
+			SettingsRow::Colors => value(
+				"settings-colors",
+				locale.option("palette", settings.palette.name()),
+			),
+			SettingsRow::HighContrast => value(
+				"settings-high-contrast",
+				locale.on_off(settings.high_contrast),
+			),
+			SettingsRow::AnnounceScore => value(
+				"settings-announce-score",
+				if cfg!(feature = "tts") {
+					locale.on_off(settings.announce_score)
+				} else {
+					locale.text("unavailable")
+				},
+			),
+			SettingsRow::Tutorial => {
+				value("settings-tutorial", locale.on_off(settings.show_tutorial))
+			}
+			SettingsRow::Binding(action) => locale.format(
+				"settings-binding",
+				&[
+					("action", &locale.option("action", action.name())),
+					("key", &key_name(settings.controls.key(action))),
+				],
+			),
+		}
+	}
+
+	/// Changes the setting one step up or down.
+	fn adjust(self, settings: &mut Settings, step: i32, locale: &Locale) {
+		let step_fraction = |value: &mut f32| {
+			let steps = (*value / FRACTION_STEP).round() + step as f32;
+			*value = (steps * FRACTION_STEP).clamp(0.0, 1.0);
+		};
+		match self {
+			SettingsRow::Language => {
+				let languages = locale.languages().collect::<Vec<_>>();
+				let count = languages.len().max(1) as i32;
+				let current = languages
+					.iter()
+					.position(|code| *code == settings.language)
+					.unwrap_or_default() as i32;
+				if let Some(code) = languages.get((current + step).rem_euclid(count) as usize) {
+					settings.language = code.to_string();
+				}
+			}
+			SettingsRow::MasterVolume => step_fraction(&mut settings.master_volume),
+			SettingsRow::MusicVolume => step_fraction(&mut settings.music_volume),
+			SettingsRow::ScreenShake => step_fraction(&mut settings.screen_shake),
+			SettingsRow::Rumble => step_fraction(&mut settings.rumble),
+			SettingsRow::Difficulty => {
+				let presets = DifficultyPreset::ALL;
+				let current = presets
+					.iter()
+					.position(|preset| *preset == settings.difficulty)
+					.unwrap_or_default();
+				let next = current.saturating_add_signed(step as isize);
+				settings.difficulty = presets[next.min(presets.len() - 1)];
+			}
+			SettingsRow::Weather => {
+				let count = Weather::ALL.len() as i32;
+				let current = Weather::ALL
+					.iter()
+					.position(|weather| *weather == settings.weather)
+					.unwrap_or_default() as i32;
+				settings.weather = Weather::ALL[(current + step).rem_euclid(count) as usize];
+			}
+			SettingsRow::WindowMode => settings.fullscreen = !settings.fullscreen,
+			SettingsRow::ScreenScaling => {
+				settings.screen_scaling = match settings.screen_scaling {
+					ScreenScaling::Letterbox => ScreenScaling::Fill,
+					ScreenScaling::Fill => ScreenScaling::Letterbox,
+				}
+			}
+			SettingsRow::Theme => {
+				let count = ThemeName::ALL.len() as i32;
+				let current = ThemeName::ALL
+					.iter()
+					.position(|theme| *theme == settings.theme)
+					.unwrap_or_default() as i32;
+				settings.theme = ThemeName::ALL[(current + step).rem_euclid(count) as usize];
+			}
+			SettingsRow::ReducedMotion => settings.reduced_motion = !settings.reduced_motion,
+			SettingsRow::ReduceFlashing => settings.reduce_flashing = !settings.reduce_flashing,
+			SettingsRow::MotionTrail => settings.motion_trail = !settings.motion_trail,
+			SettingsRow::CrtFilter => settings.crt_filter = !settings.crt_filter,
+			SettingsRow::Colors => {
+				let count = Palette::ALL.len() as i32;
+				let current = Palette::ALL
+					.iter()
+					.position(|palette| *palette == settings.palette)
+					.unwrap_or_default() as i32;
+				settings.palette = Palette::ALL[(current + step).rem_euclid(count) as usize];
+			}
+			SettingsRow::HighContrast => settings.high_contrast = !settings.high_contrast,
+			SettingsRow::AnnounceScore => {
+				if cfg!(feature = "tts") {
+					settings.announce_score = !settings.announce_score;
+				}
+			}
+			SettingsRow::Tutorial => settings.show_tutorial = !settings.show_tutorial,
+			// Changed by pressing the new key instead, see `navigate_settings`.
+			SettingsRow::Binding(_) => {}
+		}
+	}
+}
+
+/// Index of the highlighted row in [`SettingsRow::ALL`].
+#[derive(Resource, Default, Deref, DerefMut)]
+struct SelectedRow(usize);
+
+/// The action waiting for a key to be pressed, if any.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct Rebinding(Option<InputAction>);
+
+#[derive(Component)]
+struct SettingsUi;
+
+#[derive(Component)]
+struct SettingsRowText(SettingsRow);
+
+/// Text that only changes with the language, by message key.
+#[derive(Component)]
+struct Caption(&'static str);
+
+fn spawn_settings(
+	mut commands: Commands,
+	mut selected: ResMut<SelectedRow>,
+	mut rebinding: ResMut<Rebinding>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	**selected = 0;
+	**rebinding = None;
+	commands
+		.spawn((
+			SettingsUi,
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				row_gap: Val::Px(16.0),
+				..default()
+			},
+			BackgroundColor(theme.screen),
+		))
+		.with_children(|parent| {
+			parent.spawn((
+				Caption("settings-title"),
+				Text::new(locale.text("settings-title")),
+				theme.text(72.0),
+			));
+			for row in SettingsRow::ALL {
+				parent.spawn((SettingsRowText(row), Text::default(), theme.text(36.0)));
+			}
+			parent.spawn((
+				Caption("settings-help"),
+				Text::new(locale.text("settings-help")),
+				theme.text(24.0),
+			));
+		});
+}
+
+fn despawn_settings(mut commands: Commands, ui: Single<Entity, With<SettingsUi>>) {
+	commands.entity(*ui).despawn();
+}
+
+fn navigate_settings(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut selected: ResMut<SelectedRow>,
+	mut rebinding: ResMut<Rebinding>,
+	mut settings: ResMut<Settings>,
+	locale: Res<Locale>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	// Escape cancels, other keys the menus need are ignored.
+	if let Some(action) = **rebinding {
+		if keyboard_input.just_pressed(KeyCode::Escape) {
+			**rebinding = None;
+		} else if let Some(&key) = keyboard_input
+			.get_just_pressed()
+			.find(|key| !reserved_key(**key))
+		{
+			settings.controls.bind(action, key);
+			**rebinding = None;
+		}
+		return;
+	}
+	let rows = SettingsRow::ALL.len();
+	if keyboard_input.just_pressed(KeyCode::ArrowUp) {
+		**selected = (**selected + rows - 1) % rows;
+	}
+	if keyboard_input.just_pressed(KeyCode::ArrowDown) {
+		**selected = (**selected + 1) % rows;
+	}
+	let step = i32::from(keyboard_input.just_pressed(KeyCode::ArrowRight))
+		- i32::from(keyboard_input.just_pressed(KeyCode::ArrowLeft));
+	if step != 0 {
+		SettingsRow::ALL[**selected].adjust(&mut settings, step, &locale);
+	}
+	if keyboard_input.just_pressed(KeyCode::Enter) {
+		if let SettingsRow::Binding(action) = SettingsRow::ALL[**selected] {
+			**rebinding = Some(action);
+		}
+	}
+	if keyboard_input.just_pressed(KeyCode::Escape) {
+		next_screen.set(MenuScreen::Main);
+	}
+}
+
+fn translate_captions(locale: Res<Locale>, captions: Query<(&Caption, &mut Text)>) {
+	for (caption, mut text) in captions {
+		text.0 = locale.text(caption.0);
+	}
+}
+
+fn update_settings_rows(
+	settings: Res<Settings>,
+	selected: Res<SelectedRow>,
+	rebinding: Res<Rebinding>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+	rows: Query<(&SettingsRowText, &mut Text, &mut TextColor)>,
+) {
+	for (row, mut text, mut color) in rows {
+		let is_selected = SettingsRow::ALL[**selected] == row.0;
+		let prefix = if is_selected { "> " } else { "" };
+		let label = match row.0 {
+			SettingsRow::Binding(action) if **rebinding == Some(action) => locale.format(
+				"settings-rebinding",
+				&[("action", &locale.option("action", action.name()))],
+			),
+			_ => row.0.label(&settings, &locale),
+		};
+		text.0 = format!("{prefix}{label}");
+		color.0 = if is_selected {
+			theme.accent
+		} else {
+			theme.text
+		};
+	}
+}
+
+/// Endless runs use the preferred difficulty, even after a daily challenge or
+/// a replay changed it.
+fn apply_difficulty(
+	settings: Res<Settings>,
+	config: Res<GameConfig>,
+	mut difficulty: ResMut<DifficultySettings>,
+) {
+	if difficulty.preset != settings.difficulty {
+		*difficulty = DifficultySettings::new(settings.difficulty, &config);
+	}
+}
+
+/// Same as the difficulty, the daily challenge and replays set their own.
+fn apply_mutators(settings: Res<Settings>, mut mutators: ResMut<Mutators>) {
+	mutators.set_if_neq(settings.mutators);
+}
+
+fn apply_weather(settings: Res<Settings>, mut weather: ResMut<Weather>) {
+	weather.set_if_neq(settings.weather);
+}
+
+fn toggle_fullscreen_on_f11(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut settings: ResMut<Settings>,
+) {
+	if keyboard_input.just_pressed(KeyCode::F11) {
+		settings.fullscreen = !settings.fullscreen;
+	}
+}
+
+/// A window size given on the command line keeps the game windowed.
+fn apply_window_mode(
+	settings: Res<Settings>,
+	options: Res<LaunchOptions>,
+	mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+	window.mode = if settings.fullscreen && options.window_size.is_none() {
+		WindowMode::BorderlessFullscreen(MonitorSelection::Primary)
+	} else {
+		WindowMode::Windowed
+	};
+}
+
+/// Only affects sounds started from now on, the music follows the settings on
+/// its own.
+fn apply_master_volume(
+	settings: Res<Settings>,
+	options: Res<LaunchOptions>,
+	mut global_volume: ResMut<GlobalVolume>,
+) {
+	global_volume.volume = Volume::Linear(if options.mute {
+		0.0
+	} else {
+		settings.master_volume
+	});
+}
+
+fn write_settings(settings: Res<Settings>) {
+	write_ron(&profile_file(SETTINGS_FILE_NAME), &*settings);
+}
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+	fn build(&self, app: &mut App) {
+		app.insert_resource(
+			read_ron::<Settings>(&profile_file(SETTINGS_FILE_NAME)).unwrap_or_default(),
+		)
+		.init_resource::<SelectedRow>()
+		.init_resource::<Rebinding>()
+		.init_resource::<Mutators>()
+		.add_systems(
+			OnEnter(GameStates::Menu),
+			(apply_difficulty, apply_mutators, apply_weather),
+		)
+		.add_systems(OnEnter(MenuScreen::Settings), spawn_settings)
+		.add_systems(OnExit(MenuScreen::Settings), despawn_settings)
+		.add_systems(
+			Update,
+			(
+				(
+					navigate_settings,
+					update_settings_rows.run_if(
+						resource_changed::<Settings>
+							.or(resource_changed::<SelectedRow>)
+							.or(resource_changed::<Rebinding>)
+							.or(resource_changed::<Locale>)
+							.or(resource_changed::<UiTheme>),
+					),
+					translate_captions.run_if(resource_changed::<Locale>),
+				)
+					.chain()
+					.run_if(in_state(MenuScreen::Settings)),
+				toggle_fullscreen_on_f11,
+				// Runs bring their own difficulty, mutators and weather, which
+				// changing a setting mid-run, like with F11, mustn't undo.
+				(apply_difficulty, apply_mutators, apply_weather)
+					.run_if(resource_changed::<Settings>.and(in_state(GameStates::Menu))),
+				(apply_window_mode, apply_master_volume)
+					.run_if(resource_changed::<Settings>)
+					.after(toggle_fullscreen_on_f11),
+			),
+		)
+		.add_systems(
+			Last,
+			write_settings
+				.run_if(resource_changed::<Settings>.and(not(resource_added::<Settings>))),
+		);
+	}
+}
@@ -0,0 +1,272 @@
+//! Spending the coins picked up during runs on cosmetics, opened with Y from
+//! the main menu: skins for the bird, colors for its trail and themes for the
+//! pipes. What's been bought and what's equipped is kept in the save file.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	cleanup::CleanupOnExit, locale::Locale, menu::MenuScreen, save::SaveData, skins::Skin,
+	theme::UiTheme,
+};
+
+/// Color of the afterimages of the motion trail, see [`trail`](crate::trail).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailColor {
+	/// Afterimages that look just like the bird.
+	#[default]
+	Bird,
+	Shadow,
+	Gold,
+	Ice,
+	Ember,
+}
+
+impl TrailColor {
+	pub fn name(self) -> &'static str {
+		match self {
+			TrailColor::Bird => "Bird",
+			TrailColor::Shadow => "Shadow",
+			TrailColor::Gold => "Gold",
+			TrailColor::Ice => "Ice",
+			TrailColor::Ember => "Ember",
+		}
+	}
+
+	/// Tint of the afterimages, `None` to leave them as the bird is.
+	pub fn color(self) -> Option<Color> {
+		match self {
+			TrailColor::Bird => None,
+			TrailColor::Shadow => Some(Color::srgb(0.1, 0.1, 0.15)),
+			TrailColor::Gold => Some(Color::srgb(1.0, 0.85, 0.3)),
+			TrailColor::Ice => Some(Color::srgb(0.6, 0.85, 1.0)),
+			TrailColor::Ember => Some(Color::srgb(1.0, 0.45, 0.2)),
+		}
+	}
+}
+
+/// Color of the pipes of every biome, see [`palette`](crate::palette).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PipeTheme {
+	#[default]
+	Classic,
+	Copper,
+	Ocean,
+	Amethyst,
+}
+
+impl PipeTheme {
+	pub fn name(self) -> &'static str {
+		match self {
+			PipeTheme::Classic => "Classic",
+			PipeTheme::Copper => "Copper",
+			PipeTheme::Ocean => "Ocean",
+			PipeTheme::Amethyst => "Amethyst",
+		}
+	}
+
+	/// Hue the green of the pipes turns to, `None` for the pipes as drawn.
+	pub fn hue(self) -> Option<f32> {
+		match self {
+			PipeTheme::Classic => None,
+			PipeTheme::Copper => Some(25.0),
+			PipeTheme::Ocean => Some(200.0),
+			PipeTheme::Amethyst => Some(285.0),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Item {
+	Skin(Skin),
+	Trail(TrailColor),
+	Pipes(PipeTheme),
+}
+
+/// Everything for sale, with its price in coins.
+const STOCK: [(Item, u64); 9] = [
+	(Item::Skin(Skin::Teal), 30),
+	(Item::Skin(Skin::Rose), 30),
+	(Item::Trail(TrailColor::Shadow), 15),
+	(Item::Trail(TrailColor::Ice), 20),
+	(Item::Trail(TrailColor::Ember), 20),
+	(Item::Trail(TrailColor::Gold), 40),
+	(Item::Pipes(PipeTheme::Copper), 25),
+	(Item::Pipes(PipeTheme::Ocean), 25),
+	(Item::Pipes(PipeTheme::Amethyst), 50),
+];
+
+impl Item {
+	fn name(self, locale: &Locale) -> String {
+		match self {
+			Item::Skin(skin) => locale.format("shop-skin", &[("name", &skin.label(locale))]),
+			Item::Trail(color) => locale.format(
+				"shop-trail",
+				&[("name", &locale.option("trail", color.name()))],
+			),
+			Item::Pipes(theme) => locale.format(
+				"shop-pipes",
+				&[("name", &locale.option("pipes", theme.name()))],
+			),
+		}
+	}
+}
+
+/// Persisted purchases, and the trail and pipes equipped. The skin equipped is
+/// [`SaveData::skin`], since skins can also be unlocked by playing.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ShopRecord {
+	pub owned: Vec<Item>,
+	pub trail: TrailColor,
+	pub pipes: PipeTheme,
+}
+
+impl ShopRecord {
+	pub fn owns(&self, item: Item) -> bool {
+		self.owned.contains(&item)
+	}
+}
+
+fn equipped(save: &SaveData, item: Item) -> bool {
+	match item {
+		Item::Skin(skin) => save.skin == skin,
+		Item::Trail(color) => save.shop.trail == color,
+		Item::Pipes(theme) => save.shop.pipes == theme,
+	}
+}
+
+/// Equipping what's already equipped takes it off again.
+fn toggle_equipped(save: &mut SaveData, item: Item) {
+	let on = !equipped(save, item);
+	match item {
+		Item::Skin(skin) => save.skin = if on { skin } else { Skin::default() },
+		Item::Trail(color) => save.shop.trail = if on { color } else { TrailColor::default() },
+		Item::Pipes(theme) => save.shop.pipes = if on { theme } else { PipeTheme::default() },
+	}
+}
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct SelectedItem(usize);
+
+#[derive(Component)]
+struct CoinsText;
+
+#[derive(Component)]
+struct StockRow(usize);
+
+fn spawn_shop(
+	mut commands: Commands,
+	mut selected: ResMut<SelectedItem>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	**selected = 0;
+	commands
+		.spawn((
+			CleanupOnExit(MenuScreen::Shop),
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				row_gap: Val::Px(12.0),
+				..default()
+			},
+			BackgroundColor(theme.screen),
+		))
+		.with_children(|parent| {
+			parent.spawn((Text::new(locale.text("shop-title")), theme.text(72.0)));
+			parent.spawn((CoinsText, Text::default(), theme.text(32.0)));
+			for index in 0..STOCK.len() {
+				parent.spawn((StockRow(index), Text::default(), theme.text(32.0)));
+			}
+			parent.spawn((Text::new(locale.text("shop-help")), theme.text(24.0)));
+		});
+}
+
+fn refresh_shop(
+	save: Res<SaveData>,
+	selected: Res<SelectedItem>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+	mut coins: Single<&mut Text, With<CoinsText>>,
+	rows: Query<(&StockRow, &mut Text, &mut TextColor), Without<CoinsText>>,
+) {
+	coins.0 = locale.format("shop-coins", &[("coins", &save.coins)]);
+	for (row, mut text, mut color) in rows {
+		let (item, price) = STOCK[row.0];
+		let status = if equipped(&save, item) {
+			locale.text("shop-equipped")
+		} else if save.shop.owns(item) {
+			locale.text("shop-owned")
+		} else {
+			locale.format("shop-price", &[("price", &price)])
+		};
+		text.0 = locale.format(
+			"shop-item",
+			&[("item", &item.name(&locale)), ("status", &status)],
+		);
+		color.0 = if row.0 == **selected {
+			theme.accent
+		} else {
+			theme.text
+		};
+	}
+}
+
+fn browse_shop(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut selected: ResMut<SelectedItem>,
+	mut save: ResMut<SaveData>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	let rows = STOCK.len();
+	if keyboard_input.just_pressed(KeyCode::ArrowUp) {
+		**selected = (**selected + rows - 1) % rows;
+	}
+	if keyboard_input.just_pressed(KeyCode::ArrowDown) {
+		**selected = (**selected + 1) % rows;
+	}
+	if keyboard_input.just_pressed(KeyCode::Enter) {
+		let (item, price) = STOCK[**selected];
+		if save.shop.owns(item) {
+			toggle_equipped(&mut save, item);
+		} else if save.coins >= price {
+			save.coins -= price;
+			save.shop.owned.push(item);
+			toggle_equipped(&mut save, item);
+		}
+	}
+	if keyboard_input.just_pressed(KeyCode::Escape) {
+		next_screen.set(MenuScreen::Main);
+	}
+}
+
+fn open_shop_on_y(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyY) {
+		next_screen.set(MenuScreen::Shop);
+	}
+}
+
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<SelectedItem>()
+			.add_systems(OnEnter(MenuScreen::Shop), spawn_shop)
+			.add_systems(
+				Update,
+				(
+					open_shop_on_y.run_if(in_state(MenuScreen::Main)),
+					(browse_shop, refresh_shop)
+						.chain()
+						.run_if(in_state(MenuScreen::Shop)),
+				),
+			);
+	}
+}
@@ -0,0 +1,366 @@
+//! Other looks for the bird, unlocked by achievements and high scores or bought
+//! in the [`shop`](crate::shop), and picked on the skin screen, opened with J
+//! from the main menu. The skin worn is kept in the save file.
+//!
+//! Each skin is the bird texture with its blue turned to another color, drawn
+//! once the texture is loaded. High contrast keeps its own bird, whatever the
+//! skin.
+
+use std::collections::HashMap;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	achievements::Achievement,
+	cleanup::CleanupOnExit,
+	input::PlayerInput,
+	locale::Locale,
+	menu::MenuScreen,
+	palette::{self, BIRD_HUE},
+	save::SaveData,
+	settings::Settings,
+	shop::Item,
+	sprites::Textures,
+	theme::UiTheme,
+	Player,
+};
+
+const PREVIEW_SIZE: f32 = 96.0;
+const LOCKED_PREVIEW: Color = Color::srgb(0.15, 0.15, 0.15);
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Skin {
+	#[default]
+	Classic,
+	Emerald,
+	Crimson,
+	Golden,
+	Violet,
+	Sunset,
+	Teal,
+	Rose,
+}
+
+/// What it takes to wear a skin.
+enum Unlock {
+	Always,
+	Achievement(Achievement),
+	/// A best score, endless or hardcore, of at least this.
+	Score(i64),
+	Shop,
+}
+
+impl Skin {
+	const ALL: [Skin; 8] = [
+		Skin::Classic,
+		Skin::Emerald,
+		Skin::Crimson,
+		Skin::Golden,
+		Skin::Violet,
+		Skin::Sunset,
+		Skin::Teal,
+		Skin::Rose,
+	];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Skin::Classic => "Classic",
+			Skin::Emerald => "Emerald",
+			Skin::Crimson => "Crimson",
+			Skin::Golden => "Golden",
+			Skin::Violet => "Violet",
+			Skin::Sunset => "Sunset",
+			Skin::Teal => "Teal",
+			Skin::Rose => "Rose",
+		}
+	}
+
+	/// Hue the blue of the bird turns to, `None` for the bird as drawn.
+	fn hue(self) -> Option<f32> {
+		match self {
+			Skin::Classic => None,
+			Skin::Emerald => Some(140.0),
+			Skin::Crimson => Some(355.0),
+			Skin::Golden => Some(48.0),
+			Skin::Violet => Some(275.0),
+			Skin::Sunset => Some(20.0),
+			Skin::Teal => Some(180.0),
+			Skin::Rose => Some(320.0),
+		}
+	}
+
+	fn unlock(self) -> Unlock {
+		match self {
+			Skin::Classic => Unlock::Always,
+			Skin::Emerald => Unlock::Achievement(Achievement::TenPipes),
+			Skin::Crimson => Unlock::Score(25),
+			Skin::Golden => Unlock::Achievement(Achievement::FiftyPipes),
+			Skin::Violet => Unlock::Achievement(Achievement::HundredRuns),
+			Skin::Sunset => Unlock::Score(100),
+			Skin::Teal | Skin::Rose => Unlock::Shop,
+		}
+	}
+
+	pub fn unlocked(self, save: &SaveData) -> bool {
+		match self.unlock() {
+			Unlock::Always => true,
+			Unlock::Achievement(achievement) => save.achievements.unlocked.contains(&achievement),
+			Unlock::Score(score) => save.high_scores.best().max(save.hardcore_best) >= score,
+			Unlock::Shop => save.shop.owns(Item::Skin(self)),
+		}
+	}
+
+	/// Name of the skin in the language picked.
+	pub fn label(self, locale: &Locale) -> String {
+		locale.option("skin", self.name())
+	}
+
+	/// How to unlock the skin, for the skin screen.
+	fn requirement(self, locale: &Locale) -> String {
+		match self.unlock() {
+			Unlock::Always => locale.text("skins-always"),
+			Unlock::Achievement(achievement) => achievement.description(locale),
+			Unlock::Score(score) => locale.format("skins-score", &[("score", &score)]),
+			Unlock::Shop => locale.text("skins-shop"),
+		}
+	}
+}
+
+/// The bird texture of each skin but the classic one.
+#[derive(Resource, Default)]
+struct SkinImages(HashMap<Skin, Handle<Image>>);
+
+impl SkinImages {
+	fn image(&self, skin: Skin, textures: &Textures) -> Handle<Image> {
+		self.0
+			.get(&skin)
+			.cloned()
+			.unwrap_or_else(|| textures.bird_image().clone())
+	}
+}
+
+/// Runs before the palette gets to the bird texture, so the skins are drawn
+/// from the bird as loaded.
+fn draw_skins(
+	mut events: EventReader<AssetEvent<Image>>,
+	mut images: ResMut<Assets<Image>>,
+	mut skins: ResMut<SkinImages>,
+	textures: Res<Textures>,
+) {
+	let bird = textures.bird_image().id();
+	let loaded = events
+		.read()
+		.any(|event| *event == AssetEvent::LoadedWithDependencies { id: bird });
+	if !loaded {
+		return;
+	}
+	let Some(original) = images.get(bird).cloned() else {
+		return;
+	};
+	for skin in Skin::ALL {
+		if let Some(hue) = skin.hue() {
+			let handle = images.add(palette::recolor(&original, BIRD_HUE, hue));
+			skins.0.insert(skin, handle);
+		}
+	}
+}
+
+/// Puts the skin on every bird wearing the bird texture or a skin, leaving
+/// birds drawn with their own textures alone.
+fn wear_skin(
+	mut players: Query<&mut Sprite, With<Player>>,
+	save: Res<SaveData>,
+	settings: Res<Settings>,
+	skins: Res<SkinImages>,
+	textures: Res<Textures>,
+) {
+	let skin = if save.skin.unlocked(&save) && !settings.high_contrast {
+		save.skin
+	} else {
+		Skin::Classic
+	};
+	let image = skins.image(skin, &textures);
+	for mut sprite in &mut players {
+		let is_bird = sprite.image == *textures.bird_image()
+			|| skins.0.values().any(|skin| sprite.image == *skin);
+		if is_bird && sprite.image != image {
+			sprite.image = image.clone();
+		}
+	}
+}
+
+/// The skin highlighted on the skin screen.
+#[derive(Resource)]
+struct HighlightedSkin(usize);
+
+#[derive(Component)]
+struct SkinCard(usize);
+
+#[derive(Component)]
+struct SkinScreen;
+
+fn spawn_skin_screen(
+	mut commands: Commands,
+	save: Res<SaveData>,
+	skins: Res<SkinImages>,
+	textures: Res<Textures>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	let highlighted = Skin::ALL
+		.iter()
+		.position(|skin| *skin == save.skin)
+		.unwrap_or(0);
+	commands.insert_resource(HighlightedSkin(highlighted));
+	commands
+		.spawn((
+			SkinScreen,
+			CleanupOnExit(MenuScreen::Skins),
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				row_gap: Val::Px(24.0),
+				..default()
+			},
+			BackgroundColor(theme.screen),
+		))
+		.with_children(|parent| {
+			parent.spawn((Text::new(locale.text("skins-title")), theme.text(64.0)));
+			parent
+				.spawn(Node {
+					flex_wrap: FlexWrap::Wrap,
+					justify_content: JustifyContent::Center,
+					column_gap: Val::Px(16.0),
+					row_gap: Val::Px(16.0),
+					..default()
+				})
+				.with_children(|row| {
+					for (index, skin) in Skin::ALL.into_iter().enumerate() {
+						let unlocked = skin.unlocked(&save);
+						row.spawn((
+							SkinCard(index),
+							Node {
+								width: Val::Px(200.0),
+								flex_direction: FlexDirection::Column,
+								align_items: AlignItems::Center,
+								row_gap: Val::Px(8.0),
+								padding: UiRect::all(Val::Px(12.0)),
+								..default()
+							},
+							BackgroundColor(theme.button_color(index == highlighted)),
+							BorderRadius::all(Val::Px(theme.button_radius)),
+						))
+						.with_children(|card| {
+							let mut preview = textures.bird_node(skins.image(skin, &textures));
+							if !unlocked {
+								preview.color = LOCKED_PREVIEW;
+							}
+							card.spawn((
+								preview,
+								Node {
+									width: Val::Px(PREVIEW_SIZE),
+									height: Val::Px(PREVIEW_SIZE),
+									..default()
+								},
+							));
+							let name = if skin == save.skin {
+								locale.format("skins-worn", &[("skin", &skin.label(&locale))])
+							} else {
+								skin.label(&locale)
+							};
+							card.spawn((Text::new(name), theme.text(28.0)));
+							let status = if unlocked {
+								locale.text("skins-unlocked")
+							} else {
+								locale.format(
+									"skins-locked",
+									&[("requirement", &skin.requirement(&locale))],
+								)
+							};
+							card.spawn((
+								Text::new(status),
+								theme.text(18.0),
+								TextLayout::new_with_justify(JustifyText::Center),
+							));
+						});
+					}
+				});
+			parent.spawn((Text::new(locale.text("skins-help")), theme.text(24.0)));
+		});
+}
+
+fn open_skins_on_j(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyJ) {
+		next_screen.set(MenuScreen::Skins);
+	}
+}
+
+/// The skin screen and its cards.
+#[derive(SystemParam)]
+struct SkinScreenUi<'w, 's> {
+	screen: Single<'w, Entity, With<SkinScreen>>,
+	cards: Query<'w, 's, (&'static SkinCard, &'static mut BackgroundColor)>,
+	theme: Res<'w, UiTheme>,
+}
+
+impl SkinScreenUi<'_, '_> {
+	fn highlight(&mut self, highlighted: usize) {
+		for (card, mut color) in &mut self.cards {
+			color.0 = self.theme.button_color(card.0 == highlighted);
+		}
+	}
+}
+
+/// Wearing a skin redraws the screen, to move the "(worn)" along.
+fn pick_skin(
+	input: PlayerInput,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut highlighted: ResMut<HighlightedSkin>,
+	mut ui: SkinScreenUi,
+	mut save: ResMut<SaveData>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+	mut commands: Commands,
+) {
+	if keyboard_input.just_pressed(KeyCode::Escape) {
+		next_screen.set(MenuScreen::Main);
+		return;
+	}
+	let step = input.menu_step();
+	if step != 0 {
+		highlighted.0 = (highlighted.0 as i32 + step).rem_euclid(Skin::ALL.len() as i32) as usize;
+		ui.highlight(highlighted.0);
+	}
+	let skin = Skin::ALL[highlighted.0];
+	if input.confirm_pressed() && skin.unlocked(&save) && save.skin != skin {
+		save.skin = skin;
+		commands.entity(*ui.screen).despawn();
+		commands.run_system_cached(spawn_skin_screen);
+	}
+}
+
+pub struct SkinsPlugin;
+
+impl Plugin for SkinsPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<SkinImages>()
+			.add_systems(OnEnter(MenuScreen::Skins), spawn_skin_screen)
+			.add_systems(
+				Update,
+				(
+					draw_skins
+						.before(palette::recolor_textures)
+						.run_if(on_event::<AssetEvent<Image>>),
+					wear_skin,
+					open_skins_on_j.run_if(in_state(MenuScreen::Main)),
+					pick_skin.run_if(in_state(MenuScreen::Skins)),
+				),
+			);
+	}
+}
@@ -0,0 +1,70 @@
+//! Spoken score announcements, built in with the `tts` feature and turned on
+//! under Announce score in the settings: the score is read out every
+//! [`MILESTONE`] points, and the final score when the game is over, through
+//! the screen reader or speech engine of the system.
+
+use bevy::prelude::*;
+use tts::Tts;
+
+use crate::{locale::Locale, settings::Settings, GameScore, GameStates};
+
+/// Points between two announcements during a run.
+const MILESTONE: i64 = 10;
+
+/// Not every speech engine can be used from other threads, so this is kept on
+/// the main one.
+struct Speech(Tts);
+
+impl Speech {
+	/// Cuts off whatever was being said, which is out of date by now.
+	fn say(&mut self, text: &str) {
+		if let Err(err) = self.0.speak(text, true) {
+			warn!("Failed to speak: {err}");
+		}
+	}
+}
+
+fn announcing(settings: Res<Settings>) -> bool {
+	settings.announce_score
+}
+
+/// Reads out the score as it reaches each milestone. `announced` goes back
+/// down as the score does when a new run starts.
+fn announce_milestones(
+	mut speech: NonSendMut<Speech>,
+	score: Res<GameScore>,
+	mut announced: Local<i64>,
+) {
+	let milestone = **score / MILESTONE * MILESTONE;
+	if milestone > *announced {
+		speech.say(&milestone.to_string());
+	}
+	*announced = milestone;
+}
+
+fn announce_game_over(mut speech: NonSendMut<Speech>, score: Res<GameScore>, locale: Res<Locale>) {
+	speech.say(&locale.format("speech-game-over", &[("score", &**score)]));
+}
+
+pub struct SpeechPlugin;
+
+impl Plugin for SpeechPlugin {
+	fn build(&self, app: &mut App) {
+		let tts = match Tts::default() {
+			Ok(tts) => tts,
+			Err(err) => {
+				info!("Playing without speech: {err}");
+				return;
+			}
+		};
+		app.insert_non_send_resource(Speech(tts))
+			.add_systems(
+				Update,
+				announce_milestones.run_if(resource_changed::<GameScore>.and(announcing)),
+			)
+			.add_systems(
+				OnEnter(GameStates::GameOver),
+				announce_game_over.run_if(announcing),
+			);
+	}
+}
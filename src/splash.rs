@@ -0,0 +1,80 @@
+//! A short branding screen between loading and the main menu, fading in and
+//! out again. Any key, click or tap skips it.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit, input::PlayerInput, locale::Locale, theme::UiTheme,
+	timed_transition::TimedTransition, GameStates,
+};
+
+const SPLASH_DURATION: f32 = 2.5;
+/// Seconds spent fading in, and again fading out.
+const FADE_DURATION: f32 = 0.6;
+
+#[derive(Component)]
+struct SplashText;
+
+fn spawn_splash(mut commands: Commands, locale: Res<Locale>, theme: Res<UiTheme>) {
+	commands
+		.spawn((
+			CleanupOnExit(GameStates::Splash),
+			TimedTransition::new(SPLASH_DURATION, GameStates::Menu),
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				row_gap: Val::Px(16.0),
+				..default()
+			},
+			BackgroundColor(Color::BLACK),
+		))
+		.with_children(|parent| {
+			parent.spawn((
+				SplashText,
+				Text::new("KHTangent"),
+				theme.font(72.0),
+				TextColor(Color::NONE),
+			));
+			parent.spawn((
+				SplashText,
+				Text::new(locale.text("splash-presents")),
+				theme.font(28.0),
+				TextColor(Color::NONE),
+			));
+		});
+}
+
+fn fade_splash(
+	splash: Single<&TimedTransition<GameStates>>,
+	texts: Query<&mut TextColor, With<SplashText>>,
+	theme: Res<UiTheme>,
+) {
+	let timer = &splash.timer;
+	let fade_in = timer.elapsed_secs() / FADE_DURATION;
+	let fade_out = timer.remaining_secs() / FADE_DURATION;
+	let alpha = fade_in.min(fade_out).clamp(0.0, 1.0);
+	for mut color in texts {
+		color.0 = theme.text.with_alpha(alpha);
+	}
+}
+
+fn skip_on_any_press(input: PlayerInput, mut splash: Single<&mut TimedTransition<GameStates>>) {
+	if input.any_pressed() {
+		splash.skip();
+	}
+}
+
+pub struct SplashPlugin;
+
+impl Plugin for SplashPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(OnEnter(GameStates::Splash), spawn_splash)
+			.add_systems(
+				Update,
+				(skip_on_any_press, fade_splash).run_if(in_state(GameStates::Splash)),
+			);
+	}
+}
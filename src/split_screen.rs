@@ -0,0 +1,409 @@
+//! Local split-screen for two players, opened with W from the main menu. Each
+//! half of the screen has its own camera following its own bird through the
+//! same pipes of the [`sandbox`](crate::sandbox). Player one flaps with Space
+//! or W, player two with Up or a gamepad.
+//!
+//! A round ends as soon as either bird crashes, and the other one wins it.
+//! Wins are tallied until the players go back to the menu.
+
+use bevy::{
+	prelude::*,
+	render::{
+		camera::{ScalingMode, Viewport},
+		view::RenderLayers,
+	},
+	window::PrimaryWindow,
+};
+use rand::Rng;
+
+use crate::{
+	cleanup::CleanupOnExit,
+	collision::sprite_collider,
+	difficulty::{Difficulty, DifficultySettings},
+	locale::Locale,
+	menu::MenuScreen,
+	mirror::ScrollDirection,
+	pipe_pool::PipeSpawner,
+	play_area::PlayArea,
+	sandbox::{self, Sandbox, SandboxSet},
+	seed::{GameRng, SeedSettings},
+	sprites::{SpriteAnimation, Textures},
+	theme::UiTheme,
+	GameStates, MainCamera, Pipe, Velocity, PLAYER_SIZE, RESTART_DELAY, WINDOW_SIZE,
+};
+
+const PLAYERS: usize = 2;
+const COUNTDOWN: f32 = 3.0;
+const PLAYER_COLORS: [Color; PLAYERS] = [Color::WHITE, Color::srgb(1.0, 0.6, 0.6)];
+const FLAP_KEYS: [&[KeyCode]; PLAYERS] = [&[KeyCode::Space, KeyCode::KeyW], &[KeyCode::ArrowUp]];
+/// Message of the controls of each player.
+const CONTROLS: [&str; PLAYERS] = ["split-controls-left", "split-controls-right"];
+
+/// What the players are doing between rounds.
+#[derive(SubStates, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[source(GameStates = GameStates::SplitScreen)]
+pub(crate) enum SplitRound {
+	#[default]
+	Countdown,
+	Flying,
+	/// A bird crashed, showing who won until the next round.
+	Over,
+}
+
+/// Counts down to the start of a round, and holds off the next one for a
+/// moment after it's over.
+#[derive(Resource, Deref, DerefMut)]
+struct RoundTimer(Timer);
+
+#[derive(Resource, Default)]
+struct Versus {
+	wins: [u32; PLAYERS],
+	/// Pipes each player got through this round.
+	pipes: [u32; PLAYERS],
+	crashed: [bool; PLAYERS],
+}
+
+/// Flaps pressed since the last tick.
+#[derive(Resource, Default)]
+struct Flaps([bool; PLAYERS]);
+
+/// The bird and the camera of a player, by index.
+#[derive(Component)]
+struct SplitBird(usize);
+
+#[derive(Component)]
+struct SplitCamera(usize);
+
+#[derive(Component)]
+struct ScoreText(usize);
+
+#[derive(Component)]
+struct MessageText(usize);
+
+fn start_split_screen(
+	mut commands: Commands,
+	mut main_camera: Single<&mut Camera, With<MainCamera>>,
+	textures: Res<Textures>,
+	settings: Res<SeedSettings>,
+	direction: Res<ScrollDirection>,
+	theme: Res<UiTheme>,
+) {
+	main_camera.is_active = false;
+	commands.insert_resource(GameRng::new(
+		settings.fixed_seed.unwrap_or_else(|| rand::rng().random()),
+	));
+	commands.init_resource::<Versus>();
+	commands.init_resource::<Flaps>();
+	let start = direction.player_start();
+	for (player, color) in PLAYER_COLORS.into_iter().enumerate() {
+		let layer = RenderLayers::layer(player + 1);
+		let mut sprite = textures.bird_sprite(PLAYER_SIZE);
+		sprite.flip_x = direction.flip_x();
+		sprite.color = color;
+		commands.spawn((
+			SplitBird(player),
+			CleanupOnExit(GameStates::SplitScreen),
+			sprite,
+			SpriteAnimation::bird(),
+			Transform::from_translation(start),
+			Velocity::default(),
+			layer.clone(),
+		));
+		// Keeps the bird a little left of the middle of its half, to see what's
+		// coming.
+		let camera_x = start.x - direction.sign() * WINDOW_SIZE.x / 8.0;
+		let camera = commands
+			.spawn((
+				SplitCamera(player),
+				CleanupOnExit(GameStates::SplitScreen),
+				Camera2d,
+				Camera {
+					order: player as isize + 1,
+					..default()
+				},
+				Projection::Orthographic(OrthographicProjection {
+					scaling_mode: ScalingMode::AutoMin {
+						min_width: WINDOW_SIZE.x / 2.0,
+						min_height: WINDOW_SIZE.y,
+					},
+					..OrthographicProjection::default_2d()
+				}),
+				Transform::from_xyz(camera_x, 0.0, 0.0),
+				// The world on the default layer is seen by both.
+				layer.with(0),
+			))
+			.id();
+		commands
+			.spawn((
+				CleanupOnExit(GameStates::SplitScreen),
+				UiTargetCamera(camera),
+				Node {
+					width: Val::Percent(100.0),
+					height: Val::Percent(100.0),
+					flex_direction: FlexDirection::Column,
+					align_items: AlignItems::Center,
+					padding: UiRect::all(Val::Px(5.0)),
+					row_gap: Val::Percent(25.0),
+					..default()
+				},
+			))
+			.with_children(|parent| {
+				parent.spawn((ScoreText(player), Text::default(), theme.text(32.0)));
+				parent.spawn((
+					MessageText(player),
+					Text::default(),
+					theme.text(48.0),
+					TextLayout::new_with_justify(JustifyText::Center),
+				));
+			});
+	}
+}
+
+fn end_split_screen(mut main_camera: Single<&mut Camera, With<MainCamera>>) {
+	main_camera.is_active = true;
+}
+
+/// Splits the view the main camera would have between the two players, so
+/// both halves keep the same scaling as the rest of the game.
+fn fit_split_cameras(
+	window: Single<&Window, With<PrimaryWindow>>,
+	main_camera: Single<&Camera, With<MainCamera>>,
+	mut cameras: Query<(&SplitCamera, &mut Camera), Without<MainCamera>>,
+) {
+	let (position, size) = match &main_camera.viewport {
+		Some(viewport) => (viewport.physical_position, viewport.physical_size),
+		None => (UVec2::ZERO, window.physical_size()),
+	};
+	if size.x < 2 || size.y < 1 {
+		// Minimized, nothing to fit.
+		return;
+	}
+	let half = UVec2::new(size.x / 2, size.y);
+	for (split, mut camera) in &mut cameras {
+		let physical_position = position + UVec2::new(half.x * split.0 as u32, 0);
+		let fitted = camera.viewport.as_ref().is_some_and(|viewport| {
+			viewport.physical_position == physical_position && viewport.physical_size == half
+		});
+		if !fitted {
+			camera.viewport = Some(Viewport {
+				physical_position,
+				physical_size: half,
+				..default()
+			});
+		}
+	}
+}
+
+/// Puts the birds back at the start, in front of an empty screen.
+fn start_round(
+	mut commands: Commands,
+	mut versus: ResMut<Versus>,
+	mut pipes: PipeSpawner,
+	pipe_entities: Query<Entity, With<Pipe>>,
+	mut birds: Query<(&mut Transform, &mut Velocity), With<SplitBird>>,
+	direction: Res<ScrollDirection>,
+) {
+	for entity in &pipe_entities {
+		pipes.recycle(entity);
+	}
+	for (mut transform, mut velocity) in &mut birds {
+		*transform = Transform::from_translation(direction.player_start());
+		*velocity = Velocity::default();
+	}
+	versus.pipes = [0; PLAYERS];
+	versus.crashed = [false; PLAYERS];
+	commands.insert_resource(RoundTimer(Timer::from_seconds(COUNTDOWN, TimerMode::Once)));
+}
+
+fn count_down(
+	mut timer: ResMut<RoundTimer>,
+	mut next_round: ResMut<NextState<SplitRound>>,
+	time: Res<Time>,
+) {
+	if timer.tick(time.delta()).finished() {
+		next_round.set(SplitRound::Flying);
+	}
+}
+
+/// Both birds take off with a flap as the pipes start coming.
+fn take_off(mut commands: Commands, mut flaps: ResMut<Flaps>, difficulty: Res<Difficulty>) {
+	commands.insert_resource(Sandbox::new(&difficulty));
+	flaps.0 = [true; PLAYERS];
+}
+
+fn read_flaps(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	gamepads: Query<&Gamepad>,
+	mut flaps: ResMut<Flaps>,
+) {
+	flaps.0[0] |= keyboard_input.any_just_pressed(FLAP_KEYS[0].iter().copied());
+	flaps.0[1] |= keyboard_input.any_just_pressed(FLAP_KEYS[1].iter().copied())
+		|| gamepads
+			.iter()
+			.any(|gamepad| gamepad.just_pressed(GamepadButton::South));
+}
+
+fn fly_birds(
+	mut flaps: ResMut<Flaps>,
+	mut birds: Query<(&SplitBird, &mut Transform, &mut Velocity)>,
+	settings: Res<DifficultySettings>,
+	time: Res<Time>,
+) {
+	for (bird, mut transform, mut velocity) in &mut birds {
+		sandbox::fly(
+			&mut transform,
+			&mut velocity,
+			flaps.0[bird.0],
+			settings.jump_strength,
+			settings.gravity,
+			time.delta_secs(),
+		);
+	}
+	flaps.0 = [false; PLAYERS];
+}
+
+/// The round is over with the first crash. Birds crashing on the same tick
+/// make it a draw.
+fn crash_birds(
+	mut commands: Commands,
+	mut versus: ResMut<Versus>,
+	mut next_round: ResMut<NextState<SplitRound>>,
+	sandbox: Res<Sandbox>,
+	play_area: Res<PlayArea>,
+	birds: Query<(&SplitBird, &Transform, &Sprite)>,
+	pipes: Query<(&Transform, &Sprite), With<Pipe>>,
+) {
+	versus.pipes = [sandbox.pipes_passed; PLAYERS];
+	for (bird, transform, sprite) in &birds {
+		let collider = sprite_collider(transform, sprite);
+		versus.crashed[bird.0] = sandbox::hits_anything(collider, &play_area, pipes.iter());
+	}
+	match versus.crashed {
+		[false, false] => return,
+		[true, false] => versus.wins[1] += 1,
+		[false, true] => versus.wins[0] += 1,
+		[true, true] => {}
+	}
+	commands.remove_resource::<Sandbox>();
+	commands.insert_resource(RoundTimer(Timer::from_seconds(
+		RESTART_DELAY,
+		TimerMode::Once,
+	)));
+	next_round.set(SplitRound::Over);
+}
+
+/// Any flap starts the next round once the result has been up for a moment.
+fn next_round_on_flap(
+	mut timer: ResMut<RoundTimer>,
+	flaps: Res<Flaps>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_round: ResMut<NextState<SplitRound>>,
+	time: Res<Time>,
+) {
+	if timer.tick(time.delta()).finished()
+		&& (flaps.0.contains(&true) || keyboard_input.just_pressed(KeyCode::Enter))
+	{
+		next_round.set(SplitRound::Countdown);
+	}
+}
+
+fn clear_flaps(mut flaps: ResMut<Flaps>) {
+	flaps.0 = [false; PLAYERS];
+}
+
+fn update_split_texts(
+	versus: Res<Versus>,
+	round: Res<State<SplitRound>>,
+	timer: Res<RoundTimer>,
+	locale: Res<Locale>,
+	mut scores: Query<(&ScoreText, &mut Text), Without<MessageText>>,
+	mut messages: Query<(&MessageText, &mut Text), Without<ScoreText>>,
+) {
+	for (score, mut text) in &mut scores {
+		text.0 = locale.format(
+			"split-score",
+			&[
+				("player", &(score.0 + 1)),
+				("pipes", &versus.pipes[score.0]),
+				("wins", &versus.wins[score.0]),
+			],
+		);
+	}
+	for (message, mut text) in &mut messages {
+		let player = message.0;
+		text.0 = match round.get() {
+			SplitRound::Countdown => format!(
+				"{}\n{}",
+				timer.remaining_secs().ceil() as u32,
+				locale.text(CONTROLS[player])
+			),
+			SplitRound::Flying => String::new(),
+			SplitRound::Over => {
+				let result = if versus.crashed == [true; PLAYERS] {
+					"split-draw"
+				} else if versus.crashed[player] {
+					"split-crashed"
+				} else {
+					"split-win"
+				};
+				locale.format("split-over", &[("result", &locale.text(result))])
+			}
+		};
+	}
+}
+
+fn open_split_screen_on_w(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyW) {
+		next_state.set(GameStates::SplitScreen);
+	}
+}
+
+fn leave_on_escape(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::Escape) {
+		next_state.set(GameStates::Menu);
+	}
+}
+
+pub struct SplitScreenPlugin;
+
+impl Plugin for SplitScreenPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(OnEnter(GameStates::SplitScreen), start_split_screen)
+			.add_systems(
+				OnExit(GameStates::SplitScreen),
+				(end_split_screen, sandbox::clear_sandbox),
+			)
+			.add_systems(OnEnter(SplitRound::Countdown), start_round)
+			.add_systems(OnEnter(SplitRound::Flying), take_off)
+			.add_systems(
+				FixedUpdate,
+				(fly_birds, crash_birds)
+					.chain()
+					.after(SandboxSet)
+					.run_if(in_state(SplitRound::Flying).and(resource_exists::<Sandbox>)),
+			)
+			.add_systems(
+				Update,
+				(
+					open_split_screen_on_w.run_if(in_state(MenuScreen::Main)),
+					(
+						fit_split_cameras,
+						read_flaps,
+						count_down.run_if(in_state(SplitRound::Countdown)),
+						next_round_on_flap.run_if(in_state(SplitRound::Over)),
+						clear_flaps.run_if(not(in_state(SplitRound::Flying))),
+						update_split_texts,
+						leave_on_escape,
+					)
+						.chain()
+						.run_if(in_state(GameStates::SplitScreen)),
+				),
+			);
+	}
+}
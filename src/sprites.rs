@@ -0,0 +1,161 @@
+use bevy::{
+	prelude::*,
+	sprite::{BorderRect, SliceScaleMode, SpriteImageMode, TextureSlicer},
+};
+
+use crate::{loading::Preload, settings::motion_allowed};
+
+const BIRD_FRAME_SIZE: UVec2 = UVec2::splat(32);
+const BIRD_FRAMES: usize = 4;
+const BIRD_FRAME_TIME: f32 = 0.08;
+
+/// Height of the lip at each end of the pipe texture, kept unstretched when slicing.
+const PIPE_LIP_HEIGHT: f32 = 12.0;
+
+#[derive(Resource)]
+pub struct Textures {
+	bird: Handle<Image>,
+	bird_layout: Handle<TextureAtlasLayout>,
+	pipe: Handle<Image>,
+	ground: Handle<Image>,
+	coin: Handle<Image>,
+	pub heart: Handle<Image>,
+	pub background: Handle<Image>,
+}
+
+impl FromWorld for Textures {
+	fn from_world(world: &mut World) -> Self {
+		let layout =
+			TextureAtlasLayout::from_grid(BIRD_FRAME_SIZE, BIRD_FRAMES as u32, 1, None, None);
+		let bird_layout = world
+			.resource_mut::<Assets<TextureAtlasLayout>>()
+			.add(layout);
+		let asset_server = world.resource::<AssetServer>();
+		let textures = Textures {
+			bird: asset_server.load("sprites/bird.png"),
+			bird_layout,
+			pipe: asset_server.load("sprites/pipe.png"),
+			ground: asset_server.load("sprites/ground.png"),
+			coin: asset_server.load("sprites/coin.png"),
+			heart: asset_server.load("sprites/heart.png"),
+			background: asset_server.load("sprites/background.png"),
+		};
+		let images = [
+			&textures.bird,
+			&textures.pipe,
+			&textures.ground,
+			&textures.coin,
+			&textures.heart,
+			&textures.background,
+		];
+		Preload::add(world, images.map(|image| image.clone().untyped()));
+		textures
+	}
+}
+
+impl Textures {
+	pub fn bird_image(&self) -> &Handle<Image> {
+		&self.bird
+	}
+
+	pub fn ground_image(&self) -> &Handle<Image> {
+		&self.ground
+	}
+
+	pub fn bird_sprite(&self, size: Vec2) -> Sprite {
+		Sprite {
+			custom_size: Some(size),
+			..Sprite::from_atlas_image(
+				self.bird.clone(),
+				TextureAtlas {
+					layout: self.bird_layout.clone(),
+					index: 0,
+				},
+			)
+		}
+	}
+
+	/// The first frame of `image`, the bird texture or one drawn like it, for
+	/// the interface.
+	pub fn bird_node(&self, image: Handle<Image>) -> ImageNode {
+		ImageNode::from_atlas_image(
+			image,
+			TextureAtlas {
+				layout: self.bird_layout.clone(),
+				index: 0,
+			},
+		)
+	}
+
+	pub fn pipe_sprite(&self, size: Vec2) -> Sprite {
+		Sprite {
+			image: self.pipe.clone(),
+			custom_size: Some(size),
+			image_mode: SpriteImageMode::Sliced(TextureSlicer {
+				border: BorderRect::axes(0.0, PIPE_LIP_HEIGHT),
+				center_scale_mode: SliceScaleMode::Stretch,
+				sides_scale_mode: SliceScaleMode::Stretch,
+				max_corner_scale: 1.0,
+			}),
+			..default()
+		}
+	}
+
+	pub fn ground_sprite(&self, size: Vec2) -> Sprite {
+		Sprite {
+			image: self.ground.clone(),
+			custom_size: Some(size),
+			image_mode: SpriteImageMode::Tiled {
+				tile_x: true,
+				tile_y: true,
+				stretch_value: 1.0,
+			},
+			..default()
+		}
+	}
+
+	pub fn coin_sprite(&self, size: Vec2) -> Sprite {
+		Sprite {
+			image: self.coin.clone(),
+			custom_size: Some(size),
+			..default()
+		}
+	}
+}
+
+/// Cycles through the frames of a sprite's texture atlas.
+#[derive(Component)]
+pub struct SpriteAnimation {
+	frames: usize,
+	timer: Timer,
+}
+
+impl SpriteAnimation {
+	pub fn bird() -> Self {
+		SpriteAnimation {
+			frames: BIRD_FRAMES,
+			timer: Timer::from_seconds(BIRD_FRAME_TIME, TimerMode::Repeating),
+		}
+	}
+}
+
+fn animate_sprites(mut query: Query<(&mut SpriteAnimation, &mut Sprite)>, time: Res<Time>) {
+	for (mut animation, mut sprite) in &mut query {
+		animation.timer.tick(time.delta());
+		if !animation.timer.just_finished() {
+			continue;
+		}
+		if let Some(atlas) = &mut sprite.texture_atlas {
+			atlas.index = (atlas.index + 1) % animation.frames;
+		}
+	}
+}
+
+pub struct SpritesPlugin;
+
+impl Plugin for SpritesPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<Textures>()
+			.add_systems(Update, animate_sprites.run_if(motion_allowed));
+	}
+}
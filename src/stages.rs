@@ -0,0 +1,378 @@
+//! The campaign: a handful of handcrafted stages with a fixed pipe layout and
+//! a goal at the end of it. Cleared stages are rated with up to three stars
+//! for the coins picked up on the way.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit, crash::Crash, ground::ground_top, input::PlayerInput, locale::Locale,
+	pipe_patterns::Gap, pipe_pool::PipeSpawner, save::SaveData, theme::UiTheme, GameMode,
+	GameOverAt, GameStates, GameplayEvent, GameplaySet, InRun, PipeSpawnTimer, PipesPassed,
+	SpawnContext, RESTART_DELAY,
+};
+
+const MAX_STARS: u8 = 3;
+const STAR_SIZE: f32 = 48.0;
+const STAR_COLOR: Color = Color::srgb(1.0, 0.8, 0.2);
+const MISSING_STAR_COLOR: Color = Color::srgb(0.3, 0.3, 0.35);
+
+pub struct Stage {
+	/// Message of the name.
+	name: &'static str,
+	/// Where the gap of each pair of pipes goes, from 0 right above the ground
+	/// to 1 right below the top of the play area. Passing the last pair clears
+	/// the stage.
+	gaps: &'static [f32],
+}
+
+impl Stage {
+	pub fn goal(&self) -> u32 {
+		self.gaps.len() as u32
+	}
+
+	/// Every stage has a coin in each of its gaps. Clearing it is worth a
+	/// star, picking up half the coins another, and all of them the last one.
+	fn stars(&self, coins: u32) -> u8 {
+		1 + u8::from(coins * 2 >= self.goal()) + u8::from(coins >= self.goal())
+	}
+}
+
+pub const STAGES: [Stage; 5] = [
+	Stage {
+		name: "stage-first-flight",
+		gaps: &[0.5, 0.5, 0.5, 0.55, 0.6, 0.55, 0.5, 0.45, 0.4, 0.5],
+	},
+	Stage {
+		name: "stage-stairs",
+		gaps: &[0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.7, 0.6, 0.5, 0.4, 0.3],
+	},
+	Stage {
+		name: "stage-zigzag",
+		gaps: &[0.3, 0.7, 0.3, 0.7, 0.4, 0.8, 0.2, 0.6, 0.3, 0.7, 0.35, 0.65],
+	},
+	Stage {
+		name: "stage-canyon",
+		gaps: &[
+			0.1, 0.1, 0.15, 0.1, 0.9, 0.85, 0.9, 0.1, 0.15, 0.5, 0.9, 0.1, 0.5, 0.9,
+		],
+	},
+	Stage {
+		name: "stage-summit",
+		gaps: &[
+			0.5, 0.8, 0.2, 0.9, 0.1, 0.6, 1.0, 0.0, 0.5, 1.0, 0.3, 0.0, 0.7, 0.2, 0.9, 0.5,
+		],
+	},
+];
+
+/// The stage a run in the given mode plays, if any.
+pub fn stage(mode: GameMode) -> Option<&'static Stage> {
+	match mode {
+		GameMode::Stage(index) => STAGES.get(usize::from(index)),
+		_ => None,
+	}
+}
+
+pub fn in_stage(mode: Res<GameMode>) -> bool {
+	stage(*mode).is_some()
+}
+
+/// Where the campaign picks up: the first stage not cleared yet, or the first
+/// one again once all are.
+pub fn next_stage(save: &SaveData) -> u8 {
+	let cleared = save.stage_stars.iter().take_while(|stars| **stars > 0);
+	(cleared.count() % STAGES.len()) as u8
+}
+
+pub fn total_stars(save: &SaveData) -> u32 {
+	save.stage_stars.iter().map(|stars| u32::from(*stars)).sum()
+}
+
+pub fn max_stars() -> u32 {
+	STAGES.len() as u32 * u32::from(MAX_STARS)
+}
+
+/// Pairs of pipes spawned so far in the current stage.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct ColumnsSpawned(usize);
+
+/// Coins picked up in the current stage.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct StageCoins(u32);
+
+/// Whether the run that just ended reached the goal of its stage, rather than
+/// crashing on the way.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct StageCleared(bool);
+
+pub fn stage_cleared(cleared: Res<StageCleared>) -> bool {
+	**cleared
+}
+
+#[derive(Component)]
+struct StageText;
+
+#[derive(Component)]
+struct StageCompleteUi;
+
+fn reset_stage(
+	mut spawned: ResMut<ColumnsSpawned>,
+	mut coins: ResMut<StageCoins>,
+	mut cleared: ResMut<StageCleared>,
+) {
+	**spawned = 0;
+	**coins = 0;
+	**cleared = false;
+}
+
+fn spawn_stage_text(mut commands: Commands, theme: Res<UiTheme>) {
+	commands.spawn((
+		StageText,
+		CleanupOnExit(InRun),
+		Text::default(),
+		theme.text(28.0),
+		Node {
+			position_type: PositionType::Absolute,
+			bottom: Val::Px(5.0),
+			right: Val::Px(5.0),
+			..default()
+		},
+	));
+}
+
+/// Takes the place of the random pipe patterns, on the same timer.
+fn spawn_stage_columns(
+	mut pipes: PipeSpawner,
+	time: Res<Time>,
+	mut pipe_spawn_timer: ResMut<PipeSpawnTimer>,
+	mut spawned: ResMut<ColumnsSpawned>,
+	context: SpawnContext,
+	mode: Res<GameMode>,
+) {
+	pipe_spawn_timer.timer.tick(time.delta());
+	if !pipe_spawn_timer.timer.finished() {
+		return;
+	}
+	let Some(&position) = stage(*mode).and_then(|stage| stage.gaps.get(**spawned)) else {
+		return;
+	};
+	**spawned += 1;
+	let play_area = &context.play_area;
+	let height = context.difficulty.pipe_gap;
+	let lowest = ground_top(play_area);
+	// Squeezed in at the bottom when the window is too short to fit it.
+	let highest = (play_area.max.y - height).max(lowest);
+	let gap = Gap {
+		bottom: lowest.lerp(highest, position),
+		height,
+		safe: true,
+		coin: true,
+		spinning_bar: false,
+	};
+	pipes.spawn_column(
+		context.direction.entry_x(play_area),
+		play_area.height(),
+		&[gap],
+	);
+}
+
+fn reach_goal(
+	pipes_passed: Res<PipesPassed>,
+	mode: Res<GameMode>,
+	mut cleared: ResMut<StageCleared>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if stage(*mode).is_some_and(|stage| **pipes_passed >= stage.goal()) {
+		**cleared = true;
+		next_state.set(GameStates::GameOver);
+	}
+}
+
+fn count_stage_coins(mut events: EventReader<GameplayEvent>, mut coins: ResMut<StageCoins>) {
+	for event in events.read() {
+		if matches!(event, GameplayEvent::CoinCollected) {
+			**coins += 1;
+		}
+	}
+}
+
+fn update_stage_text(
+	mode: Res<GameMode>,
+	pipes_passed: Res<PipesPassed>,
+	locale: Res<Locale>,
+	mut text: Single<&mut Text, With<StageText>>,
+) {
+	if let (GameMode::Stage(index), Some(stage)) = (*mode, stage(*mode)) {
+		text.0 = locale.format(
+			"stage-progress",
+			&[
+				("stage", &(index + 1)),
+				("name", &locale.text(stage.name)),
+				("pipes", &**pipes_passed),
+				("goal", &stage.goal()),
+			],
+		);
+	}
+}
+
+/// Replays can't do better than the run they recorded, so they are left to
+/// count too.
+fn record_stars(mode: Res<GameMode>, coins: Res<StageCoins>, mut save: ResMut<SaveData>) {
+	let (GameMode::Stage(index), Some(stage)) = (*mode, stage(*mode)) else {
+		return;
+	};
+	let index = usize::from(index);
+	if save.stage_stars.len() <= index {
+		save.stage_stars.resize(index + 1, 0);
+	}
+	let stars = stage.stars(**coins);
+	if stars > save.stage_stars[index] {
+		save.stage_stars[index] = stars;
+	}
+}
+
+fn star(earned: bool) -> impl Bundle {
+	let color = if earned {
+		STAR_COLOR
+	} else {
+		MISSING_STAR_COLOR
+	};
+	(
+		Node {
+			width: Val::Px(STAR_SIZE),
+			height: Val::Px(STAR_SIZE),
+			border: UiRect::all(Val::Px(STAR_SIZE / 8.0)),
+			..default()
+		},
+		BackgroundColor(color),
+		BorderColor(color.darker(0.25)),
+		BorderRadius::MAX,
+	)
+}
+
+fn spawn_stage_complete_panel(
+	mut commands: Commands,
+	mode: Res<GameMode>,
+	coins: Res<StageCoins>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	let (GameMode::Stage(index), Some(stage)) = (*mode, stage(*mode)) else {
+		return;
+	};
+	let stars = stage.stars(**coins);
+	let next = if usize::from(index) + 1 < STAGES.len() {
+		"stage-next"
+	} else {
+		"stage-last"
+	};
+	let lines = [
+		locale.text(stage.name),
+		locale.format(
+			"stage-coins",
+			&[("coins", &**coins), ("goal", &stage.goal())],
+		),
+		locale.text(next),
+	];
+	commands
+		.spawn((
+			StageCompleteUi,
+			CleanupOnExit(GameStates::GameOver),
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				..default()
+			},
+		))
+		.with_children(|parent| {
+			parent.spawn(theme.panel()).with_children(|panel| {
+				panel.spawn((
+					Text::new(locale.format("stage-complete", &[("stage", &(index + 1))])),
+					theme.text(72.0),
+				));
+				panel
+					.spawn(Node {
+						column_gap: Val::Px(12.0),
+						..default()
+					})
+					.with_children(|row| {
+						for i in 0..MAX_STARS {
+							row.spawn(star(i < stars));
+						}
+					});
+				for line in lines {
+					panel.spawn((Text::new(line), theme.text(32.0)));
+				}
+			});
+		});
+}
+
+fn start_next_stage(
+	input: PlayerInput,
+	game_over_at: Res<GameOverAt>,
+	time: Res<Time>,
+	mut mode: ResMut<GameMode>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	let GameMode::Stage(index) = *mode else {
+		return;
+	};
+	let next = index + 1;
+	if usize::from(next) >= STAGES.len() || !input.confirm_pressed() {
+		return;
+	}
+	if time.elapsed_secs() - **game_over_at > RESTART_DELAY {
+		*mode = GameMode::Stage(next);
+		next_state.set(GameStates::InGame);
+	}
+}
+
+pub struct StagesPlugin;
+
+impl Plugin for StagesPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<ColumnsSpawned>()
+			.init_resource::<StageCoins>()
+			.init_resource::<StageCleared>()
+			.add_systems(
+				OnEnter(InRun),
+				(reset_stage, spawn_stage_text.run_if(in_stage)),
+			)
+			.add_systems(
+				OnEnter(GameStates::GameOver),
+				record_stars.run_if(stage_cleared.and(crate::counts_for_progress)),
+			)
+			.add_systems(
+				OnEnter(Crash::Over),
+				spawn_stage_complete_panel.run_if(stage_cleared),
+			)
+			.add_systems(
+				FixedUpdate,
+				(
+					spawn_stage_columns
+						.before(crate::handle_pipe_spawn)
+						.in_set(GameplaySet::Spawn),
+					reach_goal
+						.after(crate::apply_scored)
+						.in_set(GameplaySet::Scoring),
+				)
+					.run_if(in_stage),
+			)
+			.add_systems(
+				Update,
+				(
+					(
+						count_stage_coins,
+						update_stage_text.run_if(
+							resource_changed::<PipesPassed>
+								.or(any_match_filter::<Added<StageText>>),
+						),
+					)
+						.run_if(in_state(InRun)),
+					start_next_stage.run_if(in_state(Crash::Over).and(stage_cleared)),
+				)
+					.run_if(in_stage),
+			);
+	}
+}
@@ -0,0 +1,183 @@
+//! Lifetime statistics of live runs, kept between sessions, and the screen
+//! showing them.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	input::PlayerInput,
+	locale::Locale,
+	menu::MenuScreen,
+	profiles::profile_file,
+	save::{read_ron, write_ron},
+	theme::UiTheme,
+	GameScore, GameStates, GameplayEvent, PipesPassed, RunTick,
+};
+
+pub(crate) const STATS_FILE_NAME: &str = "stats.ron";
+
+#[derive(Resource, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Stats {
+	pub flaps: u64,
+	pub runs: u32,
+	pub pipes_passed: u64,
+	/// Gaps made it through with barely any room to spare.
+	pub close_calls: u64,
+	/// Time spent flying, in seconds.
+	pub playtime: f64,
+	pub best_score: i64,
+	/// Sum of the scores of every run, for the average.
+	pub total_score: i64,
+}
+
+impl Stats {
+	fn average_score(&self) -> f64 {
+		if self.runs == 0 {
+			0.0
+		} else {
+			self.total_score as f64 / f64::from(self.runs)
+		}
+	}
+}
+
+#[derive(Component)]
+struct StatsUi;
+
+fn format_playtime(seconds: f64) -> String {
+	let seconds = seconds as u64;
+	format!(
+		"{}:{:02}:{:02}",
+		seconds / 3600,
+		seconds / 60 % 60,
+		seconds % 60
+	)
+}
+
+/// Replays play back flaps too, only count those of live runs.
+fn count_flaps(mut events: EventReader<GameplayEvent>, mut stats: ResMut<Stats>) {
+	for event in events.read() {
+		match event {
+			GameplayEvent::Flapped(_) => stats.flaps += 1,
+			GameplayEvent::CloseCall => stats.close_calls += 1,
+			_ => {}
+		}
+	}
+}
+
+fn record_run(
+	score: Res<GameScore>,
+	pipes_passed: Res<PipesPassed>,
+	run_tick: Res<RunTick>,
+	fixed_time: Res<Time<Fixed>>,
+	mut stats: ResMut<Stats>,
+) {
+	stats.runs += 1;
+	stats.pipes_passed += u64::from(**pipes_passed);
+	stats.playtime += f64::from(**run_tick) * fixed_time.timestep().as_secs_f64();
+	stats.best_score = stats.best_score.max(**score);
+	stats.total_score += **score;
+}
+
+fn spawn_stats(
+	mut commands: Commands,
+	stats: Res<Stats>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	let rows = [
+		("stats-runs", stats.runs.to_string()),
+		("stats-flaps", stats.flaps.to_string()),
+		("stats-pipes", stats.pipes_passed.to_string()),
+		("stats-close-calls", stats.close_calls.to_string()),
+		("stats-time", format_playtime(stats.playtime)),
+		("stats-best", stats.best_score.to_string()),
+		("stats-average", format!("{:.1}", stats.average_score())),
+	];
+	commands
+		.spawn((
+			StatsUi,
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				row_gap: Val::Px(16.0),
+				..default()
+			},
+			BackgroundColor(theme.screen),
+		))
+		.with_children(|parent| {
+			parent.spawn((Text::new(locale.text("stats-title")), theme.text(72.0)));
+			parent
+				.spawn(Node {
+					display: Display::Grid,
+					grid_template_columns: vec![GridTrack::auto(); 2],
+					column_gap: Val::Px(48.0),
+					row_gap: Val::Px(4.0),
+					..default()
+				})
+				.with_children(|table| {
+					for (label, value) in rows {
+						table.spawn((Text::new(locale.text(label)), theme.text(32.0)));
+						table.spawn((Text::new(value), theme.text(32.0)));
+					}
+				});
+			parent.spawn((Text::new(locale.text("back")), theme.text(24.0)));
+		});
+}
+
+fn despawn_stats(mut commands: Commands, ui: Single<Entity, With<StatsUi>>) {
+	commands.entity(*ui).despawn();
+}
+
+fn open_stats_on_t(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyT) {
+		next_screen.set(MenuScreen::Stats);
+	}
+}
+
+fn close_stats(
+	input: PlayerInput,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::Escape) || input.confirm_pressed() {
+		next_screen.set(MenuScreen::Main);
+	}
+}
+
+fn write_stats(stats: Res<Stats>) {
+	write_ron(&profile_file(STATS_FILE_NAME), &*stats);
+}
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+	fn build(&self, app: &mut App) {
+		app.insert_resource(read_ron::<Stats>(&profile_file(STATS_FILE_NAME)).unwrap_or_default())
+			.add_systems(
+				OnExit(GameStates::InGame),
+				record_run.run_if(in_state(GameStates::GameOver).and(crate::counts_for_progress)),
+			)
+			.add_systems(OnEnter(MenuScreen::Stats), spawn_stats)
+			.add_systems(OnExit(MenuScreen::Stats), despawn_stats)
+			.add_systems(
+				Update,
+				(
+					count_flaps
+						.run_if(in_state(GameStates::InGame).and(crate::counts_for_progress)),
+					open_stats_on_t.run_if(in_state(MenuScreen::Main)),
+					close_stats.run_if(in_state(MenuScreen::Stats)),
+				),
+			)
+			.add_systems(
+				Last,
+				write_stats.run_if(resource_changed::<Stats>.and(not(resource_added::<Stats>))),
+			);
+	}
+}
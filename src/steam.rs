@@ -0,0 +1,183 @@
+//! Syncing to Steam, built in with the `steam` feature. Unlocked achievements
+//! and the best endless score are pushed to Steamworks as they change, and all
+//! of them once at startup in case they changed while Steam wasn't around.
+//!
+//! The save file stays the source of truth for these: nothing is read back
+//! from Steam, and without a running Steam client the game plays just the
+//! same. Unless `--cloud-save` says otherwise, the save file itself is kept in
+//! Steam Cloud, see [`cloud_sync`](crate::cloud_sync). The
+//! achievements and the `Best score` leaderboard have to be set up for the app
+//! in Steamworks, with the names used here.
+
+use std::{
+	io::{self, Read, Write},
+	sync::{Arc, Mutex},
+};
+
+use bevy::prelude::*;
+use steamworks::{
+	Client, Leaderboard, LeaderboardDisplayType, LeaderboardSortMethod, SingleClient,
+	UploadScoreMethod,
+};
+
+use crate::{
+	achievements::Achievement,
+	cloud_sync::{CloudSave, CloudStorage},
+	save::SaveData,
+};
+
+const LEADERBOARD_NAME: &str = "Best score";
+
+/// Name of the achievement in Steamworks.
+fn api_name(achievement: Achievement) -> &'static str {
+	match achievement {
+		Achievement::TenPipes => "TEN_PIPES",
+		Achievement::FiftyPipes => "FIFTY_PIPES",
+		Achievement::QuickCrash => "QUICK_CRASH",
+		Achievement::TenRuns => "TEN_RUNS",
+		Achievement::HundredRuns => "HUNDRED_RUNS",
+	}
+}
+
+/// The save of each profile as a file in Steam Cloud.
+struct SteamCloud(Client);
+
+fn cloud_file_name(profile: &str) -> String {
+	format!("save-{profile}.ron")
+}
+
+impl CloudStorage for SteamCloud {
+	fn download(&self, profile: &str) -> io::Result<Option<String>> {
+		let file = self.0.remote_storage().file(&cloud_file_name(profile));
+		if !file.exists() {
+			return Ok(None);
+		}
+		let mut contents = String::new();
+		file.read().read_to_string(&mut contents)?;
+		Ok(Some(contents))
+	}
+
+	fn upload(&self, profile: &str, contents: &str) -> io::Result<()> {
+		let mut file = self
+			.0
+			.remote_storage()
+			.file(&cloud_file_name(profile))
+			.write();
+		file.write_all(contents.as_bytes())?;
+		file.flush()
+	}
+}
+
+#[derive(Resource)]
+struct Steam {
+	client: Client,
+	/// Filled in once Steam answers with the leaderboard.
+	leaderboard: Arc<Mutex<Option<Leaderboard>>>,
+}
+
+/// Runs the callbacks of requests made to Steam, only ever from the main
+/// thread.
+struct SteamCallbacks(SingleClient);
+
+/// What has been sent to Steam so far this session.
+#[derive(Resource, Default)]
+struct Synced {
+	achievements: Vec<Achievement>,
+	best: Option<i64>,
+}
+
+fn run_callbacks(callbacks: NonSend<SteamCallbacks>) {
+	callbacks.0.run_callbacks();
+}
+
+fn sync_achievements(steam: Res<Steam>, save: Res<SaveData>, mut synced: ResMut<Synced>) {
+	let stats = steam.client.user_stats();
+	let mut unlocked_any = false;
+	for &achievement in &save.achievements.unlocked {
+		if synced.achievements.contains(&achievement) {
+			continue;
+		}
+		if stats.achievement(api_name(achievement)).set().is_err() {
+			warn!(
+				"Steam doesn't know the achievement {}",
+				api_name(achievement)
+			);
+		}
+		synced.achievements.push(achievement);
+		unlocked_any = true;
+	}
+	if unlocked_any && stats.store_stats().is_err() {
+		warn!("Failed to store achievements on Steam");
+	}
+}
+
+/// Waits for the leaderboard to be found before sending anything, so the best
+/// score is sent once it is.
+fn sync_best_score(steam: Res<Steam>, save: Res<SaveData>, mut synced: ResMut<Synced>) {
+	let best = save.high_scores.best();
+	if best <= 0 || synced.best == Some(best) {
+		return;
+	}
+	let Some(leaderboard) = steam.leaderboard.lock().unwrap().clone() else {
+		return;
+	};
+	let score = best.clamp(0, i64::from(i32::MAX)) as i32;
+	steam.client.user_stats().upload_leaderboard_score(
+		&leaderboard,
+		UploadScoreMethod::KeepBest,
+		score,
+		&[],
+		move |result| {
+			if let Err(err) = result {
+				warn!("Failed to upload a score of {score} to Steam: {err}");
+			}
+		},
+	);
+	synced.best = Some(best);
+}
+
+pub struct SteamPlugin;
+
+impl Plugin for SteamPlugin {
+	fn build(&self, app: &mut App) {
+		let (client, single) = match Client::init() {
+			Ok(clients) => clients,
+			Err(err) => {
+				info!("Playing without Steam: {err}");
+				return;
+			}
+		};
+		client.user_stats().request_current_stats();
+		let leaderboard = Arc::new(Mutex::new(None));
+		let found = leaderboard.clone();
+		client.user_stats().find_or_create_leaderboard(
+			LEADERBOARD_NAME,
+			LeaderboardSortMethod::Descending,
+			LeaderboardDisplayType::Numeric,
+			move |result| match result {
+				Ok(board) => *found.lock().unwrap() = board,
+				Err(err) => warn!("Failed to find the Steam leaderboard: {err}"),
+			},
+		);
+		if !app.world().contains_resource::<CloudSave>()
+			&& client.remote_storage().is_cloud_enabled_for_app()
+		{
+			app.insert_resource(CloudSave(Arc::new(SteamCloud(client.clone()))));
+		}
+		app.insert_resource(Steam {
+			client,
+			leaderboard,
+		})
+		.insert_non_send_resource(SteamCallbacks(single))
+		.init_resource::<Synced>()
+		.add_systems(
+			Update,
+			(
+				run_callbacks,
+				sync_achievements.run_if(resource_changed::<SaveData>),
+				sync_best_score,
+			)
+				.chain(),
+		);
+	}
+}
@@ -0,0 +1,217 @@
+//! Tool-assisted runs: a script loaded with `--tas <file>` plays every run in
+//! place of the player, flapping on exactly the ticks it lists. Along with the
+//! seed, that makes runs play out the same every time, for authoring precise
+//! runs and regression scenarios, also with `--headless`.
+//!
+//! Scripts are plain text, one entry per line, with `#` starting a comment:
+//!
+//! ```text
+//! seed 1234
+//! mode endless
+//! 0
+//! +24
+//! 60
+//! ```
+//!
+//! `seed` and `mode`, taking the same values as `--seed` and `--mode`, are
+//! optional. Every other line is a flap: the run tick to flap on, or `+n` for
+//! `n` ticks after the previous flap. While paused, `.` steps the run forward
+//! by a single tick.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit, cli, get_ready::RunPhase, locale::Locale, pause::Pause, theme::UiTheme,
+	GameMode, GameStates, GameplaySet, InRun, PendingFlap, RunTick,
+};
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TasScript {
+	pub name: String,
+	pub seed: Option<u64>,
+	pub mode: Option<GameMode>,
+	/// Run ticks to flap on, in increasing order.
+	pub flaps: Vec<u32>,
+}
+
+impl TasScript {
+	pub fn load(path: &str) -> Result<Self, String> {
+		let contents = std::fs::read_to_string(path).map_err(|err| format!("{path}: {err}"))?;
+		let mut script = Self::parse(&contents).map_err(|err| format!("{path}: {err}"))?;
+		script.name = path.to_string();
+		Ok(script)
+	}
+
+	fn parse(contents: &str) -> Result<Self, String> {
+		let mut script = TasScript::default();
+		for (number, line) in contents.lines().enumerate() {
+			let line = line.split('#').next().unwrap_or_default().trim();
+			if line.is_empty() {
+				continue;
+			}
+			let invalid = || format!("line {}: can't make sense of {line:?}", number + 1);
+			let last = script.flaps.last().copied();
+			match line.split_once(' ') {
+				Some(("seed", value)) => {
+					script.seed = Some(value.trim().parse().map_err(|_| invalid())?);
+				}
+				Some(("mode", value)) => {
+					script.mode = Some(cli::parse_mode(value.trim()).ok_or_else(invalid)?);
+				}
+				Some(_) => return Err(invalid()),
+				None => {
+					let tick = match line.strip_prefix('+') {
+						Some(after) => after
+							.parse::<u32>()
+							.ok()
+							.and_then(|after| last.unwrap_or_default().checked_add(after)),
+						None => line.parse().ok(),
+					}
+					.ok_or_else(invalid)?;
+					if last.is_some_and(|last| tick <= last) {
+						return Err(format!(
+							"line {}: flaps have to come in order, tick {tick} is not after {}",
+							number + 1,
+							last.unwrap_or_default()
+						));
+					}
+					script.flaps.push(tick);
+				}
+			}
+		}
+		Ok(script)
+	}
+}
+
+/// Whether a script plays the runs, which live input is then left out of.
+pub fn scripted(script: Option<Res<TasScript>>) -> bool {
+	script.is_some()
+}
+
+/// Index of the next flap of the script to feed in.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct NextFlap(usize);
+
+#[derive(Component)]
+struct TasText;
+
+fn start_script(mut commands: Commands, mut next_flap: ResMut<NextFlap>, theme: Res<UiTheme>) {
+	**next_flap = 0;
+	commands.spawn((
+		TasText,
+		CleanupOnExit(InRun),
+		Text::default(),
+		theme.text(24.0),
+		Node {
+			position_type: PositionType::Absolute,
+			bottom: Val::Px(5.0),
+			left: Val::Px(5.0),
+			..default()
+		},
+	));
+}
+
+/// The first flap of the script decides when the bird takes off, so there's
+/// nothing to wait for.
+fn take_off(mut next_phase: ResMut<NextState<RunPhase>>) {
+	next_phase.set(RunPhase::Flying);
+}
+
+fn play_script(
+	script: Res<TasScript>,
+	mut next_flap: ResMut<NextFlap>,
+	mut pending_flap: ResMut<PendingFlap>,
+	run_tick: Res<RunTick>,
+	time: Res<Time<Real>>,
+) {
+	while script.flaps.get(**next_flap) == Some(&**run_tick) {
+		pending_flap.press(time.elapsed_secs());
+		**next_flap += 1;
+	}
+}
+
+/// Lets the simulation run exactly one more tick, even with virtual time
+/// stopped.
+fn step_on_period(keyboard_input: Res<ButtonInput<KeyCode>>, mut fixed_time: ResMut<Time<Fixed>>) {
+	if keyboard_input.just_pressed(KeyCode::Period) {
+		let timestep = fixed_time.timestep();
+		fixed_time.accumulate(timestep);
+	}
+}
+
+fn update_tas_text(
+	script: Res<TasScript>,
+	next_flap: Res<NextFlap>,
+	run_tick: Res<RunTick>,
+	locale: Res<Locale>,
+	mut text: Single<&mut Text, With<TasText>>,
+) {
+	text.0 = locale.format(
+		"tas-status",
+		&[
+			("name", &script.name),
+			("tick", &**run_tick),
+			("flap", &**next_flap),
+			("flaps", &script.flaps.len()),
+		],
+	);
+}
+
+pub struct TasPlugin;
+
+impl Plugin for TasPlugin {
+	fn build(&self, app: &mut App) {
+		let Some(script) = app
+			.world()
+			.get_resource::<cli::LaunchOptions>()
+			.and_then(|options| options.tas.clone())
+		else {
+			return;
+		};
+		app.insert_resource(script)
+			.init_resource::<NextFlap>()
+			.add_systems(OnEnter(GameStates::InGame), start_script)
+			.add_systems(OnEnter(RunPhase::GetReady), take_off)
+			.add_systems(
+				FixedUpdate,
+				// Replays play back the flaps they recorded instead.
+				play_script
+					.in_set(GameplaySet::Input)
+					.before(crate::apply_flap)
+					.run_if(in_state(GameStates::InGame)),
+			)
+			.add_systems(
+				Update,
+				(
+					step_on_period.run_if(in_state(Pause::Paused)),
+					update_tas_text.run_if(in_state(GameStates::InGame)),
+				),
+			);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_settings_and_flaps() {
+		let script = TasScript::parse("seed 1234\nmode endless # comment\n\n0\n+24\n60\n").unwrap();
+		assert_eq!(script.seed, Some(1234));
+		assert_eq!(script.mode, Some(GameMode::Endless));
+		assert_eq!(script.flaps, [0, 24, 60]);
+	}
+
+	#[test]
+	fn rejects_flaps_out_of_order() {
+		assert!(TasScript::parse("10\n5\n").is_err());
+		assert!(TasScript::parse("10\n+0\n").is_err());
+	}
+
+	#[test]
+	fn rejects_unknown_lines() {
+		assert!(TasScript::parse("speed 2\n").is_err());
+		assert!(TasScript::parse("soon\n").is_err());
+		assert!(TasScript::parse("mode sideways\n").is_err());
+	}
+}
@@ -0,0 +1,161 @@
+//! [`TestGame`], for the integration tests in `tests/` to drive the real game
+//! headless, one simulation tick at a time, and look at and poke into what
+//! they need to check. Not meant for anything else, so it's only built with the
+//! `test-support` feature the tests turn on, see [`FlappyEnv`](crate::FlappyEnv)
+//! for playing the game from code.
+
+use bevy::prelude::*;
+
+use crate::{
+	cli::LaunchOptions,
+	env::{headless_app, is_in},
+	get_ready::RunPhase,
+	interpolation::Interpolated,
+	next_gap, GameScore, GameStates, PendingFlap, Pipe, PipesPassed, Player, RunTick, Velocity,
+};
+
+pub struct TestGame {
+	app: App,
+}
+
+impl TestGame {
+	/// Waits in the menu, with the same pipes for every run.
+	pub fn new(seed: u64) -> Self {
+		TestGame {
+			app: headless_app(LaunchOptions {
+				seed: Some(seed),
+				..default()
+			}),
+		}
+	}
+
+	/// Starts an endless run from the menu or the game over screen, the way
+	/// playing or restarting does, and lets it get to the first tick.
+	pub fn start_run(&mut self) {
+		self.app
+			.world_mut()
+			.resource_mut::<NextState<GameStates>>()
+			.set(GameStates::InGame);
+		while !self.is_flying() {
+			self.app.update();
+		}
+	}
+
+	/// Headless, every update simulates exactly one tick.
+	pub fn tick(&mut self) {
+		self.app.update();
+	}
+
+	pub fn flap(&mut self) {
+		let now = self.app.world().resource::<Time<Real>>().elapsed_secs();
+		self.app
+			.world_mut()
+			.resource_mut::<PendingFlap>()
+			.press(now);
+		self.tick();
+	}
+
+	/// Ticks until `done` or `max_ticks` have gone by, and tells which.
+	pub fn tick_until(&mut self, max_ticks: u32, done: impl Fn(&mut Self) -> bool) -> bool {
+		for _ in 0..max_ticks {
+			if done(self) {
+				return true;
+			}
+			self.tick();
+		}
+		done(self)
+	}
+
+	fn is_flying(&self) -> bool {
+		self.app
+			.world()
+			.get_resource::<State<RunPhase>>()
+			.is_some_and(|phase| *phase.get() == RunPhase::Flying)
+	}
+
+	/// Also once the crash is certain, before the game over it leads to.
+	pub fn is_game_over(&self) -> bool {
+		let world = self.app.world();
+		is_in(world, GameStates::GameOver)
+			|| matches!(
+				*world.resource::<NextState<GameStates>>(),
+				NextState::Pending(GameStates::GameOver)
+			)
+	}
+
+	pub fn score(&self) -> i64 {
+		**self.app.world().resource::<GameScore>()
+	}
+
+	pub fn pipes_passed(&self) -> u32 {
+		**self.app.world().resource::<PipesPassed>()
+	}
+
+	pub fn run_tick(&self) -> u32 {
+		**self.app.world().resource::<RunTick>()
+	}
+
+	pub fn birds(&mut self) -> usize {
+		let world = self.app.world_mut();
+		world
+			.query_filtered::<(), With<Player>>()
+			.iter(world)
+			.count()
+	}
+
+	pub fn pipes_in_play(&mut self) -> usize {
+		let world = self.app.world_mut();
+		world.query_filtered::<(), With<Pipe>>().iter(world).count()
+	}
+
+	pub fn bird_position(&mut self) -> Option<Vec2> {
+		let world = self.app.world_mut();
+		world
+			.query_filtered::<&Transform, With<Player>>()
+			.single(world)
+			.ok()
+			.map(|transform| transform.translation.truncate())
+	}
+
+	/// Middle of the gap the bird has to fly through next, if one has come in
+	/// yet.
+	pub fn next_gap(&mut self) -> Option<Vec2> {
+		let bird_x = self.bird_position()?.x;
+		let world = self.app.world_mut();
+		next_gap(
+			bird_x,
+			world.query::<(&Pipe, &Transform, &Sprite)>().iter(world),
+		)
+	}
+
+	/// Puts the bird at `position`, at rest.
+	pub fn move_bird(&mut self, position: Vec2) {
+		let world = self.app.world_mut();
+		let Ok((mut transform, mut velocity, mut interpolated)) = world
+			.query_filtered::<(&mut Transform, &mut Velocity, &mut Interpolated), With<Player>>()
+			.single_mut(world)
+		else {
+			return;
+		};
+		transform.translation = position.extend(transform.translation.z);
+		velocity.y = 0.0;
+		interpolated.teleported();
+	}
+
+	/// Keeps the bird where it is horizontally, at height `y`.
+	pub fn hold_bird_at(&mut self, y: f32) {
+		if let Some(bird) = self.bird_position() {
+			self.move_bird(Vec2::new(bird.x, y));
+		}
+	}
+
+	/// Middle of a pipe in play, if there's one yet.
+	pub fn any_pipe(&mut self) -> Option<Vec2> {
+		let world = self.app.world_mut();
+		world
+			.query_filtered::<&Transform, With<Pipe>>()
+			.iter(world)
+			.next()
+			.map(|transform| transform.translation.truncate())
+	}
+}
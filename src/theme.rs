@@ -0,0 +1,200 @@
+//! The look of the interface, picked under Theme in the settings: fonts,
+//! colors, spacing and buttons, read from the [`UiTheme`] resource by the
+//! menus, screens and panels as they're spawned. Text already on screen
+//! switches to the font and color of a new theme right away, the rest of a
+//! screen picks it up the next time it opens.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{loading::Preload, settings::Settings};
+
+const DARK_FONT_PATH: &str = "fonts/DejaVuSans.ttf";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeName {
+	/// The look the game always had, light text over the game itself.
+	#[default]
+	Classic,
+	/// Darker, solid panels and screens with a blue accent.
+	Dark,
+}
+
+impl ThemeName {
+	pub const ALL: [ThemeName; 2] = [ThemeName::Classic, ThemeName::Dark];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			ThemeName::Classic => "Classic",
+			ThemeName::Dark => "Dark",
+		}
+	}
+}
+
+/// Fonts of the themes that don't use Bevy's own, loaded with everything else.
+#[derive(Resource)]
+pub struct ThemeFonts {
+	dark: Handle<Font>,
+}
+
+impl FromWorld for ThemeFonts {
+	fn from_world(world: &mut World) -> Self {
+		let fonts = ThemeFonts {
+			dark: world.resource::<AssetServer>().load(DARK_FONT_PATH),
+		};
+		Preload::add(world, [fonts.dark.clone().untyped()]);
+		fonts
+	}
+}
+
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct UiTheme {
+	pub font: Handle<Font>,
+	pub text: Color,
+	/// For what is highlighted, like the selected row of the settings.
+	pub accent: Color,
+	/// Behind full screens like the menu and the settings.
+	pub screen: Color,
+	/// Behind panels shown over the game, like the game over one.
+	pub panel: Color,
+	pub panel_padding: f32,
+	pub panel_radius: f32,
+	pub button: Color,
+	pub selected_button: Color,
+	pub button_padding: Vec2,
+	pub button_radius: f32,
+}
+
+impl UiTheme {
+	pub fn new(name: ThemeName, fonts: &ThemeFonts) -> Self {
+		match name {
+			ThemeName::Classic => UiTheme {
+				font: Handle::default(),
+				text: Color::WHITE,
+				accent: Color::srgb(1.0, 0.85, 0.2),
+				screen: Color::NONE,
+				panel: Color::srgba(0.0, 0.0, 0.0, 0.6),
+				panel_padding: 32.0,
+				panel_radius: 16.0,
+				button: Color::srgb(0.2, 0.2, 0.25),
+				selected_button: Color::srgb(0.85, 0.65, 0.1),
+				button_padding: Vec2::new(24.0, 12.0),
+				button_radius: 8.0,
+			},
+			ThemeName::Dark => UiTheme {
+				font: fonts.dark.clone(),
+				text: Color::srgb(0.85, 0.87, 0.9),
+				accent: Color::srgb(0.45, 0.7, 1.0),
+				screen: Color::srgba(0.04, 0.04, 0.06, 0.9),
+				panel: Color::srgba(0.06, 0.06, 0.08, 0.92),
+				panel_padding: 40.0,
+				panel_radius: 4.0,
+				button: Color::srgb(0.13, 0.14, 0.17),
+				selected_button: Color::srgb(0.25, 0.45, 0.85),
+				button_padding: Vec2::new(28.0, 14.0),
+				button_radius: 4.0,
+			},
+		}
+	}
+
+	pub fn font(&self, size: f32) -> TextFont {
+		TextFont {
+			font: self.font.clone(),
+			font_size: size,
+			..default()
+		}
+	}
+
+	/// Font and color for text of this size.
+	pub fn text(&self, size: f32) -> impl Bundle {
+		(self.font(size), TextColor(self.text))
+	}
+
+	/// A panel laying out its children in a column, centered.
+	pub fn panel(&self) -> impl Bundle {
+		(
+			Node {
+				flex_direction: FlexDirection::Column,
+				align_items: AlignItems::Center,
+				row_gap: Val::Px(12.0),
+				padding: UiRect::all(Val::Px(self.panel_padding)),
+				..default()
+			},
+			BackgroundColor(self.panel),
+			BorderRadius::all(Val::Px(self.panel_radius)),
+		)
+	}
+
+	pub fn button(&self, selected: bool) -> impl Bundle {
+		(
+			Node {
+				padding: UiRect::axes(
+					Val::Px(self.button_padding.x),
+					Val::Px(self.button_padding.y),
+				),
+				..default()
+			},
+			BackgroundColor(self.button_color(selected)),
+			BorderRadius::all(Val::Px(self.button_radius)),
+		)
+	}
+
+	pub fn button_color(&self, selected: bool) -> Color {
+		if selected {
+			self.selected_button
+		} else {
+			self.button
+		}
+	}
+}
+
+impl FromWorld for UiTheme {
+	fn from_world(world: &mut World) -> Self {
+		world.init_resource::<ThemeFonts>();
+		let name = world
+			.get_resource::<Settings>()
+			.map_or_else(ThemeName::default, |settings| settings.theme);
+		UiTheme::new(name, world.resource::<ThemeFonts>())
+	}
+}
+
+fn apply_theme(settings: Res<Settings>, fonts: Res<ThemeFonts>, mut theme: ResMut<UiTheme>) {
+	theme.set_if_neq(UiTheme::new(settings.theme, &fonts));
+}
+
+/// Moves text still in the font and color of the theme before over to the new
+/// one. Text colored by something else, like a highlighted row, is left to it.
+fn restyle_text(
+	theme: Res<UiTheme>,
+	mut previous: Local<Option<UiTheme>>,
+	texts: Query<(&mut TextFont, &mut TextColor)>,
+) {
+	if let Some(previous) = previous.as_ref() {
+		for (mut font, mut color) in texts {
+			if font.font == previous.font {
+				font.font = theme.font.clone();
+			}
+			if color.0 == previous.text {
+				color.0 = theme.text;
+			}
+		}
+	}
+	*previous = Some(theme.clone());
+}
+
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<ThemeFonts>()
+			.init_resource::<UiTheme>()
+			.add_systems(
+				Update,
+				(
+					apply_theme.run_if(resource_changed::<Settings>),
+					restyle_text.run_if(resource_changed::<UiTheme>),
+				)
+					.chain(),
+			);
+	}
+}
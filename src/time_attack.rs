@@ -0,0 +1,349 @@
+//! Time attack, started with I from the main menu: passing a set number of
+//! pipes as fast as possible, against a timer to the millisecond. The time at
+//! each pipe is kept as a split, and the splits of the best run to each target
+//! are saved to compare the next runs against, both on the timer and on the
+//! results screen.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	cleanup::CleanupOnExit, crash::Crash, locale::Locale, palette::Palette, save::SaveData,
+	settings::Settings, theme::UiTheme, GameMode, GameStates, GameplaySet, InRun, PipesPassed,
+	RunTick,
+};
+
+/// Pipes to pass for each target, picked with U in the menu.
+pub const TARGETS: [u32; 3] = [10, 25, 50];
+/// The results screen shows every this many splits, and the last one.
+const SPLIT_STEP: usize = 5;
+
+/// Best runs to each target, kept in the save file.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct TimeAttackRecord {
+	/// Splits of the best run to each target, by index in [`TARGETS`]. Empty
+	/// for targets never reached.
+	pub best_splits: Vec<Vec<u32>>,
+}
+
+impl TimeAttackRecord {
+	fn best(&self, target: u8) -> Option<&[u32]> {
+		self.best_splits
+			.get(usize::from(target))
+			.filter(|splits| !splits.is_empty())
+			.map(Vec::as_slice)
+	}
+
+	pub fn menu_entry(&self, target: u8, locale: &Locale) -> String {
+		let pipes = TARGETS[usize::from(target)];
+		match self.best(target).and_then(|splits| splits.last()) {
+			Some(&best) => locale.format(
+				"menu-time-attack-best",
+				&[("pipes", &pipes), ("best", &format_millis(best))],
+			),
+			None => locale.format("menu-time-attack", &[("pipes", &pipes)]),
+		}
+	}
+}
+
+/// Target picked in the menu, by index in [`TARGETS`].
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct SelectedTarget(pub u8);
+
+/// Milliseconds since the start of the run at each pipe passed so far.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct Splits(Vec<u32>);
+
+/// Splits of the best run to the target of the current run, from before it
+/// started, to compare against.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct Compared(Vec<u32>);
+
+/// Whether the run that just ended reached its target, rather than crashing
+/// on the way.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct TargetReached(bool);
+
+pub fn target_reached(reached: Res<TargetReached>) -> bool {
+	**reached
+}
+
+/// The target of the current run, if it's a time attack.
+fn target(mode: GameMode) -> Option<u8> {
+	match mode {
+		GameMode::TimeAttack(target) if usize::from(target) < TARGETS.len() => Some(target),
+		_ => None,
+	}
+}
+
+pub fn in_time_attack(mode: Res<GameMode>) -> bool {
+	target(*mode).is_some()
+}
+
+fn format_millis(millis: u32) -> String {
+	format!(
+		"{}:{:02}.{:03}",
+		millis / 60_000,
+		millis / 1000 % 60,
+		millis % 1000
+	)
+}
+
+/// Ahead of the best run when negative, colored as good or bad news in the
+/// palette.
+fn format_diff(millis: u32, best: u32, palette: Palette) -> (String, Color) {
+	let diff = i64::from(millis) - i64::from(best);
+	let sign = if diff < 0 { '-' } else { '+' };
+	let abs = diff.unsigned_abs();
+	let color = if diff < 0 {
+		palette.good()
+	} else {
+		palette.bad()
+	};
+	(format!("{sign}{}.{:03}", abs / 1000, abs % 1000), color)
+}
+
+fn elapsed_millis(run_tick: &RunTick, fixed_time: &Time<Fixed>) -> u32 {
+	(f64::from(**run_tick) * fixed_time.timestep().as_secs_f64() * 1000.0).round() as u32
+}
+
+#[derive(Component)]
+struct TimerText;
+
+#[derive(Component)]
+struct SplitText;
+
+fn reset_time_attack(
+	mut splits: ResMut<Splits>,
+	mut reached: ResMut<TargetReached>,
+	mut compared: ResMut<Compared>,
+	mode: Res<GameMode>,
+	save: Res<SaveData>,
+) {
+	splits.clear();
+	**reached = false;
+	**compared = target(*mode)
+		.and_then(|target| save.time_attack.best(target))
+		.unwrap_or_default()
+		.to_vec();
+}
+
+fn spawn_timer(mut commands: Commands, theme: Res<UiTheme>) {
+	commands
+		.spawn((
+			CleanupOnExit(InRun),
+			Node {
+				position_type: PositionType::Absolute,
+				width: Val::Percent(100.0),
+				top: Val::Px(48.0),
+				flex_direction: FlexDirection::Column,
+				align_items: AlignItems::Center,
+				..default()
+			},
+		))
+		.with_children(|parent| {
+			parent.spawn((TimerText, Text::default(), theme.text(40.0)));
+			parent.spawn((SplitText, Text::default(), theme.text(28.0)));
+		});
+}
+
+fn record_splits(
+	mut splits: ResMut<Splits>,
+	mut reached: ResMut<TargetReached>,
+	mode: Res<GameMode>,
+	pipes_passed: Res<PipesPassed>,
+	run_tick: Res<RunTick>,
+	fixed_time: Res<Time<Fixed>>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	let Some(target) = target(*mode) else {
+		return;
+	};
+	while splits.len() < **pipes_passed as usize {
+		splits.push(elapsed_millis(&run_tick, &fixed_time));
+	}
+	if **pipes_passed >= TARGETS[usize::from(target)] {
+		**reached = true;
+		next_state.set(GameStates::GameOver);
+	}
+}
+
+fn update_timer(
+	run_tick: Res<RunTick>,
+	fixed_time: Res<Time<Fixed>>,
+	mode: Res<GameMode>,
+	splits: Res<Splits>,
+	compared: Res<Compared>,
+	settings: Res<Settings>,
+	mut timer: Single<&mut Text, (With<TimerText>, Without<SplitText>)>,
+	split_text: Single<(&mut Text, &mut TextColor), With<SplitText>>,
+) {
+	let Some(target) = target(*mode) else {
+		return;
+	};
+	timer.0 = format!(
+		"{} - {}/{}",
+		format_millis(elapsed_millis(&run_tick, &fixed_time)),
+		splits.len(),
+		TARGETS[usize::from(target)]
+	);
+	let (mut text, mut color) = split_text.into_inner();
+	// Compares the last split against the same pipe of the best run.
+	let same_pipe = splits
+		.len()
+		.checked_sub(1)
+		.and_then(|index| compared.get(index));
+	match (splits.last(), same_pipe) {
+		(Some(&split), Some(&best)) => {
+			let (diff, diff_color) = format_diff(split, best, settings.palette);
+			text.0 = diff;
+			color.0 = diff_color;
+		}
+		_ => text.0.clear(),
+	}
+}
+
+/// Replays can't do better than the run they recorded, so they are left to
+/// count too.
+fn record_best(mode: Res<GameMode>, splits: Res<Splits>, mut save: ResMut<SaveData>) {
+	let Some(target) = target(*mode) else {
+		return;
+	};
+	let index = usize::from(target);
+	let record = &mut save.time_attack;
+	let improved = record
+		.best(target)
+		.and_then(|best| best.last())
+		.is_none_or(|best| splits.last().is_some_and(|time| time < best));
+	if !improved {
+		return;
+	}
+	if record.best_splits.len() <= index {
+		record.best_splits.resize(index + 1, Vec::new());
+	}
+	record.best_splits[index] = splits.clone();
+}
+
+fn spawn_results_panel(
+	mut commands: Commands,
+	mode: Res<GameMode>,
+	splits: Res<Splits>,
+	compared: Res<Compared>,
+	settings: Res<Settings>,
+	locale: Res<Locale>,
+	theme: Res<UiTheme>,
+) {
+	let Some(target) = target(*mode) else {
+		return;
+	};
+	let Some(&time) = splits.last() else {
+		return;
+	};
+	let headline = match compared.last() {
+		Some(&best) if best <= time => locale.format(
+			"time-attack-behind",
+			&[
+				("time", &format_millis(time)),
+				("diff", &format_diff(time, best, settings.palette).0),
+			],
+		),
+		_ => locale.format("time-attack-best", &[("time", &format_millis(time))]),
+	};
+	let shown = (SPLIT_STEP - 1..splits.len())
+		.step_by(SPLIT_STEP)
+		.chain([splits.len() - 1])
+		.collect::<Vec<_>>();
+	commands
+		.spawn((
+			CleanupOnExit(GameStates::GameOver),
+			Node {
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				..default()
+			},
+		))
+		.with_children(|parent| {
+			parent.spawn(theme.panel()).with_children(|panel| {
+				panel.spawn((
+					Text::new(locale.format(
+						"time-attack-reached",
+						&[("pipes", &TARGETS[usize::from(target)])],
+					)),
+					theme.text(72.0),
+				));
+				panel.spawn((Text::new(headline), theme.text(32.0)));
+				panel
+					.spawn(Node {
+						display: Display::Grid,
+						grid_template_columns: vec![GridTrack::auto(); 3],
+						column_gap: Val::Px(32.0),
+						row_gap: Val::Px(4.0),
+						..default()
+					})
+					.with_children(|table| {
+						let mut last = None;
+						for index in shown {
+							// The last split may already be one of the steps.
+							if last == Some(index) {
+								continue;
+							}
+							last = Some(index);
+							let split = splits[index];
+							let (diff, color) = compared
+								.get(index)
+								.map_or((String::new(), theme.text), |&best| {
+									format_diff(split, best, settings.palette)
+								});
+							for (cell, color) in [
+								(
+									locale.format("time-attack-split", &[("pipe", &(index + 1))]),
+									theme.text,
+								),
+								(format_millis(split), theme.text),
+								(diff, color),
+							] {
+								table.spawn((Text::new(cell), theme.font(24.0), TextColor(color)));
+							}
+						}
+					});
+				panel.spawn((Text::new(locale.text("retry-help")), theme.text(32.0)));
+			});
+		});
+}
+
+pub struct TimeAttackPlugin;
+
+impl Plugin for TimeAttackPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<SelectedTarget>()
+			.init_resource::<Splits>()
+			.init_resource::<TargetReached>()
+			.init_resource::<Compared>()
+			.add_systems(
+				OnEnter(InRun),
+				(reset_time_attack, spawn_timer.run_if(in_time_attack)),
+			)
+			.add_systems(
+				OnEnter(GameStates::GameOver),
+				record_best.run_if(target_reached.and(crate::counts_for_progress)),
+			)
+			.add_systems(
+				OnEnter(Crash::Over),
+				spawn_results_panel.run_if(target_reached),
+			)
+			.add_systems(
+				FixedUpdate,
+				record_splits
+					.after(crate::give_score_when_over_player)
+					.in_set(GameplaySet::Scoring)
+					.run_if(in_time_attack),
+			)
+			.add_systems(
+				Update,
+				update_timer.run_if(in_state(InRun).and(in_time_attack)),
+			);
+	}
+}
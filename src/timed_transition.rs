@@ -0,0 +1,49 @@
+//! Screens that move on by themselves after a while, like the splash screen.
+
+use bevy::{prelude::*, state::state::FreelyMutableState};
+
+use crate::GameStates;
+
+/// Moves the game to `next` once the timer runs out. Goes on the root entity of
+/// a screen, so it's gone along with the screen if the game moves on some
+/// other way first.
+#[derive(Component)]
+pub struct TimedTransition<S: FreelyMutableState> {
+	pub timer: Timer,
+	next: S,
+}
+
+impl<S: FreelyMutableState> TimedTransition<S> {
+	pub fn new(seconds: f32, next: S) -> Self {
+		TimedTransition {
+			timer: Timer::from_seconds(seconds, TimerMode::Once),
+			next,
+		}
+	}
+
+	/// Moves on right away.
+	pub fn skip(&mut self) {
+		let duration = self.timer.duration();
+		self.timer.set_elapsed(duration);
+	}
+}
+
+fn advance_timed_transitions<S: FreelyMutableState>(
+	transitions: Query<&mut TimedTransition<S>>,
+	time: Res<Time>,
+	mut next_state: ResMut<NextState<S>>,
+) {
+	for mut transition in transitions {
+		if transition.timer.tick(time.delta()).finished() {
+			next_state.set(transition.next.clone());
+		}
+	}
+}
+
+pub struct TimedTransitionPlugin;
+
+impl Plugin for TimedTransitionPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(Update, advance_timed_transitions::<GameStates>);
+	}
+}
@@ -0,0 +1,118 @@
+//! Controls and layout for touch screens.
+//!
+//! Tapping anywhere already flaps through [`crate::input::PlayerInput`], the
+//! flap button only gives thumbs an obvious place to rest.
+
+use bevy::{
+	prelude::*,
+	window::{PrimaryWindow, WindowResized},
+};
+
+use crate::{
+	cleanup::CleanupOnExit, locale::Locale, pause::Pause, theme::UiTheme, GameStates, PendingFlap,
+};
+
+pub const IS_MOBILE: bool = cfg!(any(target_os = "android", target_os = "ios"));
+
+const FLAP_BUTTON_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.25);
+
+/// Whether the on-screen flap button is shown during runs.
+#[derive(Resource)]
+pub struct TouchControls {
+	pub flap_button: bool,
+}
+
+impl Default for TouchControls {
+	fn default() -> Self {
+		Self {
+			flap_button: IS_MOBILE,
+		}
+	}
+}
+
+#[derive(Component)]
+struct FlapButton;
+
+fn flap_button_node(window: &Window) -> Node {
+	let portrait = window.height() > window.width();
+	let size = if portrait { 160.0 } else { 120.0 };
+	Node {
+		position_type: PositionType::Absolute,
+		width: Val::Px(size),
+		height: Val::Px(size),
+		bottom: Val::Px(40.0),
+		// Centered for either thumb when upright, under the right thumb otherwise.
+		left: if portrait {
+			Val::Percent(50.0)
+		} else {
+			Val::Auto
+		},
+		right: if portrait { Val::Auto } else { Val::Px(40.0) },
+		margin: if portrait {
+			UiRect::left(Val::Px(-size / 2.0))
+		} else {
+			UiRect::DEFAULT
+		},
+		justify_content: JustifyContent::Center,
+		align_items: AlignItems::Center,
+		..default()
+	}
+}
+
+fn spawn_flap_button(
+	mut commands: Commands,
+	controls: Res<TouchControls>,
+	locale: Res<Locale>,
+	window: Single<&Window, With<PrimaryWindow>>,
+	theme: Res<UiTheme>,
+) {
+	if !controls.flap_button {
+		return;
+	}
+	commands
+		.spawn((
+			FlapButton,
+			CleanupOnExit(GameStates::InGame),
+			Button,
+			flap_button_node(&window),
+			BorderRadius::MAX,
+			BackgroundColor(FLAP_BUTTON_COLOR),
+		))
+		.with_child((Text::new(locale.text("flap-button")), theme.text(32.0)));
+}
+
+fn layout_flap_button(
+	window: Single<&Window, With<PrimaryWindow>>,
+	mut button: Single<&mut Node, With<FlapButton>>,
+) {
+	**button = flap_button_node(&window);
+}
+
+fn flap_on_button(
+	buttons: Query<&Interaction, (Changed<Interaction>, With<FlapButton>)>,
+	mut pending_flap: ResMut<PendingFlap>,
+	time: Res<Time<Real>>,
+) {
+	if buttons
+		.iter()
+		.any(|interaction| *interaction == Interaction::Pressed)
+	{
+		pending_flap.press(time.elapsed_secs());
+	}
+}
+
+pub struct TouchPlugin;
+
+impl Plugin for TouchPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<TouchControls>()
+			.add_systems(OnEnter(GameStates::InGame), spawn_flap_button)
+			.add_systems(
+				Update,
+				(
+					layout_flap_button.run_if(on_event::<WindowResized>),
+					flap_on_button.run_if(in_state(Pause::Running)),
+				),
+			);
+	}
+}
@@ -0,0 +1,98 @@
+//! A trail of fading afterimages behind the bird. They're left where the bird
+//! was and drift off with the pipes, so the trail stretches out the faster the
+//! game goes and follows the bird's ups and downs. Turned off under Motion
+//! trail in the settings, or with reduced motion. Other colors for it are sold
+//! in the [`shop`](crate::shop).
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit,
+	difficulty::Difficulty,
+	mirror::ScrollDirection,
+	save::SaveData,
+	settings::{motion_allowed, Settings},
+	tween::Drift,
+	InRun, Player,
+};
+
+/// Seconds between two afterimages.
+const TRAIL_INTERVAL: f32 = 0.04;
+const TRAIL_LIFETIME: f32 = 0.25;
+/// Alpha of a fresh afterimage, fading out from there.
+const TRAIL_ALPHA: f32 = 0.35;
+
+#[derive(Component)]
+struct Afterimage(Timer);
+
+#[derive(Resource, Deref, DerefMut)]
+struct TrailTimer(Timer);
+
+impl Default for TrailTimer {
+	fn default() -> Self {
+		TrailTimer(Timer::from_seconds(TRAIL_INTERVAL, TimerMode::Repeating))
+	}
+}
+
+fn trail_enabled(settings: Res<Settings>) -> bool {
+	settings.motion_trail
+}
+
+fn leave_afterimages(
+	mut commands: Commands,
+	mut timer: ResMut<TrailTimer>,
+	players: Query<(&Transform, &Sprite), With<Player>>,
+	difficulty: Res<Difficulty>,
+	direction: Res<ScrollDirection>,
+	save: Res<SaveData>,
+	time: Res<Time>,
+) {
+	if !timer.tick(time.delta()).just_finished() {
+		return;
+	}
+	for (transform, sprite) in &players {
+		let mut sprite = sprite.clone();
+		if let Some(color) = save.shop.trail.color() {
+			sprite.color = color;
+		}
+		sprite.color.set_alpha(TRAIL_ALPHA);
+		commands.spawn((
+			Afterimage(Timer::from_seconds(TRAIL_LIFETIME, TimerMode::Once)),
+			CleanupOnExit(InRun),
+			sprite,
+			// Right behind the bird.
+			transform.with_translation(transform.translation - Vec3::Z * 0.1),
+			Drift(Vec2::new(direction.sign() * difficulty.pipe_speed, 0.0)),
+		));
+	}
+}
+
+fn fade_afterimages(
+	mut commands: Commands,
+	afterimages: Query<(Entity, &mut Afterimage, &mut Sprite)>,
+	time: Res<Time>,
+) {
+	for (entity, mut afterimage, mut sprite) in afterimages {
+		if afterimage.0.tick(time.delta()).finished() {
+			commands.entity(entity).despawn();
+		} else {
+			sprite
+				.color
+				.set_alpha(TRAIL_ALPHA * afterimage.0.fraction_remaining());
+		}
+	}
+}
+
+pub struct TrailPlugin;
+
+impl Plugin for TrailPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<TrailTimer>().add_systems(
+			Update,
+			(
+				leave_afterimages.run_if(in_state(InRun).and(trail_enabled).and(motion_allowed)),
+				fade_afterimages,
+			),
+		);
+	}
+}
@@ -0,0 +1,103 @@
+//! Hints shown during the first run, until the player passes a pipe. They're
+//! turned off in the settings once the run is over, so a first daily run isn't
+//! disturbed by the settings changing under it.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit,
+	get_ready::RunPhase,
+	input::{key_name, InputAction},
+	locale::Locale,
+	settings::Settings,
+	theme::UiTheme,
+	GameStates, PipesPassed,
+};
+
+#[derive(Component)]
+struct TutorialText;
+
+/// Set once a pipe was passed with the hints up, to turn them off after the
+/// run.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct TutorialFinished(bool);
+
+fn tutorial_enabled(settings: Res<Settings>) -> bool {
+	settings.show_tutorial
+}
+
+fn spawn_tutorial(mut commands: Commands, theme: Res<UiTheme>) {
+	commands
+		.spawn((
+			Node {
+				position_type: PositionType::Absolute,
+				width: Val::Percent(100.0),
+				bottom: Val::Px(96.0),
+				justify_content: JustifyContent::Center,
+				..default()
+			},
+			CleanupOnExit(GameStates::InGame),
+		))
+		.with_child((
+			TutorialText,
+			Text::default(),
+			theme.text(28.0),
+			TextLayout::new_with_justify(JustifyText::Center),
+		));
+}
+
+fn update_tutorial_text(
+	phase: Res<State<RunPhase>>,
+	settings: Res<Settings>,
+	locale: Res<Locale>,
+	mut text: Single<&mut Text, With<TutorialText>>,
+) {
+	let flap = key_name(settings.controls.key(InputAction::Flap));
+	let restart = key_name(settings.controls.key(InputAction::Restart));
+	text.0 = match phase.get() {
+		RunPhase::GetReady => locale.format("tutorial-get-ready", &[("key", &flap)]),
+		RunPhase::Flying => locale.format("tutorial-flying", &[("key", &restart)]),
+	};
+}
+
+/// The player got the hang of it, no need to show the hints again.
+fn finish_tutorial(
+	mut commands: Commands,
+	pipes_passed: Res<PipesPassed>,
+	mut finished: ResMut<TutorialFinished>,
+	text: Single<&ChildOf, With<TutorialText>>,
+) {
+	if **pipes_passed > 0 {
+		**finished = true;
+		commands.entity(text.parent()).despawn();
+	}
+}
+
+fn turn_off_tutorial(mut finished: ResMut<TutorialFinished>, mut settings: ResMut<Settings>) {
+	if **finished {
+		settings.show_tutorial = false;
+		**finished = false;
+	}
+}
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<TutorialFinished>()
+			.add_systems(
+				OnEnter(GameStates::InGame),
+				spawn_tutorial.run_if(tutorial_enabled),
+			)
+			.add_systems(OnExit(GameStates::InGame), turn_off_tutorial)
+			.add_systems(
+				Update,
+				(
+					update_tutorial_text
+						.run_if(state_changed::<RunPhase>.or(resource_changed::<Locale>)),
+					finish_tutorial.run_if(resource_changed::<PipesPassed>),
+				)
+					.run_if(in_state(GameStates::InGame)),
+			);
+	}
+}
@@ -0,0 +1,93 @@
+//! Small reusable animations for short-lived effects.
+
+use bevy::prelude::*;
+
+/// Moves an entity at a constant speed, in world units per second.
+#[derive(Component, Deref)]
+pub struct Drift(pub Vec2);
+
+/// Fades a sprite, a text or else the background of a node from one alpha to
+/// another, out to nothing unless told otherwise. Gone once invisible, and
+/// left at its final alpha otherwise.
+#[derive(Component)]
+pub struct FadeOut {
+	timer: Timer,
+	from: f32,
+	to: f32,
+	/// Timed in real time, so slow motion doesn't drag the fade out.
+	real_time: bool,
+}
+
+impl FadeOut {
+	pub fn new(seconds: f32) -> Self {
+		FadeOut::between(seconds, 1.0, 0.0)
+	}
+
+	pub fn between(seconds: f32, from: f32, to: f32) -> Self {
+		FadeOut {
+			timer: Timer::from_seconds(seconds, TimerMode::Once),
+			from,
+			to,
+			real_time: false,
+		}
+	}
+
+	pub fn in_real_time(self) -> Self {
+		FadeOut {
+			real_time: true,
+			..self
+		}
+	}
+}
+
+fn drift(query: Query<(&Drift, &mut Transform)>, time: Res<Time>) {
+	for (drift, mut transform) in query {
+		transform.translation += (**drift * time.delta_secs()).extend(0.0);
+	}
+}
+
+fn fade_out(
+	mut commands: Commands,
+	query: Query<(
+		Entity,
+		&mut FadeOut,
+		Option<&mut Sprite>,
+		Option<&mut TextColor>,
+		Option<&mut BackgroundColor>,
+	)>,
+	time: Res<Time>,
+	real_time: Res<Time<Real>>,
+) {
+	for (entity, mut fade, sprite, text_color, background) in query {
+		let delta = if fade.real_time {
+			real_time.delta()
+		} else {
+			time.delta()
+		};
+		if fade.timer.tick(delta).finished() && fade.to <= 0.0 {
+			commands.entity(entity).despawn();
+			continue;
+		}
+		let alpha = fade.from + (fade.to - fade.from) * fade.timer.fraction();
+		if let Some(mut sprite) = sprite {
+			sprite.color.set_alpha(alpha);
+		}
+		// Texts have a transparent background of their own, which stays so.
+		if let Some(mut text_color) = text_color {
+			text_color.0.set_alpha(alpha);
+		} else if let Some(mut background) = background {
+			background.0.set_alpha(alpha);
+		}
+		if fade.timer.finished() {
+			commands.entity(entity).remove::<FadeOut>();
+		}
+	}
+}
+
+pub struct TweenPlugin;
+
+impl Plugin for TweenPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(Update, (drift, fade_out));
+	}
+}
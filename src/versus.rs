@@ -0,0 +1,253 @@
+//! Local versus, started with B from the main menu: two birds share the screen
+//! and fly through the same pipes. Player one flaps with Space or W, player
+//! two with Up or a gamepad. A bird that crashes is left behind on the ground
+//! while the other one keeps scoring, and the run is over once both are down.
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+	cleanup::CleanupOnExit,
+	difficulty::DifficultySettings,
+	get_ready::RunPhase,
+	ground::ground_top,
+	input::{InputAction, PlayerInput},
+	locale::Locale,
+	mirror::ScrollDirection,
+	offscreen::DespawnOffscreen,
+	pause::Pause,
+	play_area::PlayArea,
+	settings::Settings,
+	sprites::Textures,
+	theme::UiTheme,
+	Acceleration, GameMode, GameStates, GameplayEvent, GameplaySet, InRun, PendingFlap,
+	PipesPassed, Player, RunTick, Scrolling, Seat, Velocity, PLAYER_SIZE, SCOREBOARD_TEXT_PADDING,
+};
+
+const PLAYERS: usize = 2;
+const SECOND_COLOR: Color = Color::srgb(1.0, 0.6, 0.6);
+const DOWNED_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+/// Message keys of the controls of each player.
+const CONTROLS: [&str; PLAYERS] = ["versus-controls-1", "versus-controls-2"];
+
+/// A bird that crashed while another was still flying, left behind with the
+/// world scrolling past.
+#[derive(Component)]
+#[require(Scrolling, DespawnOffscreen = DespawnOffscreen::new(PLAYER_SIZE.x))]
+pub(crate) struct Downed;
+
+/// Flaps of the second player, like [`PendingFlap`] is for the first.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct SecondFlap(PendingFlap);
+
+/// How far each player got in the current run.
+#[derive(Resource, Default)]
+pub(crate) struct Versus {
+	pipes: [u32; PLAYERS],
+	/// The tick each player crashed on, if they did before the run was over.
+	downed_at: [Option<u32>; PLAYERS],
+}
+
+impl Versus {
+	/// The last bird flying wins, birds going down together draw.
+	pub fn outcome(&self, locale: &Locale) -> String {
+		let [first, second] = self.downed_at.map(|tick| tick.unwrap_or(u32::MAX));
+		match first.cmp(&second) {
+			std::cmp::Ordering::Greater => locale.format("versus-win", &[("player", &1)]),
+			std::cmp::Ordering::Less => locale.format("versus-win", &[("player", &2)]),
+			std::cmp::Ordering::Equal => locale.text("versus-draw"),
+		}
+	}
+
+	pub fn scores<'a>(&'a self, locale: &'a Locale) -> impl Iterator<Item = String> + 'a {
+		(0..PLAYERS).map(|seat| {
+			locale.format(
+				"versus-score",
+				&[("player", &(seat + 1)), ("pipes", &self.pipes[seat])],
+			)
+		})
+	}
+}
+
+#[derive(Component)]
+struct VersusText(usize);
+
+fn start_versus(
+	mut commands: Commands,
+	textures: Res<Textures>,
+	settings: Res<DifficultySettings>,
+	direction: Res<ScrollDirection>,
+	theme: Res<UiTheme>,
+) {
+	commands.insert_resource(Versus::default());
+	commands.init_resource::<SecondFlap>();
+	let mut sprite = textures.bird_sprite(PLAYER_SIZE);
+	sprite.flip_x = direction.flip_x();
+	sprite.color = SECOND_COLOR;
+	// Right behind the first bird, which starts at the same spot.
+	commands
+		.spawn((
+			crate::make_player(&textures, &settings, *direction),
+			Seat(1),
+		))
+		.insert((
+			sprite,
+			Transform::from_translation(direction.player_start().with_z(-0.5)),
+		));
+	commands
+		.spawn((
+			CleanupOnExit(InRun),
+			Node {
+				position_type: PositionType::Absolute,
+				bottom: SCOREBOARD_TEXT_PADDING,
+				left: SCOREBOARD_TEXT_PADDING,
+				column_gap: Val::Px(32.0),
+				..default()
+			},
+		))
+		.with_children(|parent| {
+			for seat in 0..PLAYERS {
+				parent.spawn((VersusText(seat), Text::default(), theme.text(32.0)));
+			}
+		});
+}
+
+#[derive(SystemParam)]
+struct Flaps<'w> {
+	first: ResMut<'w, PendingFlap>,
+	second: ResMut<'w, SecondFlap>,
+	time: Res<'w, Time<Real>>,
+}
+
+/// Hands out flaps to each player. Before taking off, either player can start
+/// the run for both.
+fn read_flaps(
+	input: PlayerInput,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	gamepads: Query<&Gamepad>,
+	settings: Res<Settings>,
+	phase: Res<State<RunPhase>>,
+	mut flaps: Flaps,
+) {
+	let first = keyboard_input
+		.any_just_pressed([settings.controls.key(InputAction::Flap), KeyCode::KeyW])
+		|| input.pointer_pressed();
+	let second = keyboard_input.just_pressed(KeyCode::ArrowUp)
+		|| gamepads
+			.iter()
+			.any(|gamepad| gamepad.just_pressed(GamepadButton::South));
+	let both = *phase.get() == RunPhase::GetReady && (first || second);
+	let now = flaps.time.elapsed_secs();
+	if first || both {
+		flaps.first.press(now);
+	}
+	if second || both {
+		flaps.second.press(now);
+	}
+}
+
+fn expire_second_flap(mut second_flap: ResMut<SecondFlap>, time: Res<Time<Real>>) {
+	second_flap.expire(time.elapsed_secs());
+}
+
+fn apply_second_flap(
+	settings: Res<DifficultySettings>,
+	mut second_flap: ResMut<SecondFlap>,
+	players: Query<(Entity, &Seat, &mut Velocity, &Acceleration), With<Player>>,
+	mut events: EventWriter<GameplayEvent>,
+) {
+	if !second_flap.take() {
+		return;
+	}
+	for (entity, seat, mut velocity, acceleration) in players {
+		if *seat == Seat(1) {
+			crate::flap(&mut velocity, acceleration, &settings);
+			events.write(GameplayEvent::Flapped(entity));
+		}
+	}
+}
+
+/// Downed birds drop to the ground and lie there.
+fn settle_downed(
+	mut downed: Query<(&mut Transform, &mut Velocity, &mut Sprite), With<Downed>>,
+	play_area: Res<PlayArea>,
+	direction: Res<ScrollDirection>,
+) {
+	let floor = ground_top(&play_area) + PLAYER_SIZE.y / 2.0;
+	for (mut transform, mut velocity, mut sprite) in &mut downed {
+		if transform.translation.y <= floor {
+			transform.translation.y = floor;
+			velocity.y = 0.0;
+		}
+		transform.rotation = Quat::from_rotation_z(crate::player_tilt(velocity.y, *direction));
+		sprite.color = DOWNED_COLOR;
+	}
+}
+
+/// Players score for as long as their bird is in the run.
+fn track_players(
+	mut versus: ResMut<Versus>,
+	pipes_passed: Res<PipesPassed>,
+	run_tick: Res<RunTick>,
+	players: Query<&Seat, With<Player>>,
+	downed: Query<&Seat, With<Downed>>,
+) {
+	for seat in &players {
+		versus.pipes[seat.0] = **pipes_passed;
+	}
+	for seat in &downed {
+		versus.downed_at[seat.0].get_or_insert(**run_tick);
+	}
+}
+
+fn update_versus_text(
+	versus: Res<Versus>,
+	locale: Res<Locale>,
+	mut texts: Query<(&VersusText, &mut Text)>,
+) {
+	for (text, mut content) in &mut texts {
+		let seat = text.0;
+		let key = if versus.downed_at[seat].is_some() {
+			"versus-hud-crashed"
+		} else {
+			"versus-hud"
+		};
+		content.0 = locale.format(
+			key,
+			&[
+				("player", &(seat + 1)),
+				("controls", &locale.text(CONTROLS[seat])),
+				("pipes", &versus.pipes[seat]),
+			],
+		);
+	}
+}
+
+pub struct VersusPlugin;
+
+impl Plugin for VersusPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			OnEnter(GameStates::InGame),
+			start_versus.run_if(resource_equals(GameMode::Versus)),
+		)
+		.add_systems(
+			FixedUpdate,
+			(
+				apply_second_flap.in_set(GameplaySet::Input),
+				settle_downed.in_set(GameplaySet::Collision),
+				track_players.in_set(GameplaySet::Progress),
+			)
+				.run_if(resource_equals(GameMode::Versus)),
+		)
+		.add_systems(
+			Update,
+			(
+				expire_second_flap,
+				read_flaps.run_if(in_state(Pause::Running)),
+				update_versus_text.run_if(resource_changed::<Versus>),
+			)
+				.chain()
+				.run_if(in_state(InRun).and(resource_equals(GameMode::Versus))),
+		);
+	}
+}
@@ -0,0 +1,139 @@
+//! Rain and snow falling across the screen. Each changes the run a little: rain
+//! weighs the bird down, snow slows the pipes.
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	difficulty::Difficulty,
+	mirror::ScrollDirection,
+	play_area::PlayArea,
+	settings::motion_allowed,
+	tween::{Drift, FadeOut},
+	Acceleration, InRun, Player,
+};
+
+/// How much heavier rain makes the bird.
+const RAIN_GRAVITY_SCALE: f32 = 1.05;
+/// How much snow slows the pipes down.
+pub const SNOW_PIPE_SPEED_SCALE: f32 = 0.9;
+
+const RAIN_PER_SECOND: f32 = 120.0;
+const RAIN_SIZE: Vec2 = Vec2::new(2.0, 14.0);
+const RAIN_COLOR: Color = Color::srgba(0.7, 0.8, 1.0, 0.6);
+const RAIN_FALL_SPEED: f32 = 900.0;
+const SNOW_PER_SECOND: f32 = 30.0;
+const SNOW_SIZE: Vec2 = Vec2::splat(5.0);
+const SNOW_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.85);
+const SNOW_FALL_SPEED: f32 = 90.0;
+/// How far each flake drifts sideways on its own, on top of the scrolling.
+const SNOW_SWAY: f32 = 40.0;
+
+/// The weather of the current run. The preferred one is kept in
+/// [`Settings`](crate::settings::Settings), replays bring their own.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Weather {
+	#[default]
+	Clear,
+	Rain,
+	Snow,
+}
+
+impl Weather {
+	pub const ALL: [Weather; 3] = [Weather::Clear, Weather::Rain, Weather::Snow];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Weather::Clear => "Clear",
+			Weather::Rain => "Rain",
+			Weather::Snow => "Snow",
+		}
+	}
+
+	fn particles_per_second(self) -> f32 {
+		match self {
+			Weather::Clear => 0.0,
+			Weather::Rain => RAIN_PER_SECOND,
+			Weather::Snow => SNOW_PER_SECOND,
+		}
+	}
+}
+
+/// Applied as the bird is spawned, before the run's first tick.
+fn weigh_down_in_rain(
+	trigger: Trigger<OnAdd, Player>,
+	weather: Res<Weather>,
+	mut players: Query<&mut Acceleration>,
+) {
+	if *weather != Weather::Rain {
+		return;
+	}
+	if let Ok(mut acceleration) = players.get_mut(trigger.target()) {
+		acceleration.y *= RAIN_GRAVITY_SCALE;
+	}
+}
+
+/// Drops and flakes come in from the top and drift along with the world.
+fn spawn_weather_particles(
+	mut commands: Commands,
+	weather: Res<Weather>,
+	difficulty: Res<Difficulty>,
+	direction: Res<ScrollDirection>,
+	play_area: Res<PlayArea>,
+	time: Res<Time>,
+	mut owed: Local<f32>,
+) {
+	*owed += weather.particles_per_second() * time.delta_secs();
+	let mut rng = rand::rng();
+	let scroll = direction.sign() * difficulty.pipe_speed;
+	while *owed >= 1.0 {
+		*owed -= 1.0;
+		// Also upwind of the screen, since everything drifts sideways.
+		let (from, to) = match *direction {
+			ScrollDirection::Left => (play_area.min.x, play_area.max.x + play_area.width()),
+			ScrollDirection::Right => (play_area.min.x - play_area.width(), play_area.max.x),
+		};
+		let x = rng.random_range(from..to);
+		let position = Vec3::new(x, play_area.max.y, 4.0);
+		let (sprite, velocity) = match *weather {
+			Weather::Clear => return,
+			Weather::Rain => (
+				Sprite::from_color(RAIN_COLOR, RAIN_SIZE),
+				Vec2::new(scroll, -RAIN_FALL_SPEED),
+			),
+			Weather::Snow => (
+				Sprite::from_color(SNOW_COLOR, SNOW_SIZE),
+				Vec2::new(
+					scroll * 0.5 + rng.random_range(-SNOW_SWAY..SNOW_SWAY),
+					-SNOW_FALL_SPEED,
+				),
+			),
+		};
+		// Gone by the time it reaches the bottom.
+		let lifetime = play_area.height() / -velocity.y;
+		commands.spawn((
+			sprite,
+			// Streaks line up with the way they fall.
+			Transform::from_translation(position)
+				.with_rotation(Quat::from_rotation_z(velocity.to_angle() + FRAC_PI_2)),
+			Drift(velocity),
+			FadeOut::new(lifetime),
+		));
+	}
+}
+
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<Weather>()
+			.add_observer(weigh_down_in_rain)
+			.add_systems(
+				Update,
+				spawn_weather_particles.run_if(in_state(InRun).and(motion_allowed)),
+			);
+	}
+}
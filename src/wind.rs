@@ -0,0 +1,182 @@
+//! Gusts of wind pushing the bird around for a couple of seconds, announced by
+//! leaves blowing across the screen and a whoosh just before they hit.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+	loading::Preload,
+	mirror::ScrollDirection,
+	play_area::PlayArea,
+	power_ups::{expire_effects, timed_effect},
+	seed::GameRng,
+	settings::Settings,
+	tween::{Drift, FadeOut},
+	GameScore, GameplayEvent, GameplaySet, InRun, Player, Velocity,
+};
+
+/// Score from which gusts blow. Nothing is drawn from the game's randomness
+/// before then, so the start of a run plays out the same as it did before there
+/// was wind.
+const GUST_START_SCORE: i64 = 5;
+/// Seconds between two gusts, in game time.
+const GUST_INTERVAL: f32 = 9.0;
+/// How long the leaves blow before the gust hits.
+const GUST_WARNING: f32 = 1.0;
+const GUST_DURATION: f32 = 2.0;
+/// Strongest horizontal push, in units per second squared.
+const MAX_GUST_X: f32 = 80.0;
+/// Strongest vertical push, in units per second squared.
+const MAX_GUST_Y: f32 = 500.0;
+/// How quickly the bird drifts back to where it flies once a gust is over.
+const RETURN_RATE: f32 = 1.5;
+
+const LEAF_COUNT: usize = 12;
+const LEAF_SIZE: Vec2 = Vec2::new(10.0, 5.0);
+const LEAF_COLOR: Color = Color::srgb(0.45, 0.7, 0.2);
+const LEAF_SPEED: f32 = 900.0;
+const LEAF_DURATION: f32 = 1.6;
+
+/// Pushes the bird for as long as it lasts, in units per second squared.
+#[derive(Component, Deref)]
+pub struct Gust(Vec2);
+
+/// Counts down to the next gust, and then to it hitting.
+#[derive(Resource)]
+struct GustSchedule {
+	timer: Timer,
+	/// A gust on its way, to hit once the timer runs out again.
+	coming: Option<Vec2>,
+}
+
+impl Default for GustSchedule {
+	fn default() -> Self {
+		GustSchedule {
+			timer: Timer::from_seconds(GUST_INTERVAL, TimerMode::Once),
+			coming: None,
+		}
+	}
+}
+
+#[derive(Resource)]
+struct WindSound(Handle<AudioSource>);
+
+impl FromWorld for WindSound {
+	fn from_world(world: &mut World) -> Self {
+		let handle = world.resource::<AssetServer>().load("sounds/wind.wav");
+		Preload::add(world, [handle.clone().untyped()]);
+		WindSound(handle)
+	}
+}
+
+fn reset_gusts(mut schedule: ResMut<GustSchedule>) {
+	*schedule = GustSchedule::default();
+}
+
+fn schedule_gusts(
+	mut commands: Commands,
+	mut schedule: ResMut<GustSchedule>,
+	score: Res<GameScore>,
+	time: Res<Time>,
+	player: Single<Entity, With<Player>>,
+	mut game_rng: ResMut<GameRng>,
+	mut events: EventWriter<GameplayEvent>,
+) {
+	if **score < GUST_START_SCORE || !schedule.timer.tick(time.delta()).finished() {
+		return;
+	}
+	if let Some(force) = schedule.coming.take() {
+		commands
+			.entity(*player)
+			.insert(timed_effect(Gust(force), GUST_DURATION));
+		schedule.timer = Timer::from_seconds(GUST_INTERVAL, TimerMode::Once);
+	} else {
+		let force = Vec2::new(
+			game_rng.random_range(-MAX_GUST_X..MAX_GUST_X),
+			game_rng.random_range(-MAX_GUST_Y..MAX_GUST_Y),
+		);
+		schedule.coming = Some(force);
+		schedule.timer = Timer::from_seconds(GUST_WARNING, TimerMode::Once);
+		events.write(GameplayEvent::WindRising(force));
+	}
+}
+
+/// Gusts push the bird off its line, which it drifts back to once they are
+/// over.
+fn blow_player(
+	player: Single<(&Transform, &mut Velocity, Option<&Gust>), With<Player>>,
+	direction: Res<ScrollDirection>,
+	time: Res<Time>,
+) {
+	let (transform, mut velocity, gust) = player.into_inner();
+	match gust {
+		Some(gust) => {
+			velocity.x += gust.x * time.delta_secs();
+			velocity.y += gust.y * time.delta_secs();
+		}
+		None => {
+			velocity.x = (direction.player_start().x - transform.translation.x) * RETURN_RATE;
+		}
+	}
+}
+
+/// Leaves blow across the screen the way the coming gust pushes.
+fn announce_gusts(
+	mut commands: Commands,
+	mut events: EventReader<GameplayEvent>,
+	play_area: Res<PlayArea>,
+	settings: Res<Settings>,
+	sound: Res<WindSound>,
+) {
+	for event in events.read() {
+		let GameplayEvent::WindRising(force) = event else {
+			continue;
+		};
+		commands.spawn((AudioPlayer::new(sound.0.clone()), PlaybackSettings::DESPAWN));
+		// Mostly sideways, so the leaves cross the screen whichever way it blows.
+		let heading =
+			Vec2::new(if force.x < 0.0 { -1.0 } else { 1.0 }, force.y / MAX_GUST_Y).normalize();
+		let start_x = if heading.x < 0.0 {
+			play_area.max.x
+		} else {
+			play_area.min.x
+		};
+		let mut rng = rand::rng();
+		for _ in 0..LEAF_COUNT {
+			let position = Vec2::new(
+				start_x - heading.x * rng.random_range(0.0..play_area.width() / 2.0),
+				rng.random_range(play_area.min.y..play_area.max.y),
+			);
+			let mut leaf = commands.spawn((
+				Sprite::from_color(LEAF_COLOR, LEAF_SIZE),
+				Transform::from_translation(position.extend(5.0))
+					.with_rotation(Quat::from_rotation_z(heading.to_angle())),
+				FadeOut::new(LEAF_DURATION),
+			));
+			if !settings.reduced_motion {
+				leaf.insert(Drift(heading * LEAF_SPEED));
+			}
+		}
+	}
+}
+
+pub struct WindPlugin;
+
+impl Plugin for WindPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<GustSchedule>()
+			.init_resource::<WindSound>()
+			.add_systems(OnEnter(InRun), reset_gusts)
+			.add_systems(
+				FixedUpdate,
+				(
+					blow_player
+						.after(crate::apply_acceleration)
+						.before(crate::apply_velocity)
+						.in_set(GameplaySet::Physics),
+					(schedule_gusts, expire_effects::<Gust>).in_set(GameplaySet::Progress),
+				),
+			)
+			.add_systems(Update, announce_gusts.run_if(in_state(InRun)));
+	}
+}
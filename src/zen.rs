@@ -0,0 +1,102 @@
+//! Zen mode, for practicing without crashing. The bird flies through pipes and
+//! bounces off the ground, and every miss is counted instead.
+
+use bevy::prelude::*;
+
+use crate::{
+	cleanup::CleanupOnExit, loading::Preload, locale::Locale, theme::UiTheme, GameMode, GameStates,
+	GameplayEvent, InRun, Pause,
+};
+
+/// How long the bird passes through pipes after flying into one, long enough
+/// to clear it without counting it twice.
+pub const ZEN_MISS_GRACE: f32 = 0.3;
+
+/// Misses in the current zen run.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct Misses(u32);
+
+#[derive(Resource)]
+struct MissSound(Handle<AudioSource>);
+
+impl FromWorld for MissSound {
+	fn from_world(world: &mut World) -> Self {
+		let handle = world.resource::<AssetServer>().load("sounds/miss.wav");
+		Preload::add(world, [handle.clone().untyped()]);
+		MissSound(handle)
+	}
+}
+
+#[derive(Component)]
+struct MissesText;
+
+fn start_zen_run(mut commands: Commands, mut misses: ResMut<Misses>, theme: Res<UiTheme>) {
+	**misses = 0;
+	commands.spawn((
+		MissesText,
+		CleanupOnExit(InRun),
+		Text::default(),
+		theme.text(28.0),
+		Node {
+			position_type: PositionType::Absolute,
+			bottom: Val::Px(5.0),
+			right: Val::Px(5.0),
+			..default()
+		},
+	));
+}
+
+fn count_misses(
+	mut commands: Commands,
+	mut events: EventReader<GameplayEvent>,
+	mut misses: ResMut<Misses>,
+	sound: Res<MissSound>,
+) {
+	for event in events.read() {
+		if matches!(event, GameplayEvent::Missed) {
+			**misses += 1;
+			commands.spawn((AudioPlayer::new(sound.0.clone()), PlaybackSettings::DESPAWN));
+		}
+	}
+}
+
+fn update_misses_text(
+	misses: Res<Misses>,
+	locale: Res<Locale>,
+	mut text: Single<&mut Text, With<MissesText>>,
+) {
+	text.0 = locale.format("zen-misses", &[("misses", &**misses)]);
+}
+
+/// Zen runs don't end on their own.
+fn finish_on_q(
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut next_state: ResMut<NextState<GameStates>>,
+) {
+	if keyboard_input.just_pressed(KeyCode::KeyQ) {
+		next_state.set(GameStates::GameOver);
+	}
+}
+
+pub struct ZenPlugin;
+
+impl Plugin for ZenPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<Misses>()
+			.init_resource::<MissSound>()
+			.add_systems(
+				OnEnter(InRun),
+				start_zen_run.run_if(resource_equals(GameMode::Zen)),
+			)
+			.add_systems(
+				Update,
+				(
+					count_misses,
+					update_misses_text.run_if(resource_changed::<Misses>),
+					finish_on_q.run_if(in_state(Pause::Running)),
+				)
+					.chain()
+					.run_if(resource_equals(GameMode::Zen).and(in_state(InRun))),
+			);
+	}
+}
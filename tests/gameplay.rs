@@ -0,0 +1,79 @@
+//! Plays the game headless through [`TestGame`] to check the rules of a run
+//! hold up.
+
+use flappy::TestGame;
+
+const SEED: u64 = 1234;
+/// Far longer than it takes for anything checked here to happen.
+const MAX_TICKS: u32 = 60 * 60;
+
+fn game_in_run() -> TestGame {
+	let mut game = TestGame::new(SEED);
+	game.start_run();
+	game
+}
+
+/// Flies through the middle of every gap until `pipes` have been passed.
+fn pass_pipes(game: &mut TestGame, pipes: u32) {
+	let passed = game.tick_until(MAX_TICKS, |game| {
+		if let Some(gap) = game.next_gap() {
+			game.hold_bird_at(gap.y);
+		}
+		game.pipes_passed() >= pipes
+	});
+	assert!(passed, "only passed {} pipes", game.pipes_passed());
+}
+
+#[test]
+fn hitting_a_pipe_ends_the_run() {
+	let mut game = game_in_run();
+	assert!(game.tick_until(MAX_TICKS, |game| game.any_pipe().is_some()));
+	let pipe = game.any_pipe().unwrap();
+	game.move_bird(pipe);
+	game.tick();
+	assert!(game.is_game_over());
+}
+
+#[test]
+fn falling_to_the_ground_ends_the_run() {
+	let mut game = game_in_run();
+	assert!(game.tick_until(MAX_TICKS, |game| game.is_game_over()));
+	assert_eq!(game.score(), 0);
+}
+
+#[test]
+fn passing_a_pipe_scores_once() {
+	let mut game = game_in_run();
+	pass_pipes(&mut game, 1);
+	assert_eq!(game.score(), 1);
+	// Staying in line with the pipe just passed doesn't score it again.
+	for _ in 0..10 {
+		game.tick();
+	}
+	assert_eq!(game.pipes_passed(), 1);
+	assert_eq!(game.score(), 1);
+	assert!(!game.is_game_over());
+}
+
+#[test]
+fn flapping_lifts_the_bird() {
+	let mut game = game_in_run();
+	game.hold_bird_at(0.0);
+	game.flap();
+	assert!(game.bird_position().unwrap().y > 0.0);
+}
+
+#[test]
+fn restarting_resets_the_run() {
+	let mut game = game_in_run();
+	pass_pipes(&mut game, 2);
+	assert!(game.tick_until(MAX_TICKS, |game| game.is_game_over()));
+	game.tick();
+	game.start_run();
+	assert_eq!(game.score(), 0);
+	assert_eq!(game.pipes_passed(), 0);
+	assert!(game.run_tick() <= 1);
+	assert_eq!(game.birds(), 1);
+	assert_eq!(game.pipes_in_play(), 0);
+	assert!(!game.is_game_over());
+}